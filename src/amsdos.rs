@@ -0,0 +1,328 @@
+use crate::dsk::Dsk;
+
+///////////////////////
+//
+// AMSDOS - the disc filing system's directory and files
+//
+///////////////////////
+
+// AMSDOS discs have 9 sectors of 512 bytes per track and allocate space in 1K blocks, counted from
+// the first track after any reserved ones. The directory is the first two blocks: 64 entries of
+// 32 bytes. Data format discs number their sectors #C1-#C9 and reserve no tracks; system format
+// discs number them #41-#49 and reserve two tracks for CP/M.
+const SECTORS_PER_TRACK: usize = 9;
+const SECTORS_PER_BLOCK: usize = 2;
+const DIRECTORY_BLOCKS: usize = 2;
+const DIRECTORY_ENTRY_SIZE: usize = 32;
+// A directory entry's RC counts 128-byte records.
+const RECORD_SIZE: usize = 128;
+// Entries with this user number are deleted files or were never used.
+const DELETED: u8 = 0xE5;
+
+struct DiscFormat {
+    first_sector_id: u8,
+    reserved_tracks: u8
+}
+
+impl DiscFormat {
+    // Tells the format apart by the sector IDs on the first track.
+    fn detect(dsk: &Dsk) -> Option<DiscFormat> {
+        let (id, _) = *dsk.sectors(0, 0).first()?;
+        match id.r & 0xC0 {
+            0xC0 => Some(DiscFormat { first_sector_id: 0xC1, reserved_tracks: 0 }),
+            0x40 => Some(DiscFormat { first_sector_id: 0x41, reserved_tracks: 2 }),
+            _ => None
+        }
+    }
+
+    fn read_block(&self, dsk: &Dsk, block: u8) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for x in 0..SECTORS_PER_BLOCK {
+            let sector = block as usize * SECTORS_PER_BLOCK + x;
+            let track = self.reserved_tracks + (sector / SECTORS_PER_TRACK) as u8;
+            let sector_id = self.first_sector_id + (sector % SECTORS_PER_TRACK) as u8;
+            bytes.extend_from_slice(dsk.read_sector(track, 0, sector_id).unwrap_or(&[]));
+        }
+        bytes
+    }
+}
+
+// A file in the directory, gathered from all of its entries ("extents"), each of which holds up
+// to 16K.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmsdosEntry {
+    pub user: u8,
+    pub name: String,
+    pub extension: String,
+    // Attributes are kept in the top bits of the extension's first two characters.
+    pub read_only: bool,
+    pub system: bool,
+    // The blocks holding the file's data, in order across its extents.
+    pub blocks: Vec<u8>,
+    pub records: usize
+}
+
+impl AmsdosEntry {
+    // NAME.EXT, or just NAME when there's no extension.
+    pub fn file_name(&self) -> String {
+        if self.extension.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}.{}", self.name, self.extension)
+        }
+    }
+
+    // The space the file takes on the disc, in bytes. Its data may be shorter; see AmsdosHeader.
+    pub fn size(&self) -> usize {
+        self.records * RECORD_SIZE
+    }
+}
+
+// One 32-byte directory entry:
+//   00     user number, or #E5 if unused
+//   01-08  name, padded with spaces
+//   09-0B  extension, with the read only and system attributes in bit 7 of 09 and 0A
+//   0C     extent number
+//   0F     records used in this extent
+//   10-1F  block numbers, 0 when unused
+struct DirectoryEntry {
+    user: u8,
+    name: String,
+    extension: String,
+    read_only: bool,
+    system: bool,
+    extent: u8,
+    records: u8,
+    blocks: Vec<u8>
+}
+
+impl DirectoryEntry {
+    fn from_bytes(bytes: &[u8]) -> Option<DirectoryEntry> {
+        let user = bytes[0x00];
+        if user == DELETED || user > 15 {
+            return None;
+        }
+        let text = |range: &[u8]| -> String {
+            range.iter().map(|byte| (byte & 0x7F) as char).collect::<String>().trim_end().to_string()
+        };
+        Some(DirectoryEntry {
+            user,
+            name: text(&bytes[0x01..0x09]),
+            extension: text(&bytes[0x09..0x0C]),
+            read_only: bytes[0x09] & 0x80 != 0,
+            system: bytes[0x0A] & 0x80 != 0,
+            extent: bytes[0x0C],
+            records: bytes[0x0F],
+            blocks: bytes[0x10..0x20].iter().copied().filter(|block| *block != 0).collect()
+        })
+    }
+}
+
+// The first 128 bytes of BASIC and binary files. ASCII files have no header, so a header is only
+// taken to be there if its checksum, the sum of bytes 00-42, matches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmsdosHeader {
+    pub file_type: FileType,
+    pub load_address: u16,
+    pub entry_address: u16,
+    pub length: usize
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileType {
+    Basic,
+    ProtectedBasic,
+    Binary,
+    Other(u8)
+}
+
+pub const HEADER_SIZE: usize = 128;
+
+impl AmsdosHeader {
+    pub fn from_bytes(bytes: &[u8]) -> Option<AmsdosHeader> {
+        if bytes.len() < HEADER_SIZE {
+            return None;
+        }
+        let checksum: u16 = bytes[..0x43].iter().map(|byte| *byte as u16).fold(0, u16::wrapping_add);
+        if checksum != u16::from_le_bytes([bytes[0x43], bytes[0x44]]) {
+            return None;
+        }
+        let file_type = match bytes[0x12] {
+            0 => FileType::Basic,
+            1 => FileType::ProtectedBasic,
+            2 => FileType::Binary,
+            other => FileType::Other(other)
+        };
+        Some(AmsdosHeader {
+            file_type,
+            load_address: u16::from_le_bytes([bytes[0x15], bytes[0x16]]),
+            entry_address: u16::from_le_bytes([bytes[0x1A], bytes[0x1B]]),
+            length: u32::from_le_bytes([bytes[0x40], bytes[0x41], bytes[0x42], 0]) as usize
+        })
+    }
+}
+
+impl Dsk {
+    // The files on an AMSDOS disc, in directory order. Empty if the disc isn't in an AMSDOS format.
+    pub fn catalog(&self) -> Vec<AmsdosEntry> {
+        let format = match DiscFormat::detect(self) {
+            Some(format) => format,
+            None => return Vec::new()
+        };
+        let mut directory = Vec::new();
+        for block in 0..DIRECTORY_BLOCKS {
+            directory.extend(format.read_block(self, block as u8));
+        }
+        let mut entries: Vec<DirectoryEntry> = directory.chunks_exact(DIRECTORY_ENTRY_SIZE).filter_map(DirectoryEntry::from_bytes).collect();
+        // Extents can be anywhere in the directory; the file's first one gives its place.
+        entries.sort_by_key(|entry| entry.extent);
+
+        let mut files: Vec<AmsdosEntry> = Vec::new();
+        for entry in entries {
+            match files.iter_mut().find(|file| file.user == entry.user && file.name == entry.name && file.extension == entry.extension) {
+                Some(file) => {
+                    file.blocks.extend(entry.blocks);
+                    file.records += entry.records as usize;
+                }
+                None => files.push(AmsdosEntry {
+                    user: entry.user, name: entry.name, extension: entry.extension, read_only: entry.read_only,
+                    system: entry.system, blocks: entry.blocks, records: entry.records as usize
+                })
+            }
+        }
+        files
+    }
+
+    // A file's contents, looked up by NAME.EXT ignoring case. Files with an AMSDOS header come back
+    // without it and cut to the length it gives. Others are whole records, so ASCII files run to
+    // their end of file character (#1A) and beyond.
+    pub fn read_file(&self, name: &str) -> Option<Vec<u8>> {
+        let format = DiscFormat::detect(self)?;
+        let entry = self.catalog().into_iter().find(|entry| entry.file_name().eq_ignore_ascii_case(name))?;
+        let mut bytes = Vec::new();
+        for block in &entry.blocks {
+            bytes.extend(format.read_block(self, *block));
+        }
+        bytes.truncate(entry.size());
+        match AmsdosHeader::from_bytes(&bytes) {
+            Some(header) => {
+                let end = (HEADER_SIZE + header.length).min(bytes.len());
+                Some(bytes[HEADER_SIZE..end].to_vec())
+            }
+            None => Some(bytes)
+        }
+    }
+
+    // The AMSDOS header at the start of a file, if it has one.
+    pub fn file_header(&self, name: &str) -> Option<AmsdosHeader> {
+        let entry = self.catalog().into_iter().find(|entry| entry.file_name().eq_ignore_ascii_case(name))?;
+        let format = DiscFormat::detect(self)?;
+        AmsdosHeader::from_bytes(&format.read_block(self, *entry.blocks.first()?))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::dsk::{Dsk, tests::normal_image};
+    use super::{AmsdosHeader, FileType, HEADER_SIZE};
+
+    // A blank data format disc: every sector of every track filled with #E5, as FORMAT leaves it.
+    fn blank_disc(tracks: u8) -> Dsk {
+        Dsk::init_from_bytes(&normal_image(tracks, 9, Some(0xE5))).unwrap()
+    }
+
+    // Writes bytes into consecutive 1K blocks starting at first_block, on a data format disc.
+    fn write_blocks(dsk: &mut Dsk, first_block: u8, bytes: &[u8]) {
+        for (x, chunk) in bytes.chunks(512).enumerate() {
+            let sector = first_block as usize * 2 + x;
+            let mut data = [0; 512];
+            data[..chunk.len()].copy_from_slice(chunk);
+            dsk.write_sector((sector / 9) as u8, 0, 0xC1 + (sector % 9) as u8, &data).unwrap();
+        }
+    }
+
+    fn directory_entry(user: u8, name: &str, extent: u8, records: u8, blocks: &[u8]) -> [u8; 32] {
+        let mut entry = [0; 32];
+        entry[0] = user;
+        let (name, extension) = name.split_once('.').unwrap_or((name, ""));
+        entry[1..12].copy_from_slice(format!("{:8}{:3}", name, extension).as_bytes());
+        entry[0x0C] = extent;
+        entry[0x0F] = records;
+        entry[0x10..0x10 + blocks.len()].copy_from_slice(blocks);
+        entry
+    }
+
+    pub fn header(file_type: u8, load_address: u16, entry_address: u16, length: usize) -> Vec<u8> {
+        let mut header = vec![0; HEADER_SIZE];
+        header[0x12] = file_type;
+        header[0x15..0x17].copy_from_slice(&load_address.to_le_bytes());
+        header[0x1A..0x1C].copy_from_slice(&entry_address.to_le_bytes());
+        header[0x40..0x43].copy_from_slice(&(length as u32).to_le_bytes()[..3]);
+        let checksum: u16 = header[..0x43].iter().map(|byte| *byte as u16).sum();
+        header[0x43..0x45].copy_from_slice(&checksum.to_le_bytes());
+        header
+    }
+
+    // A data format disc with:
+    //   HELLO.BIN  a 1500 byte binary file loading at #4000, in blocks 2 and 3
+    //   README.TXT an ASCII file with no header, in block 4
+    //   BIG.DAT    17K over two extents, the second listed first, in blocks 5 to 21. Each 1K
+    //              is filled with its number, counting from 1.
+    //   OLD.BAK    deleted
+    pub fn sample_disc() -> Dsk {
+        let mut dsk = blank_disc(6);
+        let mut directory = Vec::new();
+        directory.extend(directory_entry(0, "HELLO.BIN", 0, 13, &[2, 3]));
+        directory.extend(directory_entry(0, "BIG.DAT", 1, 8, &[21]));
+        directory.extend(directory_entry(0xE5, "OLD.BAK", 0, 8, &[6]));
+        directory.extend(directory_entry(0, "README.TXT", 0, 1, &[4]));
+        directory.extend(directory_entry(0, "BIG.DAT", 0, 0x80, &(5..21).collect::<Vec<u8>>()));
+        directory.resize(2048, 0xE5);
+        write_blocks(&mut dsk, 0, &directory);
+
+        let mut hello = header(2, 0x4000, 0, 1500);
+        hello.extend((0..1500).map(|x| x as u8));
+        write_blocks(&mut dsk, 2, &hello);
+        write_blocks(&mut dsk, 4, b"10 PRINT \"HI\"\r\n\x1A");
+        // Filled from 1: a run of zeros would checksum as a header, as it would to AMSDOS.
+        let big: Vec<u8> = (0..17 * 1024).map(|x| (x / 1024 + 1) as u8).collect();
+        write_blocks(&mut dsk, 5, &big);
+        dsk
+    }
+
+    #[test]
+    fn catalog_lists_files() {
+        let catalog = sample_disc().catalog();
+        let names: Vec<String> = catalog.iter().map(|entry| entry.file_name()).collect();
+        assert_eq!(names, ["HELLO.BIN", "README.TXT", "BIG.DAT"]);
+        assert_eq!(catalog[0].size(), 13 * 128);
+        assert_eq!(catalog[2].blocks, (5..22).collect::<Vec<u8>>());
+        assert_eq!(catalog[2].size(), 17 * 1024);
+    }
+
+    #[test]
+    fn read_file_strips_header() {
+        let dsk = sample_disc();
+        let hello = dsk.read_file("hello.bin").unwrap();
+        assert_eq!(hello.len(), 1500);
+        assert_eq!(hello[..3], [0, 1, 2]);
+        assert_eq!(hello[1499], (1499 % 256) as u8);
+
+        let header = dsk.file_header("HELLO.BIN").unwrap();
+        assert_eq!(header, AmsdosHeader { file_type: FileType::Binary, load_address: 0x4000, entry_address: 0, length: 1500 });
+    }
+
+    #[test]
+    fn read_file_follows_extents_and_keeps_headerless_files_whole() {
+        let dsk = sample_disc();
+        let big = dsk.read_file("BIG.DAT").unwrap();
+        assert_eq!(big.len(), 17 * 1024);
+        assert_eq!(big[16 * 1024], 17);
+
+        let readme = dsk.read_file("README.TXT").unwrap();
+        assert_eq!(readme.len(), 128);
+        assert!(readme.starts_with(b"10 PRINT"));
+
+        assert_eq!(dsk.read_file("OLD.BAK"), None);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::memory::PortDevice;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+///////////////////////
+//
+// CRTC 6845 - screen geometry and video memory addressing
+//
+///////////////////////
+
+// The CRTC is reached through #BCxx to #BFxx, with A9 and A8 choosing the function:
+//   #BCxx - select the register that following reads and writes use
+//   #BDxx - write the selected register
+//   #BExx - status (not present on the common type 0 CRTC)
+//   #BFxx - read the selected register
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Crtc {
+    selected: usize,
+    registers: [u8; 18],
+    // The raster position: the scanline within the frame, and cycles into that scanline.
+    scanline: u16,
+    line_cycles: u32
+}
+
+// Registers are narrower than a byte in places; the unused high bits are dropped on write.
+const REGISTER_MASKS: [u8; 18] = [0xFF, 0xFF, 0xFF, 0xFF, 0x7F, 0x1F, 0x7F, 0x7F, 0xFF, 0x1F, 0x7F, 0x1F, 0x3F, 0xFF, 0x3F, 0xFF, 0x3F, 0xFF];
+
+// The values the firmware programs at start up: a 40x25 character screen at #C000.
+const FIRMWARE_DEFAULTS: [u8; 18] = [63, 40, 46, 0x8E, 38, 0, 25, 30, 0, 7, 0, 0, 0x30, 0x00, 0, 0, 0, 0];
+
+impl Crtc {
+    pub fn default() -> Crtc {
+        Crtc { selected: 0, registers: FIRMWARE_DEFAULTS, scanline: 0, line_cycles: 0 }
+    }
+
+    pub fn select(&mut self, register: u8) {
+        self.selected = (register & 0x1F) as usize;
+    }
+
+    // Writes to the selected register. Selecting a register above R17 makes writes do nothing.
+    pub fn write_selected(&mut self, value: u8) {
+        if self.selected < self.registers.len() {
+            self.registers[self.selected] = value & REGISTER_MASKS[self.selected];
+        }
+    }
+
+    // Only R12 to R17 can be read back; everything else reads as 0.
+    pub fn read_selected(&self) -> u8 {
+        match self.selected {
+            12..=17 => self.registers[self.selected],
+            _ => 0
+        }
+    }
+
+    pub fn selected(&self) -> u8 {
+        self.selected as u8
+    }
+
+    pub fn register(&self, index: usize) -> u8 {
+        self.registers[index]
+    }
+
+    // R1: characters displayed per line.
+    pub fn horizontal_displayed(&self) -> u8 {
+        self.registers[1]
+    }
+
+    // R6: character rows displayed.
+    pub fn vertical_displayed(&self) -> u8 {
+        self.registers[6]
+    }
+
+    // R9: the last scanline of each character row, so rows are this plus one lines tall.
+    pub fn max_raster_address(&self) -> u8 {
+        self.registers[9]
+    }
+
+    // Moves the raster on by the cycles just emulated. Each character takes 1us, or 4 cycles, and a
+    // line is R0 + 1 characters. A frame is R4 + 1 character rows of R9 + 1 lines, plus R5 more.
+    pub fn add_cycles(&mut self, cycles: u16) {
+        let line_length = (self.registers[0] as u32 + 1) * 4;
+        let frame_lines = (self.registers[4] as u16 + 1) * (self.max_raster_address() as u16 + 1) + self.registers[5] as u16;
+        self.line_cycles += cycles as u32;
+        while self.line_cycles >= line_length {
+            self.line_cycles -= line_length;
+            self.scanline += 1;
+            if self.scanline >= frame_lines {
+                self.scanline = 0;
+            }
+        }
+    }
+
+    // VSYNC starts at the top of character row R7 and lasts for the number of lines in the top
+    // four bits of R3, where 0 means 16.
+    pub fn vsync(&self) -> bool {
+        let start = self.registers[7] as u16 * (self.max_raster_address() as u16 + 1);
+        let length = match self.registers[3] >> 4 {
+            0 => 16,
+            lines => lines as u16
+        };
+        (start..start + length).contains(&self.scanline)
+    }
+
+    // R12 (high) and R13 (low) together. This is in CRTC terms: see screen.rs for how it
+    // maps onto video memory.
+    pub fn screen_start(&self) -> u16 {
+        ((self.registers[12] as u16) << 8) | self.registers[13] as u16
+    }
+}
+
+impl PortDevice for Crtc {
+    fn write(&mut self, port: u16, value: u8) {
+        match (port >> 8) & 0x03 {
+            0 => self.select(value),
+            1 => self.write_selected(value),
+            _ => {}
+        }
+    }
+
+    fn read(&mut self, port: u16) -> Option<u8> {
+        match (port >> 8) & 0x03 {
+            3 => Some(self.read_selected()),
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::memory::PortDevice;
+    use super::Crtc;
+
+    #[test]
+    fn screen_start_write_and_read_back() {
+        let mut crtc = Crtc::default();
+        PortDevice::write(&mut crtc, 0xBC00, 12);
+        PortDevice::write(&mut crtc, 0xBD00, 0x10);
+        PortDevice::write(&mut crtc, 0xBC00, 13);
+        PortDevice::write(&mut crtc, 0xBD00, 0x80);
+
+        assert_eq!(crtc.screen_start(), 0x1080);
+        assert_eq!(PortDevice::read(&mut crtc, 0xBF00), Some(0x80));
+        PortDevice::write(&mut crtc, 0xBC00, 12);
+        assert_eq!(PortDevice::read(&mut crtc, 0xBF00), Some(0x10));
+    }
+
+    #[test]
+    fn vsync_lasts_eight_lines_once_a_frame() {
+        // The firmware's 64us lines, 312 lines a frame and VSYNC from line 240 for 8 lines.
+        let mut crtc = Crtc::default();
+        let mut vsync_lines = Vec::new();
+        for line in 0..624 {
+            if crtc.vsync() {
+                vsync_lines.push(line);
+            }
+            crtc.add_cycles(256);
+        }
+        assert_eq!(vsync_lines, [240, 241, 242, 243, 244, 245, 246, 247, 552, 553, 554, 555, 556, 557, 558, 559]);
+    }
+
+    #[test]
+    fn write_only_registers_read_as_zero() {
+        let mut crtc = Crtc::default();
+        crtc.select(1);
+        crtc.write_selected(32);
+        assert_eq!(crtc.horizontal_displayed(), 32);
+        assert_eq!(crtc.read_selected(), 0);
+    }
+
+    #[test]
+    fn writes_are_masked_to_register_width() {
+        let mut crtc = Crtc::default();
+        crtc.select(12);
+        crtc.write_selected(0xFF);
+        assert_eq!(crtc.register(12), 0x3F);
+
+        // There's no R20, so nothing changes.
+        crtc.select(20);
+        crtc.write_selected(0x55);
+        assert_eq!(crtc.read_selected(), 0);
+    }
+
+    #[test]
+    fn defaults_match_firmware_screen() {
+        let crtc = Crtc::default();
+        assert_eq!(crtc.horizontal_displayed(), 40);
+        assert_eq!(crtc.vertical_displayed(), 25);
+        assert_eq!(crtc.max_raster_address(), 7);
+        assert_eq!(crtc.screen_start(), 0x3000);
+    }
+}
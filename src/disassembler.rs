@@ -0,0 +1,121 @@
+///////////////////////
+//
+// Disassembler - decodes memory into Z80 assembly
+//
+///////////////////////
+
+use crate::memory::Memory;
+use crate::instruction_set::{InstructionSet, Operands, OperandKind, render, index::IndexRegister};
+use crate::utils::{combine_to_double_byte, signed};
+
+// Decodes count instructions starting at start, returning the address, machine code and
+// assembly of each. Memory is read as the CPU would see it, so enabled ROMs show through.
+pub fn disassemble(mem: &Memory, start: u16, count: usize) -> Vec<(u16, String, String)> {
+    disassemble_with(&InstructionSet::default(), mem, start, count)
+}
+
+// As disassemble, but reuses an existing instruction set rather than building a new one.
+pub fn disassemble_with(instruction_set: &InstructionSet, mem: &Memory, start: u16, count: usize) -> Vec<(u16, String, String)> {
+    let mut addr = start;
+    let mut lines = Vec::with_capacity(count);
+    for _ in 0..count {
+        let (line, length) = decode_at(instruction_set, mem, addr);
+        lines.push(line);
+        addr = addr.wrapping_add(length);
+    }
+    lines
+}
+
+// Decodes the instruction at addr the same way Runtime::execute_instruction does, returning its
+// line and length in bytes. Opcodes with no implementation come out as DEFB so decoding can carry on.
+fn decode_at(instruction_set: &InstructionSet, mem: &Memory, addr: u16) -> ((u16, String, String), u16) {
+    let byte_at = |offset: u16| mem.read_u8(addr.wrapping_add(offset));
+    // The offset of the opcode from addr, and the displacement of a DD CB or FD CB instruction,
+    // which comes before its opcode.
+    let (decoded, opcode_offset, displacement) = match byte_at(0) {
+        0xCB => (instruction_set.bit_instruction_for(byte_at(1)), 1, None),
+        0xED => (instruction_set.extended_instruction_for(byte_at(1)), 1, None),
+        prefix @ (0xDD | 0xFD) => {
+            let index = if prefix == 0xDD { IndexRegister::IX } else { IndexRegister::IY };
+            if byte_at(1) == 0xCB {
+                (instruction_set.indexed_bit_instruction_for(index, byte_at(3)), 3, Some(signed(byte_at(2))))
+            } else {
+                (instruction_set.index_instruction_for(index, byte_at(1)), 1, None)
+            }
+        }
+        opcode => (instruction_set.instruction_for(opcode), 0, None)
+    };
+    let instruction = match decoded {
+        Ok(instruction) => instruction,
+        Err(_) => {
+            let bytes: Vec<u8> = (0..=opcode_offset).map(byte_at).collect();
+            let machine_code = bytes.iter().map(|byte| format!("{:0>2X}", byte)).collect::<Vec<_>>().join(" ");
+            let assembly = format!("DEFB {}", bytes.iter().map(|byte| format!("#{:0>2X}", byte)).collect::<Vec<_>>().join(","));
+            return ((addr, machine_code, assembly), opcode_offset + 1);
+        }
+    };
+
+    let operand_at = |n: u16| byte_at(opcode_offset + n);
+    let (operands, operand_length) = match (displacement, instruction.operand_kind()) {
+        (Some(offset), _) => (Operands::Relative(offset), 0),
+        (None, OperandKind::None) => (Operands::None, 0),
+        (None, OperandKind::Immediate8) => (Operands::Immediate(operand_at(1)), 1),
+        (None, OperandKind::Relative8) => (Operands::Relative(signed(operand_at(1))), 1),
+        (None, OperandKind::Word16) => (Operands::Word(combine_to_double_byte(operand_at(2), operand_at(1))), 2),
+        (None, OperandKind::IndexedImmediate8) => (Operands::IndexedImmediate(signed(operand_at(1)), operand_at(2)), 2)
+    };
+    let length = opcode_offset + 1 + operand_length;
+    let bytes: Vec<u8> = (0..length).map(byte_at).collect();
+    let (machine_code, assembly) = render(instruction, &operands, addr, &bytes);
+    ((addr, machine_code, assembly), length)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::memory::Memory;
+    use super::disassemble;
+
+    fn memory_with_program(start: u16, program: &[u8]) -> Memory {
+        let mut mem = Memory::default();
+        for (i, byte) in program.iter().enumerate() {
+            mem.write_u8(start + i as u16, *byte);
+        }
+        mem
+    }
+
+    #[test]
+    fn decodes_each_prefix_with_its_operands() {
+        let program = [
+            0x01, 0x34, 0x12,           // LD BC,#1234
+            0xDD, 0x21, 0x78, 0x56,     // LD IX,#5678
+            0xFD, 0x36, 0x05, 0xAA,     // LD (IY+5),#AA
+            0xDD, 0xCB, 0xFE, 0x06,     // RLC (IX-2)
+            0xCB, 0x47,                 // BIT 0,A
+            0xED, 0xB0,                 // LDIR
+            0x18, 0xFE,                 // JR -2
+            0xCD, 0x00, 0xBB,           // CALL #BB00
+            0xFE, 0x20                  // CP #20
+        ];
+        let mem = memory_with_program(0x4000, &program);
+        let lines = disassemble(&mem, 0x4000, 9);
+
+        let addresses: Vec<u16> = lines.iter().map(|(addr, _, _)| *addr).collect();
+        assert_eq!(addresses, vec![0x4000, 0x4003, 0x4007, 0x400B, 0x400F, 0x4011, 0x4013, 0x4015, 0x4018]);
+        let mnemonics: Vec<&str> = lines.iter().map(|(_, _, assembly)| assembly.as_str()).collect();
+        assert_eq!(mnemonics, vec![
+            "LD BC,1234", "LD IX,5678", "LD (IY+05),AA", "RLC (IX-02)", "BIT 0,A", "LDIR", "JR 4013", "CALL BB00", "CP 20"
+        ]);
+        let machine_code: Vec<&str> = lines.iter().map(|(_, machine_code, _)| machine_code.as_str()).collect();
+        assert_eq!(machine_code, vec![
+            "01 34 12", "DD 21 78 56", "FD 36 05 AA", "DD CB FE 06", "CB 47", "ED B0", "18 FE", "CD 00 BB", "FE 20"
+        ]);
+    }
+
+    #[test]
+    fn unknown_opcodes_become_defb() {
+        let mem = memory_with_program(0x4000, &[0xED, 0x00, 0x00]);
+        let lines = disassemble(&mem, 0x4000, 2);
+        assert_eq!(lines[0], (0x4000, "ED 00".to_string(), "DEFB #ED,#00".to_string()));
+        assert_eq!(lines[1], (0x4002, "00".to_string(), "nop".to_string()));
+    }
+}
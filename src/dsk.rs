@@ -6,30 +6,52 @@
 
 use std::fmt;
 use log::{debug};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[allow(clippy::upper_case_acronyms)]
 enum DskType {
     NORMAL,
     EXTENDED
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Dsk {
     dsk_type: DskType,
     dsk_info: DiscInformationBlock,
     tracks: Vec<Track>
 }
 
+// A sector's ID field and stored status, as a floppy disc controller finds them on the track.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SectorId {
+    pub c: u8, // track
+    pub h: u8, // side
+    pub r: u8, // sector ID
+    pub n: u8, // size code
+    pub st1: u8,
+    pub st2: u8
+}
+
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct DiscInformationBlock {
     dsk_type: DskType,
     creator: String, // 22-2f	name of creator	14
     track_count: u8, // 30	number of tracks	1
     side_count: u8, // 31	number of sides	1
-    track_size: u32 // 32-33	size of a track (little endian; low byte followed by high byte)	2. Includes the &100 byte Track Information Block.
+    track_size: u32, // 32-33	size of a track (little endian; low byte followed by high byte)	2. Includes the &100 byte Track Information Block.
+    // One entry per track block. Normal images repeat track_size; extended images take each
+    // from the track size table at &34 (high byte only, so a multiple of 256), where 0 means
+    // the track is unformatted and has no block in the file.
+    track_sizes: Vec<u32>
 }
 
 //#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct Track {
     track_info: TrackInformationBlock,
     sector_infos: Vec<SectorInfo>,
@@ -37,15 +59,18 @@ struct Track {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct TrackInformationBlock {
     track_number: u8, // 10	track number	1
     side_number: u8, // 11	side number	1
     sector_size: u8, // 14	sector size	1 (note: this is not the value in bytes..has to be looked up). 
     sector_count: u8, // 15	number of sectors	1
     gap_3_length: u8,  // 16	GAP#3 length	1
+    filler_byte: u8, // 17	filler byte	1
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct SectorInfo {
     track_number: u8, // 00	track (equivalent to C parameter in NEC765 commands)	1
     side_number: u8, // 01	side (equivalent to H parameter in NEC765 commands)	1
@@ -53,6 +78,7 @@ struct SectorInfo {
     sector_size: u8, // 03	sector size (equivalent to N parameter in NEC765 commands)	1. Same value for all sectors in a given track.
     fdc_status_register_1: u8, // 04	FDC status register 1 (equivalent to NEC765 ST1 status register)	1
     fdc_status_register_2: u8, // 05	FDC status register 2 (equivalent to NEC765 ST2 status register)	1
+    actual_length: u16, // 06-07	actual data length in bytes (little endian). Extended images only; unused in normal ones.
 }
 
 impl Dsk {
@@ -68,11 +94,79 @@ impl Dsk {
         };
         res
     }
+
+    // Replaces the contents of a sector in the in-memory image. data must be exactly the
+    // sector's size.
+    pub fn write_sector(&mut self, track: u8, side: u8, sector_id: u8, data: &[u8]) -> Result<(), &str> {
+        let dsk_type = self.dsk_type;
+        let track = self.tracks.iter_mut()
+            .find(|t| t.track_info.track_number == track && t.track_info.side_number == side)
+            .ok_or("No such track")?;
+        let range = track.sector_range(dsk_type, sector_id).ok_or("No such sector")?;
+        if range.len() != data.len() {
+            return Err("Sector data is the wrong size");
+        }
+        track.sector_data[range].copy_from_slice(data);
+        Ok(())
+    }
+
+    // The data of the sector with the given ID, or None if the track or sector isn't on the disc.
+    pub fn read_sector(&self, track: u8, side: u8, sector_id: u8) -> Option<&[u8]> {
+        let track = self.find_track(track, side)?;
+        let range = track.sector_range(self.dsk_type, sector_id)?;
+        Some(&track.sector_data[range])
+    }
+
+    // Every sector on a physical track and side, in the order they pass the head. Empty if the
+    // track is unformatted or beyond the end of the disc.
+    pub fn sectors(&self, track: u8, side: u8) -> Vec<(SectorId, &[u8])> {
+        let track = match self.find_track(track, side) {
+            Some(track) => track,
+            None => return Vec::new()
+        };
+        let mut sectors = Vec::new();
+        let mut start = 0;
+        for sector_info in &track.sector_infos {
+            let end = (start + sector_info.data_length(self.dsk_type)).min(track.sector_data.len());
+            let id = SectorId {
+                c: sector_info.track_number, h: sector_info.side_number, r: sector_info.sector_id, n: sector_info.sector_size,
+                st1: sector_info.fdc_status_register_1, st2: sector_info.fdc_status_register_2
+            };
+            sectors.push((id, &track.sector_data[start.min(end)..end]));
+            start = end;
+        }
+        sectors
+    }
+
+    fn find_track(&self, track: u8, side: u8) -> Option<&Track> {
+        self.tracks.iter().find(|t| t.track_info.track_number == track && t.track_info.side_number == side)
+    }
+
+    // Serializes the image back into DSK file format, e.g. to save changes made by write_sector.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.dsk_info.to_bytes();
+        let mut tracks = self.tracks.iter();
+        for track_size in self.dsk_info.track_sizes.iter().filter(|size| **size != 0) {
+            let track = match tracks.next() {
+                Some(track) => track,
+                None => break
+            };
+            let start = bytes.len();
+            bytes.extend(track.to_bytes());
+            bytes.resize(start + *track_size as usize, 0);
+        }
+        bytes
+    }
 }
 
 
+#[allow(clippy::needless_borrow)]
 const TYPE_NORMAL_PREAMBLE: &str =   &"MV - CPCEMU"; // 00-21	"MV - CPCEMU Disk-File\r\nDisk-Info\r\n"	34
+#[allow(clippy::needless_borrow)]
 const TYPE_EXTENDED_PREAMBLE: &str = &"EXTENDED CP"; //  00-21 "EXTENDED CPC DSK File\r\nDisk-Info\r\n"   34
+const NORMAL_HEADER: &[u8] = b"MV - CPCEMU Disk-File\r\nDisk-Info\r\n";
+const EXTENDED_HEADER: &[u8] = b"EXTENDED CPC DSK File\r\nDisk-Info\r\n";
+const TRACK_HEADER: &[u8] = b"Track-Info\r\n";
 
 impl DiscInformationBlock {
     fn from_bytes(bytes: &[u8]) -> Result<DiscInformationBlock, &str> {
@@ -94,18 +188,32 @@ impl DiscInformationBlock {
             anything_but_zero => anything_but_zero
         };
 
+        let track_blocks = track_count as usize * side_count as usize;
+        let track_sizes = match dsk_type {
+            Some(DskType::EXTENDED) => bytes[0x34..0x34 + track_blocks].iter().map(|size| *size as u32 * 0x100).collect(),
+            _ => vec![track_size; track_blocks]
+        };
+
         match dsk_type {
-            Some(dsk_type) => Ok(DiscInformationBlock { dsk_type: dsk_type, creator: creator.to_string(), track_count: track_count, side_count: side_count, track_size: track_size }),
+            Some(dsk_type) => Ok(DiscInformationBlock { dsk_type, creator: creator.to_string(), track_count, side_count, track_size, track_sizes }),
             None => Err("Invalid Dsk format")
         }
     }
 
+    // Tracks are stored in order, alternating between sides on double-sided discs. Unformatted
+    // tracks in an extended image take up no space.
+    #[allow(clippy::unused_unit)]
     fn load_tracks(&mut self, bytes: &[u8]) -> Vec<Track> {
         let mut tracks: Vec<Track> = Vec::new();
-        for x in 0..self.track_count {
-            let track_start: u32 = x as u32 * self.track_size;
-            let track_end = track_start + self.track_size - 1;
-            match Track::init_from_bytes(&bytes[track_start as usize..track_end as usize], self.track_size) {
+        let mut track_start = 0;
+        for track_size in self.track_sizes.iter().filter(|size| **size != 0) {
+            let track_end = track_start + *track_size as usize;
+            if track_end > bytes.len() {
+                break;
+            }
+            let track = Track::init_from_bytes(&bytes[track_start..track_end], *track_size);
+            track_start = track_end;
+            match track {
                 Ok(track) => tracks.push(track),
                 Err(msg) => { dbg!(msg);() }
             }
@@ -113,11 +221,35 @@ impl DiscInformationBlock {
         tracks
     }
 
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0; 0x100];
+        let header = match self.dsk_type {
+            DskType::NORMAL => NORMAL_HEADER,
+            DskType::EXTENDED => EXTENDED_HEADER
+        };
+        bytes[..header.len()].copy_from_slice(header);
+        let creator = self.creator.as_bytes();
+        let creator_length = creator.len().min(0x0E);
+        bytes[0x22..0x22 + creator_length].copy_from_slice(&creator[..creator_length]);
+        bytes[0x30] = self.track_count;
+        bytes[0x31] = self.side_count;
+        match self.dsk_type {
+            DskType::NORMAL => bytes[0x32..0x34].copy_from_slice(&(self.track_size as u16).to_le_bytes()),
+            DskType::EXTENDED => {
+                for (x, track_size) in self.track_sizes.iter().enumerate() {
+                    bytes[0x34 + x] = (track_size / 0x100) as u8;
+                }
+            }
+        }
+        bytes
+    }
+
 }
 
 
 
 impl Track {
+    #[allow(clippy::redundant_field_names)]
     fn init_from_bytes(bytes: &[u8], track_size: u32) -> Result<Track, &str> {
         
         let track_info = TrackInformationBlock::init_from_bytes(bytes);
@@ -138,6 +270,30 @@ impl Track {
             }
         )
     }
+
+    // Where a sector's data is in sector_data. Sectors are stored in the order of their
+    // Sector Information Blocks, each data_length() bytes long.
+    fn sector_range(&self, dsk_type: DskType, sector_id: u8) -> Option<std::ops::Range<usize>> {
+        let mut start = 0;
+        for sector_info in &self.sector_infos {
+            let length = sector_info.data_length(dsk_type);
+            if sector_info.sector_id == sector_id {
+                return if start + length <= self.sector_data.len() { Some(start..start + length) } else { None };
+            }
+            start += length;
+        }
+        None
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.track_info.to_bytes();
+        for (x, sector_info) in self.sector_infos.iter().enumerate() {
+            let start = 0x18 + x * 8;
+            bytes[start..start + 8].copy_from_slice(&sector_info.to_bytes());
+        }
+        bytes.extend(&self.sector_data);
+        bytes
+    }
 }
 
 impl fmt::Debug for Track {
@@ -156,9 +312,23 @@ impl TrackInformationBlock {
             side_number: bytes[0x11],
             sector_size: bytes[0x14],
             sector_count: bytes[0x15],
-            gap_3_length: bytes[0x16]
+            gap_3_length: bytes[0x16],
+            filler_byte: bytes[0x17]
         }
     }
+
+    // The Sector Information List starting at &18 is filled in by Track::to_bytes.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0; 0x100];
+        bytes[..TRACK_HEADER.len()].copy_from_slice(TRACK_HEADER);
+        bytes[0x10] = self.track_number;
+        bytes[0x11] = self.side_number;
+        bytes[0x14] = self.sector_size;
+        bytes[0x15] = self.sector_count;
+        bytes[0x16] = self.gap_3_length;
+        bytes[0x17] = self.filler_byte;
+        bytes
+    }
 }
 
 fn debug_print_bytes(bytes: &[u8], max: u32 ) {
@@ -179,7 +349,176 @@ impl SectorInfo {
             sector_id: bytes[0x2],
             sector_size: bytes[0x3],
             fdc_status_register_1: bytes[0x4],
-            fdc_status_register_2: bytes[0x5]
+            fdc_status_register_2: bytes[0x5],
+            actual_length: u16::from_le_bytes([bytes[0x6], bytes[0x7]])
+        }
+    }
+
+    // Extended images give each sector's stored length, which needn't match N, e.g. for
+    // copy protection. Otherwise N is a size code: 0 is 128 bytes, 1 is 256, 2 is 512 and so on.
+    // Normal images don't use bytes 06-07, so whatever is left there is ignored.
+    fn data_length(&self, dsk_type: DskType) -> usize {
+        match (dsk_type, self.actual_length) {
+            (DskType::EXTENDED, actual_length) if actual_length != 0 => actual_length as usize,
+            _ => 128 << (self.sector_size & 0x07)
+        }
+    }
+
+    fn to_bytes(&self) -> [u8; 8] {
+        let [low, high] = self.actual_length.to_le_bytes();
+        [self.track_number, self.side_number, self.sector_id, self.sector_size, self.fdc_status_register_1, self.fdc_status_register_2, low, high]
+    }
+}
+#[cfg(test)]
+pub mod tests {
+    use super::{Dsk, DskType};
+
+    // A single-sided normal-format image with the given number of 512-byte sectors on each
+    // track, numbered from #C1 as on a data format disc. Each sector is filled with fill, or
+    // with its track number and sector ID repeated if fill is None.
+    pub fn normal_image(tracks: u8, sectors: u8, fill: Option<u8>) -> Vec<u8> {
+        let mut bytes = vec![0; 0x100];
+        bytes[..34].copy_from_slice(b"MV - CPCEMU Disk-File\r\nDisk-Info\r\n");
+        bytes[0x22..0x2B].copy_from_slice(b"cpc-tests");
+        bytes[0x30] = tracks;
+        bytes[0x31] = 1;
+        bytes[0x32..0x34].copy_from_slice(&(0x100 + sectors as u16 * 512).to_le_bytes());
+        for track in 0..tracks {
+            let mut block = vec![0; 0x100];
+            block[..12].copy_from_slice(b"Track-Info\r\n");
+            block[0x10] = track;
+            block[0x14] = 2;
+            block[0x15] = sectors;
+            block[0x16] = 0x4E;
+            block[0x17] = 0xE5;
+            for sector in 0..sectors {
+                let info = 0x18 + sector as usize * 8;
+                block[info..info + 4].copy_from_slice(&[track, 0, 0xC1 + sector, 2]);
+            }
+            for sector in 0..sectors {
+                match fill {
+                    Some(byte) => block.extend([byte; 512]),
+                    None => block.extend([track, 0xC1 + sector].repeat(256))
+                }
+            }
+            bytes.extend(block);
         }
-    }  
-}
\ No newline at end of file
+        bytes
+    }
+
+    // A track block for an extended image, with sectors given as (ID, N, actual length) and
+    // each filled with its ID.
+    fn extended_track(track: u8, sectors: &[(u8, u8, u16)]) -> Vec<u8> {
+        let mut block = vec![0; 0x100];
+        block[..12].copy_from_slice(b"Track-Info\r\n");
+        block[0x10] = track;
+        block[0x14] = sectors[0].1;
+        block[0x15] = sectors.len() as u8;
+        block[0x16] = 0x4E;
+        block[0x17] = 0xE5;
+        for (x, (id, n, length)) in sectors.iter().enumerate() {
+            let info = 0x18 + x * 8;
+            block[info..info + 4].copy_from_slice(&[track, 0, *id, *n]);
+            block[info + 6..info + 8].copy_from_slice(&length.to_le_bytes());
+        }
+        for (id, _, length) in sectors {
+            block.extend(vec![*id; *length as usize]);
+        }
+        block.resize((block.len() + 0xFF) & !0xFF, 0);
+        block
+    }
+
+    // Four tracks of different sizes, the second one unformatted. The third has a sector whose
+    // stored data is shorter than its N says, as on some protected discs.
+    fn extended_image() -> Vec<u8> {
+        let mut bytes = vec![0; 0x100];
+        bytes[..34].copy_from_slice(b"EXTENDED CPC DSK File\r\nDisk-Info\r\n");
+        bytes[0x22..0x2B].copy_from_slice(b"cpc-tests");
+        bytes[0x30] = 4;
+        bytes[0x31] = 1;
+        let tracks = [
+            extended_track(0, &(0xC1..=0xC9).map(|id| (id, 2, 512)).collect::<Vec<_>>()),
+            extended_track(2, &[(0x01, 2, 512), (0x02, 3, 0x300)]),
+            extended_track(3, &[(0x41, 1, 256), (0x42, 1, 256), (0x43, 1, 256), (0x44, 1, 256)])
+        ];
+        bytes[0x34..0x38].copy_from_slice(&[0x13, 0, 0x06, 0x05]);
+        for track in &tracks {
+            bytes.extend(track);
+        }
+        bytes
+    }
+
+    #[test]
+    fn extended_image_uses_track_and_sector_sizes() {
+        let bytes = extended_image();
+        let dsk = Dsk::init_from_bytes(&bytes).unwrap();
+        let track_numbers: Vec<u8> = dsk.tracks.iter().map(|t| t.track_info.track_number).collect();
+        assert_eq!(track_numbers, [0, 2, 3]);
+
+        let track = &dsk.tracks[1];
+        assert_eq!(track.sector_range(DskType::EXTENDED, 0x01), Some(0..512));
+        assert_eq!(track.sector_range(DskType::EXTENDED, 0x02), Some(512..1280));
+        assert!(track.sector_data[512..1280].iter().all(|byte| *byte == 0x02));
+
+        let track = &dsk.tracks[2];
+        assert_eq!(track.sector_range(DskType::EXTENDED, 0x44), Some(768..1024));
+        assert!(track.sector_data[768..1024].iter().all(|byte| *byte == 0x44));
+
+        assert_eq!(dsk.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn unmodified_image_serializes_identically() {
+        let bytes = normal_image(3, 9, None);
+        let dsk = Dsk::init_from_bytes(&bytes).unwrap();
+        assert_eq!(dsk.tracks.len(), 3);
+        assert_eq!(dsk.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn written_sector_survives_round_trip() {
+        let mut dsk = Dsk::init_from_bytes(&normal_image(3, 9, None)).unwrap();
+        dsk.write_sector(1, 0, 0xC4, &[0xAA; 512]).unwrap();
+
+        let reparsed = Dsk::init_from_bytes(&dsk.to_bytes()).unwrap();
+        let track = &reparsed.tracks[1];
+        let range = track.sector_range(DskType::NORMAL, 0xC4).unwrap();
+        assert_eq!(range, 1536..2048);
+        assert!(track.sector_data[range].iter().all(|byte| *byte == 0xAA));
+        // Neighbouring sectors are untouched.
+        assert_eq!(track.sector_data[1534..1536], [1, 0xC3]);
+        assert_eq!(track.sector_data[2048..2050], [1, 0xC5]);
+    }
+
+    #[test]
+    fn normal_image_ignores_stray_sector_lengths() {
+        let mut bytes = normal_image(1, 9, None);
+        // Bytes 06-07 of the second Sector Information Block, unused in a normal image.
+        bytes[0x100 + 0x18 + 8 + 6..0x100 + 0x18 + 8 + 8].copy_from_slice(&0x0100u16.to_le_bytes());
+        let dsk = Dsk::init_from_bytes(&bytes).unwrap();
+        let sector = dsk.read_sector(0, 0, 0xC3).unwrap();
+        assert_eq!(sector.len(), 512);
+        assert_eq!(sector[..2], [0, 0xC3]);
+        assert_eq!(dsk.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn read_sector_finds_sector_by_id() {
+        let dsk = Dsk::init_from_bytes(&normal_image(3, 9, None)).unwrap();
+        let sector = dsk.read_sector(2, 0, 0xC7).unwrap();
+        assert_eq!(sector.len(), 512);
+        assert_eq!(sector[..4], [2, 0xC7, 2, 0xC7]);
+
+        assert_eq!(dsk.read_sector(2, 0, 0x41), None);
+        assert_eq!(dsk.read_sector(2, 1, 0xC1), None);
+        assert_eq!(dsk.read_sector(3, 0, 0xC1), None);
+    }
+
+    #[test]
+    fn write_sector_rejects_bad_targets() {
+        let mut dsk = Dsk::init_from_bytes(&normal_image(1, 9, None)).unwrap();
+        assert_eq!(dsk.write_sector(5, 0, 0xC1, &[0; 512]), Err("No such track"));
+        assert_eq!(dsk.write_sector(0, 0, 0x41, &[0; 512]), Err("No such sector"));
+        assert_eq!(dsk.write_sector(0, 0, 0xC1, &[0; 256]), Err("Sector data is the wrong size"));
+    }
+}
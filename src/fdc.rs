@@ -0,0 +1,411 @@
+use std::collections::VecDeque;
+
+use crate::memory::PortDevice;
+use crate::dsk::{Dsk, SectorId};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+///////////////////////
+//
+// FDC 765 - floppy disc controller
+//
+///////////////////////
+
+// The FDC is selected by A10 and A7 being low, with A8 and A0 choosing the function:
+//   #FA7E - motor control, write only: bit 0 turns the drive motors on
+//   #FB7E - main status register, read only
+//   #FB7F - data register
+//
+// Every command goes through three phases. In the command phase the CPU writes the command byte
+// and its parameters to the data register. Commands that transfer data then enter the execution
+// phase, where the CPU reads the data one byte at a time. Finally, most commands have a result
+// phase where the CPU reads back status bytes. The main status register says which phase the
+// FDC is in and whether it's waiting for the CPU.
+//
+// Only drive A (unit 0) has a disc. Unit select bit 1 isn't wired on the CPC, so units 2 and 3
+// are drives A and B again. Seeks complete immediately.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Fdc {
+    disc: Option<Dsk>,
+    motor_on: bool,
+    phase: Phase,
+    command: Vec<u8>,
+    // Bytes waiting to be read in the execution and result phases.
+    data: VecDeque<u8>,
+    result: VecDeque<u8>,
+    // The track each drive's head is over.
+    present_track: [u8; DRIVES],
+    // ST0 and present track for each finished SEEK or RECALIBRATE, until SENSE INTERRUPT STATUS
+    // reads it.
+    interrupts: VecDeque<(u8, u8)>,
+    // Sectors pass under the head in turn, so successive READ IDs return successive sectors.
+    next_id_index: usize
+}
+
+const DRIVES: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum Phase {
+    Command,
+    Execution,
+    Result
+}
+
+// Main status register bits.
+const REQUEST_FOR_MASTER: u8 = 0x80; // the data register is ready
+const DATA_TO_CPU: u8 = 0x40;
+const EXECUTION_MODE: u8 = 0x20;
+const BUSY: u8 = 0x10;
+
+// Status register 0 bits, besides the head and unit in bits 0-2.
+const ABNORMAL_TERMINATION: u8 = 0x40;
+const INVALID_COMMAND: u8 = 0x80;
+const SEEK_END: u8 = 0x20;
+const NOT_READY: u8 = 0x08;
+
+// Status register 1 bits.
+const END_OF_CYLINDER: u8 = 0x80;
+const NO_DATA: u8 = 0x04;
+const MISSING_ADDRESS_MARK: u8 = 0x01;
+
+// Status register 3 bits, besides the head and unit in bits 0-2.
+const READY: u8 = 0x20;
+const TRACK_0: u8 = 0x10;
+
+// The low five bits of the first byte pick the command. The top three are the multi-track, MFM
+// and skip flags.
+const SPECIFY: u8 = 0x03;
+const SENSE_DRIVE_STATUS: u8 = 0x04;
+const READ_DATA: u8 = 0x06;
+const RECALIBRATE: u8 = 0x07;
+const SENSE_INTERRUPT_STATUS: u8 = 0x08;
+const READ_ID: u8 = 0x0A;
+const SEEK: u8 = 0x0F;
+
+// How many bytes each command takes, including the command byte. None for commands that aren't
+// implemented, which the FDC rejects as invalid.
+fn command_length(command: u8) -> Option<usize> {
+    match command & 0x1F {
+        SPECIFY => Some(3),
+        SENSE_DRIVE_STATUS => Some(2),
+        READ_DATA => Some(9),
+        RECALIBRATE => Some(2),
+        SENSE_INTERRUPT_STATUS => Some(1),
+        READ_ID => Some(2),
+        SEEK => Some(3),
+        _ => None
+    }
+}
+
+impl Fdc {
+    pub fn default() -> Fdc {
+        Fdc {
+            disc: None, motor_on: false, phase: Phase::Command, command: Vec::new(), data: VecDeque::new(),
+            result: VecDeque::new(), present_track: [0; DRIVES], interrupts: VecDeque::new(), next_id_index: 0
+        }
+    }
+
+    pub fn insert_disc(&mut self, disc: Dsk) {
+        self.disc = Some(disc);
+        self.next_id_index = 0;
+    }
+
+    pub fn eject_disc(&mut self) -> Option<Dsk> {
+        self.disc.take()
+    }
+
+    pub fn disc(&self) -> Option<&Dsk> {
+        self.disc.as_ref()
+    }
+
+    pub fn motor_on(&self) -> bool {
+        self.motor_on
+    }
+
+    pub fn main_status(&self) -> u8 {
+        match self.phase {
+            Phase::Command if self.command.is_empty() => REQUEST_FOR_MASTER,
+            Phase::Command => REQUEST_FOR_MASTER | BUSY,
+            Phase::Execution => REQUEST_FOR_MASTER | DATA_TO_CPU | EXECUTION_MODE | BUSY,
+            Phase::Result => REQUEST_FOR_MASTER | DATA_TO_CPU | BUSY
+        }
+    }
+
+    pub fn write_data(&mut self, value: u8) {
+        if self.phase != Phase::Command {
+            return;
+        }
+        self.command.push(value);
+        match command_length(self.command[0]) {
+            Some(length) if self.command.len() < length => {}
+            Some(_) => {
+                let command = std::mem::take(&mut self.command);
+                self.execute(&command);
+            }
+            None => {
+                self.command.clear();
+                self.finish(Vec::new(), vec![INVALID_COMMAND]);
+            }
+        }
+    }
+
+    pub fn read_data(&mut self) -> u8 {
+        let value = match self.phase {
+            Phase::Command => return 0xFF,
+            Phase::Execution => self.data.pop_front(),
+            Phase::Result => self.result.pop_front()
+        };
+        if self.phase == Phase::Execution && self.data.is_empty() {
+            self.phase = Phase::Result;
+        }
+        if self.phase == Phase::Result && self.result.is_empty() {
+            self.phase = Phase::Command;
+        }
+        value.unwrap_or(0xFF)
+    }
+
+    // Queues the execution phase data and result bytes, then moves to the first phase that has
+    // something to read.
+    fn finish(&mut self, data: Vec<u8>, result: Vec<u8>) {
+        self.data = data.into();
+        self.result = result.into();
+        self.phase = if !self.data.is_empty() {
+            Phase::Execution
+        } else if !self.result.is_empty() {
+            Phase::Result
+        } else {
+            Phase::Command
+        };
+    }
+
+    fn execute(&mut self, command: &[u8]) {
+        // The second byte of most commands is the head in bit 2 and the unit in bits 0-1.
+        let head_unit = command.get(1).copied().unwrap_or(0) & 0x07;
+        let drive = (head_unit & 0x01) as usize;
+        let head = (head_unit >> 2) & 0x01;
+        match command[0] & 0x1F {
+            SPECIFY => self.finish(Vec::new(), Vec::new()),
+            SENSE_DRIVE_STATUS => {
+                let mut st3 = head_unit;
+                if self.present_track[drive] == 0 {
+                    st3 |= TRACK_0;
+                }
+                if self.has_disc(drive) {
+                    st3 |= READY;
+                }
+                self.finish(Vec::new(), vec![st3]);
+            }
+            SEEK => self.seek(drive, head_unit, command[2]),
+            RECALIBRATE => self.seek(drive, head_unit, 0),
+            SENSE_INTERRUPT_STATUS => {
+                let result = match self.interrupts.pop_front() {
+                    Some((st0, track)) => vec![st0, track],
+                    None => vec![INVALID_COMMAND]
+                };
+                self.finish(Vec::new(), result);
+            }
+            READ_ID => {
+                let track = self.present_track[drive];
+                let sectors = self.sectors(drive, track, head);
+                let result = if !self.has_disc(drive) {
+                    vec![ABNORMAL_TERMINATION | NOT_READY | head_unit, 0, 0, track, head, 0, 0]
+                } else if sectors.is_empty() {
+                    vec![ABNORMAL_TERMINATION | head_unit, MISSING_ADDRESS_MARK, 0, track, head, 0, 0]
+                } else {
+                    let id = sectors[self.next_id_index % sectors.len()];
+                    self.next_id_index = (self.next_id_index + 1) % sectors.len();
+                    vec![head_unit, 0, 0, id.c, id.h, id.r, id.n]
+                };
+                self.finish(Vec::new(), result);
+            }
+            READ_DATA => self.read_data_command(drive, head, head_unit, command),
+            _ => unreachable!()
+        }
+    }
+
+    fn has_disc(&self, drive: usize) -> bool {
+        drive == 0 && self.disc.is_some()
+    }
+
+    fn sectors(&self, drive: usize, track: u8, head: u8) -> Vec<SectorId> {
+        match &self.disc {
+            Some(disc) if drive == 0 => disc.sectors(track, head).into_iter().map(|(id, _)| id).collect(),
+            _ => Vec::new()
+        }
+    }
+
+    fn seek(&mut self, drive: usize, head_unit: u8, track: u8) {
+        self.present_track[drive] = track;
+        self.next_id_index = 0;
+        let mut st0 = SEEK_END | head_unit;
+        if !self.has_disc(drive) {
+            st0 |= NOT_READY;
+        }
+        self.interrupts.push_back((st0, track));
+        self.finish(Vec::new(), Vec::new());
+    }
+
+    // READ DATA's parameters are the C, H, R and N of the first sector to read, then EOT (the
+    // last sector ID to read), the gap length and the data length (unused when N isn't 0).
+    // Sectors are read from R to EOT on the present track. The CPC doesn't connect the FDC's
+    // terminal count line, so every read that gets that far ends with END_OF_CYLINDER set and
+    // the FDC reporting abnormal termination, which AMSDOS expects.
+    fn read_data_command(&mut self, drive: usize, head: u8, head_unit: u8, command: &[u8]) {
+        let (c, h, mut r, n, eot) = (command[2], command[3], command[4], command[5], command[6]);
+        let disc = match &self.disc {
+            Some(disc) if drive == 0 => disc,
+            _ => {
+                self.finish(Vec::new(), vec![ABNORMAL_TERMINATION | NOT_READY | head_unit, 0, 0, c, h, r, n]);
+                return;
+            }
+        };
+        let sectors = disc.sectors(self.present_track[drive], head);
+        let mut data = Vec::new();
+        let (mut st1, mut st2) = (0, 0);
+        loop {
+            let sector = sectors.iter().find(|(id, _)| id.c == c && id.h == h && id.r == r);
+            match sector {
+                Some((id, bytes)) => {
+                    data.extend_from_slice(bytes);
+                    st1 |= id.st1;
+                    st2 |= id.st2;
+                }
+                None => {
+                    st1 |= NO_DATA;
+                    break;
+                }
+            }
+            if r == eot {
+                st1 |= END_OF_CYLINDER;
+                break;
+            }
+            r = r.wrapping_add(1);
+        }
+        let result = if st1 & END_OF_CYLINDER != 0 {
+            vec![ABNORMAL_TERMINATION | head_unit, st1, st2, c.wrapping_add(1), h, 1, n]
+        } else {
+            vec![ABNORMAL_TERMINATION | head_unit, st1, st2, c, h, r, n]
+        };
+        self.finish(data, result);
+    }
+}
+
+impl PortDevice for Fdc {
+    fn write(&mut self, port: u16, value: u8) {
+        if port & 0x0080 != 0 {
+            return;
+        }
+        match (port & 0x0100 != 0, port & 0x0001 != 0) {
+            (false, _) => self.motor_on = value & 0x01 != 0,
+            (true, true) => self.write_data(value),
+            (true, false) => {}
+        }
+    }
+
+    fn read(&mut self, port: u16) -> Option<u8> {
+        if port & 0x0080 != 0 {
+            return None;
+        }
+        match (port & 0x0100 != 0, port & 0x0001 != 0) {
+            (true, false) => Some(self.main_status()),
+            (true, true) => Some(self.read_data()),
+            (false, _) => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::memory::PortDevice;
+    use crate::dsk::{Dsk, tests::normal_image};
+    use super::Fdc;
+
+    const STATUS: u16 = 0xFB7E;
+    const DATA: u16 = 0xFB7F;
+
+    // Two single-sided tracks of two 512-byte sectors each, #C1 and #C2, filled with their
+    // track number and then their ID.
+    fn disc() -> Dsk {
+        Dsk::init_from_bytes(&normal_image(2, 2, None)).unwrap()
+    }
+
+    fn fdc() -> Fdc {
+        let mut fdc = Fdc::default();
+        fdc.insert_disc(disc());
+        fdc
+    }
+
+    fn send(fdc: &mut Fdc, bytes: &[u8]) {
+        for byte in bytes {
+            assert_eq!(fdc.read(STATUS).unwrap() & 0xC0, 0x80, "FDC isn't waiting for a command byte");
+            fdc.write(DATA, *byte);
+        }
+    }
+
+    fn read_result(fdc: &mut Fdc) -> Vec<u8> {
+        let mut result = Vec::new();
+        while fdc.read(STATUS).unwrap() & 0xF0 == 0xD0 {
+            result.push(fdc.read(DATA).unwrap());
+        }
+        result
+    }
+
+    #[test]
+    fn read_id_returns_sector_on_track_0() {
+        let mut fdc = fdc();
+        send(&mut fdc, &[0x07, 0x00]); // RECALIBRATE drive A
+        assert_eq!(read_result(&mut fdc), []);
+        send(&mut fdc, &[0x08]); // SENSE INTERRUPT STATUS
+        assert_eq!(read_result(&mut fdc), [0x20, 0]);
+
+        send(&mut fdc, &[0x4A, 0x00]); // READ ID, MFM
+        assert_eq!(read_result(&mut fdc), [0x00, 0x00, 0x00, 0, 0, 0xC1, 2]);
+        assert_eq!(fdc.read(STATUS), Some(0x80));
+        // The next sector round has come under the head.
+        send(&mut fdc, &[0x4A, 0x00]);
+        assert_eq!(read_result(&mut fdc)[5], 0xC2);
+    }
+
+    #[test]
+    fn read_data_transfers_sectors_to_eot() {
+        let mut fdc = fdc();
+        send(&mut fdc, &[0x0F, 0x00, 1]); // SEEK to track 1
+        send(&mut fdc, &[0x08]);
+        assert_eq!(read_result(&mut fdc), [0x20, 1]);
+
+        send(&mut fdc, &[0x46, 0x00, 1, 0, 0xC1, 2, 0xC2, 0x2A, 0xFF]);
+        let mut data = Vec::new();
+        while fdc.read(STATUS).unwrap() & 0xF0 == 0xF0 {
+            data.push(fdc.read(DATA).unwrap());
+        }
+        assert_eq!(data.len(), 1024);
+        assert_eq!(data[..2], [1, 0xC1]);
+        assert_eq!(data[512..514], [1, 0xC2]);
+        assert_eq!(read_result(&mut fdc), [0x40, 0x80, 0x00, 2, 0, 1, 2]);
+    }
+
+    #[test]
+    fn missing_sector_and_drive_b_fail() {
+        let mut fdc = fdc();
+        send(&mut fdc, &[0x46, 0x00, 0, 0, 0x41, 2, 0x41, 0x2A, 0xFF]);
+        assert_eq!(read_result(&mut fdc), [0x40, 0x04, 0x00, 0, 0, 0x41, 2]);
+
+        send(&mut fdc, &[0x4A, 0x01]);
+        assert_eq!(read_result(&mut fdc)[0], 0x49);
+        // Nothing is pending, so SENSE INTERRUPT STATUS is invalid.
+        send(&mut fdc, &[0x08]);
+        assert_eq!(read_result(&mut fdc), [0x80]);
+    }
+
+    #[test]
+    fn motor_control_and_port_decoding() {
+        let mut fdc = fdc();
+        fdc.write(0xFA7E, 0x01);
+        assert!(fdc.motor_on());
+        fdc.write(0xFA7E, 0x00);
+        assert!(!fdc.motor_on());
+        // A7 high doesn't select the FDC.
+        assert_eq!(fdc.read(0xFBFE), None);
+    }
+}
@@ -0,0 +1,224 @@
+use crate::memory::PortDevice;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+///////////////////////
+//
+// Gate Array - screen mode, palette and ROM enables
+//
+///////////////////////
+
+// Pen numbers 0-15 are the screen inks; this is the extra entry for the border.
+pub const BORDER: usize = 16;
+
+// Cycles per scanline: 64us at 4MHz.
+const SCANLINE_CYCLES: u32 = 256;
+
+// The Gate Array counts scanlines and raises an interrupt every 52 of them, which is 300 times a
+// second or six times a frame.
+const INTERRUPT_SCANLINES: u8 = 52;
+
+// Every write to the Gate Array is a command. The top two bits of the data pick which:
+//   00 - pen select:    bits 0-3 pick a pen, or bit 4 picks the border.
+//   01 - colour select: bits 0-4 give the hardware colour for the selected pen.
+//   10 - RMR:           bits 0-1 set the screen mode, bit 2 disables the lower ROM and
+//                       bit 3 the upper ROM. Bit 4 resets the interrupt counter.
+//   11 - RAM configuration: bits 0-2 pick how the 6128's second 64K is paged in. A PAL beside
+//        the Gate Array decodes this rather than the Gate Array itself, but it shares the port.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GateArray {
+    selected_pen: usize,
+    // The hardware colour (0-31) assigned to each pen, with the border last.
+    inks: [u8; 17],
+    mode: u8,
+    lower_rom_enabled: bool,
+    upper_rom_enabled: bool,
+    interrupt_counter_reset: bool,
+    scanline_cycles: u32,
+    interrupt_counter: u8,
+    interrupt_pending: bool,
+    ram_config: u8
+}
+
+impl GateArray {
+    pub fn default() -> GateArray {
+        GateArray {
+            selected_pen: 0, inks: [0; 17], mode: 0, lower_rom_enabled: true, upper_rom_enabled: true,
+            interrupt_counter_reset: false, scanline_cycles: 0, interrupt_counter: 0, interrupt_pending: false, ram_config: 0
+        }
+    }
+
+    pub fn mode(&self) -> u8 {
+        self.mode
+    }
+
+    // The hardware colour assigned to pen, where BORDER is the border.
+    pub fn ink(&self, pen: usize) -> u8 {
+        self.inks[pen]
+    }
+
+    pub fn lower_rom_enabled(&self) -> bool {
+        self.lower_rom_enabled
+    }
+
+    pub fn upper_rom_enabled(&self) -> bool {
+        self.upper_rom_enabled
+    }
+
+    // The pen colour select writes go to, where BORDER is the border.
+    pub fn selected_pen(&self) -> usize {
+        self.selected_pen
+    }
+
+    // The RMR value that sets the current mode and ROM enables.
+    pub fn rmr(&self) -> u8 {
+        self.mode | if self.lower_rom_enabled { 0 } else { 0x04 } | if self.upper_rom_enabled { 0 } else { 0x08 }
+    }
+
+    pub fn ram_config(&self) -> u8 {
+        self.ram_config
+    }
+
+    // True once after an RMR write with bit 4 set.
+    pub fn take_interrupt_counter_reset(&mut self) -> bool {
+        std::mem::replace(&mut self.interrupt_counter_reset, false)
+    }
+
+    // Advances the scanline count by the cycles just emulated, raising an interrupt every 52 lines.
+    // An RMR reset (bit 4) restarts the count and withdraws any pending interrupt.
+    pub fn add_cycles(&mut self, cycles: u16) {
+        if self.take_interrupt_counter_reset() {
+            self.interrupt_counter = 0;
+            self.interrupt_pending = false;
+        }
+        self.scanline_cycles += cycles as u32;
+        while self.scanline_cycles >= SCANLINE_CYCLES {
+            self.scanline_cycles -= SCANLINE_CYCLES;
+            self.interrupt_counter += 1;
+            if self.interrupt_counter == INTERRUPT_SCANLINES {
+                self.interrupt_counter = 0;
+                self.interrupt_pending = true;
+            }
+        }
+    }
+
+    pub fn interrupt_pending(&self) -> bool {
+        self.interrupt_pending
+    }
+
+    // Called when the CPU accepts the interrupt. Clearing bit 5 of the count keeps the next
+    // interrupt at least 32 lines away, so a late acknowledge can't cause two close together.
+    pub fn acknowledge_interrupt(&mut self) {
+        self.interrupt_pending = false;
+        self.interrupt_counter &= 0x1F;
+    }
+
+    pub fn write(&mut self, value: u8) {
+        match value >> 6 {
+            0b00 => {
+                self.selected_pen = if value & 0x10 != 0 { BORDER } else { (value & 0x0F) as usize };
+            }
+            0b01 => {
+                self.inks[self.selected_pen] = value & 0x1F;
+            }
+            0b10 => {
+                self.mode = value & 0x03;
+                self.lower_rom_enabled = value & 0x04 == 0;
+                self.upper_rom_enabled = value & 0x08 == 0;
+                if value & 0x10 != 0 {
+                    self.interrupt_counter_reset = true;
+                }
+            }
+            _ => {
+                self.ram_config = value & 0x07;
+            }
+        }
+    }
+}
+
+impl PortDevice for GateArray {
+    fn write(&mut self, port: u16, value: u8) {
+        GateArray::write(self, value);
+    }
+
+    // The Gate Array is write only.
+    fn read(&mut self, port: u16) -> Option<u8> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GateArray, BORDER, SCANLINE_CYCLES};
+
+    #[test]
+    fn pen_then_colour_sets_ink() {
+        let mut gate_array = GateArray::default();
+        gate_array.write(0x03); // pen 3
+        gate_array.write(0x40 | 0x0C); // hardware colour #0C
+        assert_eq!(gate_array.ink(3), 0x0C);
+        assert_eq!(gate_array.ink(0), 0x00);
+
+        gate_array.write(0x10); // border
+        gate_array.write(0x54);
+        assert_eq!(gate_array.ink(BORDER), 0x14);
+        assert_eq!(gate_array.ink(3), 0x0C);
+    }
+
+    #[test]
+    fn rmr_sets_mode_and_rom_enables() {
+        let mut gate_array = GateArray::default();
+        assert!(gate_array.lower_rom_enabled());
+        assert!(gate_array.upper_rom_enabled());
+
+        gate_array.write(0x80 | 0x04 | 0x01);
+        assert_eq!(gate_array.mode(), 1);
+        assert!(!gate_array.lower_rom_enabled());
+        assert!(gate_array.upper_rom_enabled());
+        assert!(!gate_array.take_interrupt_counter_reset());
+
+        gate_array.write(0x80 | 0x10 | 0x08 | 0x02);
+        assert_eq!(gate_array.mode(), 2);
+        assert!(gate_array.lower_rom_enabled());
+        assert!(!gate_array.upper_rom_enabled());
+        assert!(gate_array.take_interrupt_counter_reset());
+        assert!(!gate_array.take_interrupt_counter_reset());
+    }
+
+    #[test]
+    fn interrupt_every_52_scanlines() {
+        let mut gate_array = GateArray::default();
+        for _ in 0..51 {
+            gate_array.add_cycles(SCANLINE_CYCLES as u16);
+        }
+        assert!(!gate_array.interrupt_pending());
+        gate_array.add_cycles(SCANLINE_CYCLES as u16);
+        assert!(gate_array.interrupt_pending());
+
+        gate_array.acknowledge_interrupt();
+        assert!(!gate_array.interrupt_pending());
+    }
+
+    #[test]
+    fn late_acknowledge_delays_next_interrupt() {
+        let mut gate_array = GateArray::default();
+        // 52 lines to raise it, then 40 more before it's acknowledged.
+        gate_array.add_cycles(92 * SCANLINE_CYCLES as u16);
+        gate_array.acknowledge_interrupt();
+        // The count drops from 40 to 8, so it's another 44 lines rather than 12.
+        gate_array.add_cycles(43 * SCANLINE_CYCLES as u16);
+        assert!(!gate_array.interrupt_pending());
+        gate_array.add_cycles(SCANLINE_CYCLES as u16);
+        assert!(gate_array.interrupt_pending());
+    }
+
+    #[test]
+    fn rmr_reset_withdraws_interrupt() {
+        let mut gate_array = GateArray::default();
+        gate_array.add_cycles(52 * SCANLINE_CYCLES as u16);
+        assert!(gate_array.interrupt_pending());
+        gate_array.write(0x80 | 0x10);
+        gate_array.add_cycles(0);
+        assert!(!gate_array.interrupt_pending());
+    }
+}
@@ -8,8 +8,8 @@ use std::str::FromStr;
 
 use log::{debug, error};
 
-use crate::{memory::{Memory, Registers, FlagValue, AddressBus, DataBus, RegisterOperations, Register, DefaultRegister}, utils::{combine_to_double_byte, split_double_byte, self, signed}, runtime::{RuntimeComponents}};
-use super::{Instruction, Operands};
+use crate::{memory::{Memory, Registers, FlagValue, AddressBus, DataBus, RegisterOperations, Register, DefaultRegister}, utils::{combine_to_double_byte, split_double_byte, self}, runtime::{RuntimeComponents}};
+use super::{Instruction, Operands, OperandKind};
 
 
 #[macro_export]
@@ -49,8 +49,8 @@ pub struct _0x01 {}
 impl Instruction for _0x01 {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         match operands {
-            Operands::Two(first, second) => {
-                RegisterOperations::ld_register_pair_with_value((&mut components.registers.b, &mut components.registers.c), combine_to_double_byte(second, first));
+            Operands::Word(value) => {
+                RegisterOperations::ld_register_pair_with_value((&mut components.registers.b, &mut components.registers.c), value);
             }
             _ => error!("Wrong operands used for ld_bc"),
         }
@@ -62,8 +62,9 @@ impl Instruction for _0x01 {
 
 pub struct _0x02 {}
 impl Instruction for _0x02 {
+    // Stores A into the memory location pointed to by BC.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::ld_register_from_addr_with_register_pair(&mut components.mem, &mut components.registers.a, (&components.registers.b, &components.registers.c));
+        RegisterOperations::ld_addr_from_reg_pair_with_register(&mut components.mem, (&components.registers.b, &components.registers.c), &components.registers.a);
         7
     }
 
@@ -73,7 +74,7 @@ impl Instruction for _0x02 {
 pub struct _0x03 {}
 impl Instruction for _0x03 {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::inc_register_pair((&mut components.registers.b, &mut components.registers.c), &mut components.registers.f);
+        RegisterOperations::inc_register_pair((&mut components.registers.b, &mut components.registers.c));
         6
     }
 
@@ -104,7 +105,7 @@ pub struct _0x06 {}
 impl Instruction for _0x06 {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         match operands {
-            Operands::One(value) => {
+            Operands::Immediate(value) => {
                 RegisterOperations::ld_register_with_value(&mut components.registers.b, value)
             }
             _ => error!("Wrong operands used for ld_b"),
@@ -121,18 +122,11 @@ impl Instruction for _0x07 {
     // The contents of A are rotated left one bit position. 
     // The contents of bit 7 are copied to the carry flag and bit 0.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        let value = components.registers.a.get();
-        let bit_7 = (value & 0x80) >> 7; // left-most bit (i.e. 128)
-        components.registers.a.set((value << 1) | bit_7);
-        match bit_7 {
-            0 => components.registers.f.set_carry(FlagValue::Unset),
-            1 => components.registers.f.set_carry(FlagValue::Set),
-            _ => error!("bit 7 incorrectly set for {}", self.assembly())
-        }
+        components.registers.a.rlca(&mut components.registers.f);
         4
     }
 
-    inst_metadata!(0, "07", "RCLA");
+    inst_metadata!(0, "07", "RLCA");
 }
 
 pub struct _0x08 {}
@@ -164,17 +158,27 @@ impl Instruction for _0x09 {
     inst_metadata!(0, "09", "ADD HL,BC");
 }
 
+pub struct _0x0A {}
+impl Instruction for _0x0A {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        //Loads the value pointed to by BC into A.
+        RegisterOperations::ld_register_from_addr_with_register_pair(&components.mem, &mut components.registers.a, (&components.registers.b, &components.registers.c));
+        7
+    }
+
+    inst_metadata!(0, "0A", "LD A,(BC)");
+}
+
 pub struct _0x10 {}
 impl Instruction for _0x10 {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        // If the zero flag is unset, the signed value d is added to PC. The jump is measured from the start of the instruction opcode.
+        // B is decremented, wrapping from 0 to #FF. If it is then non-zero, the signed value d is added to PC.
         match operands {
-            Operands::One(value) => {
-                let b = components.registers.b.get();
-                components.registers.b.set(b - 1);
-                if b-1 != 0 {
-                    let jump_val = signed(value);
-                    let val = components.registers.pc.get().wrapping_add(jump_val as u16); 
+            Operands::Relative(offset) => {
+                let b = components.registers.b.get().wrapping_sub(1);
+                components.registers.b.set(b);
+                if b != 0 {
+                    let val = components.registers.pc.get().wrapping_add(offset as u16);
                     components.registers.pc.set(val);
                     return 13;
                 }
@@ -184,6 +188,10 @@ impl Instruction for _0x10 {
         8
     }
 
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
     inst_metadata!(1, "10 *1", "DJNZ *1");
 }
 
@@ -192,7 +200,7 @@ pub struct _0x0B {}
 impl Instruction for _0x0B {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         let mut registers = &mut components.registers;
-        RegisterOperations::dec_register_pair((&mut registers.b, &mut registers.c), &mut registers.f);
+        RegisterOperations::dec_register_pair((&mut registers.b, &mut registers.c));
         6
     }
 
@@ -227,7 +235,7 @@ impl Instruction for _0x0E {
     // Loads n into C.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         match operands {
-            Operands::One(value) => {
+            Operands::Immediate(value) => {
                 RegisterOperations::ld_register_with_value(&mut components.registers.c, value)
             }
             _ => error!("Wrong operands used for {}", self.assembly()),
@@ -238,6 +246,18 @@ impl Instruction for _0x0E {
     inst_metadata!(1, "0E *1", "LD C,*1");
 }
 
+pub struct _0x0F {}
+impl Instruction for _0x0F {
+    // The contents of A are rotated right one bit position.
+    // The contents of bit 0 are copied to the carry flag and bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.rrca(&mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "0F", "RRCA");
+}
+
 
 // #10 to 1F
 
@@ -248,8 +268,8 @@ impl Instruction for _0x11 {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         // The signed value d is added to PC. The jump is measured from the start of the instruction opcode.
         match operands {
-            Operands::Two(low, high) => {
-                RegisterOperations::ld_register_pair_with_value((&mut components.registers.d, &mut components.registers.e), combine_to_double_byte(high, low));
+            Operands::Word(value) => {
+                RegisterOperations::ld_register_pair_with_value((&mut components.registers.d, &mut components.registers.e), value);
             }
             _ => error!("Wrong operands used for {}", self.assembly()),
         }
@@ -260,26 +280,89 @@ impl Instruction for _0x11 {
 }
 
 
+pub struct _0x12 {}
+impl Instruction for _0x12 {
+    // Stores A into the memory location pointed to by DE.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_addr_from_reg_pair_with_register(&mut components.mem, (&components.registers.d, &components.registers.e), &components.registers.a);
+        7
+    }
+
+    inst_metadata!(0, "12", "LD (DE),A");
+}
+
+pub struct _0x14 {}
+impl Instruction for _0x14 {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::inc(&mut components.registers.d, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "14", "INC D");
+}
+
+pub struct _0x15 {}
+impl Instruction for _0x15 {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::dec(&mut components.registers.d, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "15", "DEC D");
+}
+
+pub struct _0x16 {}
+impl Instruction for _0x16 {
+    // Loads n into D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Immediate(value) => {
+                RegisterOperations::ld_register_with_value(&mut components.registers.d, value)
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        7
+    }
+
+    inst_metadata!(1, "16 *1", "LD D,*1");
+}
+
+pub struct _0x17 {}
+impl Instruction for _0x17 {
+    // The contents of A are rotated left one bit position through the carry flag.
+    // The previous carry is copied to bit 0 and bit 7 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.rla(&mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "17", "RLA");
+}
+
 pub struct _0x18 {}
 impl Instruction for _0x18 {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         // The signed value d is added to PC. The jump is measured from the start of the instruction opcode.
         match operands {
-            Operands::One(op1) => {
-                components.registers.pc.set(components.registers.pc.get() + (op1 as u16));
+            Operands::Relative(offset) => {
+                components.registers.pc.set(components.registers.pc.get().wrapping_add(offset as u16));
             }
             _ => error!("Wrong operands used for {}", self.assembly()),
         }
         12
     }
 
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
     inst_metadata!(1, "18 *1", "JR *1");
 }
 
 pub struct _0x13 {}
 impl Instruction for _0x13 {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::inc_register_pair((&mut components.registers.d, &mut components.registers.e), &mut components.registers.f);
+        RegisterOperations::inc_register_pair((&mut components.registers.d, &mut components.registers.e));
         6
     }
 
@@ -301,14 +384,62 @@ impl Instruction for _0x19 {
 pub struct _0x1A {}
 impl Instruction for _0x1A {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        //Loads the value pointed to by BC into A.
-        RegisterOperations::ld_register_from_addr_with_register_pair(&components.mem, &mut components.registers.a, (&components.registers.b, &components.registers.c));
+        //Loads the value pointed to by DE into A.
+        RegisterOperations::ld_register_from_addr_with_register_pair(&components.mem, &mut components.registers.a, (&components.registers.d, &components.registers.e));
         7
     }
 
     inst_metadata!(0, "1A", "LD A,(DE)");
 }
 
+pub struct _0x1C {}
+impl Instruction for _0x1C {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::inc(&mut components.registers.e, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "1C", "INC E");
+}
+
+pub struct _0x1D {}
+impl Instruction for _0x1D {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::dec(&mut components.registers.e, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "1D", "DEC E");
+}
+
+pub struct _0x1E {}
+impl Instruction for _0x1E {
+    // Loads n into E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Immediate(value) => {
+                RegisterOperations::ld_register_with_value(&mut components.registers.e, value)
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        7
+    }
+
+    inst_metadata!(1, "1E *1", "LD E,*1");
+}
+
+pub struct _0x1F {}
+impl Instruction for _0x1F {
+    // The contents of A are rotated right one bit position through the carry flag.
+    // The previous carry is copied to bit 7 and bit 0 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.rra(&mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "1F", "RRA");
+}
+
 // #20 to 2F
 
 pub struct _0x20 {}
@@ -316,10 +447,9 @@ impl Instruction for _0x20 {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         // If the zero flag is unset, the signed value d is added to PC. The jump is measured from the start of the instruction opcode.
         match operands {
-            Operands::One(op1) => {
+            Operands::Relative(offset) => {
                 if components.registers.f.get_zero() == FlagValue::Unset {
-                    let jump_val = signed(op1);
-                    let val = components.registers.pc.get().wrapping_add(jump_val as u16);
+                    let val = components.registers.pc.get().wrapping_add(offset as u16);
                     components.registers.pc.set(val);
                     return 12;
                 }
@@ -329,6 +459,10 @@ impl Instruction for _0x20 {
         7
     }
 
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
     inst_metadata!(1, "20 *1", "JR NZ,*1");
 }
 
@@ -337,8 +471,8 @@ impl Instruction for _0x21 {
     // load nn into hl
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         match operands {
-            Operands::Two(op1, op2) => {
-                RegisterOperations::ld_register_pair_with_value((&mut components.registers.h, &mut components.registers.l), combine_to_double_byte(op2, op1));
+            Operands::Word(value) => {
+                RegisterOperations::ld_register_pair_with_value((&mut components.registers.h, &mut components.registers.l), value);
             }
             _ => error!("Wrong operands used for {}", self.assembly()),
         }
@@ -351,11 +485,11 @@ impl Instruction for _0x21 {
 
 pub struct _0x22 {}
 impl Instruction for _0x22 {
-    // //Stores HL into the memory location pointed to by nn.
+    // Stores L into the memory location pointed to by nn and H into nn + 1.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         match operands {
-            Operands::Two(op1, op2) => {
-                RegisterOperations::ld_addr_from_value_with_register_pair(&mut components.mem, combine_to_double_byte(op1, op2), (&components.registers.h, &components.registers.l));
+            Operands::Word(address) => {
+                RegisterOperations::ld_addr_from_value_with_register_pair(&mut components.mem, address, (&components.registers.h, &components.registers.l));
             }
             _ => error!("Wrong operands used for {}", self.assembly()),
         }
@@ -369,7 +503,7 @@ pub struct _0x23 {}
 impl Instruction for _0x23 {
     // inc hl
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::inc_register_pair((&mut components.registers.h, &mut components.registers.l), &mut components.registers.f);
+        RegisterOperations::inc_register_pair((&mut components.registers.h, &mut components.registers.l));
         6
     }
 
@@ -377,6 +511,77 @@ impl Instruction for _0x23 {
 }
 
 
+pub struct _0x24 {}
+impl Instruction for _0x24 {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::inc(&mut components.registers.h, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "24", "INC H");
+}
+
+pub struct _0x25 {}
+impl Instruction for _0x25 {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::dec(&mut components.registers.h, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "25", "DEC H");
+}
+
+pub struct _0x26 {}
+impl Instruction for _0x26 {
+    // Loads n into H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Immediate(value) => {
+                RegisterOperations::ld_register_with_value(&mut components.registers.h, value)
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        7
+    }
+
+    inst_metadata!(1, "26 *1", "LD H,*1");
+}
+
+pub struct _0x27 {}
+impl Instruction for _0x27 {
+    // Adjusts A for BCD addition and subtraction operations.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.daa(&mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "27", "DAA");
+}
+
+pub struct _0x28 {}
+impl Instruction for _0x28 {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        // If the zero flag is set, the signed value d is added to PC. The jump is measured from the start of the instruction opcode.
+        match operands {
+            Operands::Relative(offset) => {
+                if components.registers.f.get_zero() == FlagValue::Set {
+                    let val = components.registers.pc.get().wrapping_add(offset as u16);
+                    components.registers.pc.set(val);
+                    return 12;
+                }
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        7
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    inst_metadata!(1, "28 *1", "JR Z,*1");
+}
+
 pub struct _0x29 {}
 impl Instruction for _0x29 {
     // The value of HL is added to HL.
@@ -389,17 +594,43 @@ impl Instruction for _0x29 {
 }
 
 
+pub struct _0x2A {}
+impl Instruction for _0x2A {
+    // Loads L from the memory location pointed to by nn and H from nn + 1.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Word(address) => {
+                RegisterOperations::ld_register_pair_from_addr(&components.mem, (&mut components.registers.h, &mut components.registers.l), address);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        16
+    }
+
+    inst_metadata!(2, "2A *1 *2", "LD HL,(*2*1)");
+}
+
 pub struct _0x2B {}
 impl Instruction for _0x2B {
     // dec hl
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::dec_register_pair((&mut components.registers.h, &mut components.registers.l), &mut components.registers.f);
+        RegisterOperations::dec_register_pair((&mut components.registers.h, &mut components.registers.l));
         6
     }
 
     inst_metadata!(0, "2B", "DEC HL");
 }
 
+pub struct _0x2C {}
+impl Instruction for _0x2C {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::inc(&mut components.registers.l, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "2C", "INC L");
+}
+
 pub struct _0x2D {}
 impl Instruction for _0x2D {
     // dec l
@@ -411,6 +642,22 @@ impl Instruction for _0x2D {
     inst_metadata!(0, "2D", "DEC L");
 }
 
+pub struct _0x2E {}
+impl Instruction for _0x2E {
+    // Loads n into L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Immediate(value) => {
+                RegisterOperations::ld_register_with_value(&mut components.registers.l, value)
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        7
+    }
+
+    inst_metadata!(1, "2E *1", "LD L,*1");
+}
+
 pub struct _0x2F {}
 impl Instruction for _0x2F {
     // Contents of A are inverted
@@ -431,10 +678,9 @@ impl Instruction for _0x30 {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         // If the carry flag is unset, the signed value d is added to PC. The jump is measured from the start of the instruction opcode.
         match operands {
-            Operands::One(op1) => {
+            Operands::Relative(offset) => {
                 if components.registers.f.get_carry() == FlagValue::Unset {
-                    let jump_val = signed(op1);
-                    let val = components.registers.pc.get().wrapping_add(jump_val as u16);
+                    let val = components.registers.pc.get().wrapping_add(offset as u16);
                     components.registers.pc.set(val);
                     return 12;
                 }
@@ -444,6 +690,10 @@ impl Instruction for _0x30 {
         7
     }
 
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
     inst_metadata!(1, "30 *1", "JR NC,*1");
 }
 
@@ -452,8 +702,8 @@ impl Instruction for _0x31 {
     // load nn into sp
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         match operands {
-            Operands::Two(op1, op2) => {
-                components.registers.sp.set(combine_to_double_byte(op2, op1) as usize);
+            Operands::Word(value) => {
+                components.registers.sp.set(value as usize);
             }
             _ => error!("Wrong operands used for {}", self.assembly()),
         }
@@ -468,8 +718,8 @@ impl Instruction for _0x32 {
     // Stores A into the memory location pointed to by nn.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         match operands {
-            Operands::Two(op1, op2) => {
-                RegisterOperations::ld_addr_from_value_with_register(&mut components.mem, combine_to_double_byte(op2, op1), &components.registers.a);
+            Operands::Word(address) => {
+                RegisterOperations::ld_addr_from_value_with_register(&mut components.mem, address, &components.registers.a);
             }
             _ => error!("Wrong operands used for {}", self.assembly()),
         }
@@ -479,12 +729,49 @@ impl Instruction for _0x32 {
     inst_metadata!(2, "32 *1 *2", "LD (*2*1),A");
 }
 
+pub struct _0x33 {}
+impl Instruction for _0x33 {
+    // Adds one to SP.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.sp.inc();
+        6
+    }
+
+    inst_metadata!(0, "33", "INC SP");
+}
+
+pub struct _0x34 {}
+impl Instruction for _0x34 {
+    // Adds one to the value pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let addr = combine_to_double_byte(components.registers.h.get(), components.registers.l.get());
+        let value = RegisterOperations::inc_value(components.mem.read_u8(addr), &mut components.registers.f);
+        components.mem.write_u8(addr, value);
+        11
+    }
+
+    inst_metadata!(0, "34", "INC (HL)");
+}
+
+pub struct _0x35 {}
+impl Instruction for _0x35 {
+    // Subtracts one from the value pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let addr = combine_to_double_byte(components.registers.h.get(), components.registers.l.get());
+        let value = RegisterOperations::dec_value(components.mem.read_u8(addr), &mut components.registers.f);
+        components.mem.write_u8(addr, value);
+        11
+    }
+
+    inst_metadata!(0, "35", "DEC (HL)");
+}
+
 pub struct _0x36 {}
 impl Instruction for _0x36 {
     // Loads n into (HL).
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         match operands {
-            Operands::One(value) => {
+            Operands::Immediate(value) => {
                 RegisterOperations::ld_addr_from_reg_pair_with_value(&mut components.mem,(&mut components.registers.h, &mut components.registers.l), value);
             }
             _ => error!("Wrong operands used for {}", self.assembly()),
@@ -495,13 +782,49 @@ impl Instruction for _0x36 {
     inst_metadata!(1, "36 *1", "LD (HL),*1");
 }
 
+pub struct _0x38 {}
+impl Instruction for _0x38 {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        // If the carry flag is set, the signed value d is added to PC. The jump is measured from the start of the instruction opcode.
+        match operands {
+            Operands::Relative(offset) => {
+                if components.registers.f.get_carry() == FlagValue::Set {
+                    let val = components.registers.pc.get().wrapping_add(offset as u16);
+                    components.registers.pc.set(val);
+                    return 12;
+                }
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        7
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    inst_metadata!(1, "38 *1", "JR C,*1");
+}
+
+pub struct _0x39 {}
+impl Instruction for _0x39 {
+    // The value of SP is added to HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::add_sp_to_hl((&mut registers.h, &mut registers.l), &registers.sp, &mut registers.f);
+        11
+    }
+
+    inst_metadata!(0, "39", "ADD HL,SP");
+}
+
 pub struct _0x3A {}
 impl Instruction for _0x3A {
     // Loads the value pointed to by nn into A.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         match operands {
-            Operands::Two(op1, op2) => {
-                RegisterOperations::ld_register_from_addr(&components.mem, &mut components.registers.a, combine_to_double_byte(op2, op1));
+            Operands::Word(address) => {
+                RegisterOperations::ld_register_from_addr(&components.mem, &mut components.registers.a, address);
             }
             _ => error!("Wrong operands used for {}", self.assembly()),
         }
@@ -511,6 +834,17 @@ impl Instruction for _0x3A {
     inst_metadata!(2, "3A *1 *2", "LD A,(*2*1)");
 }
 
+pub struct _0x3B {}
+impl Instruction for _0x3B {
+    // Subtracts one from SP.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.sp.dec();
+        6
+    }
+
+    inst_metadata!(0, "3B", "DEC SP");
+}
+
 pub struct _0x3C {}
 impl Instruction for _0x3C {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
@@ -521,12 +855,22 @@ impl Instruction for _0x3C {
     inst_metadata!(0, "3C", "INC A");
 }
 
+pub struct _0x3D {}
+impl Instruction for _0x3D {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::dec(&mut components.registers.a, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "3D", "DEC A");
+}
+
 pub struct _0x3E {}
 impl Instruction for _0x3E {
     // load nn into hl
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         match operands {
-            Operands::One(value) => {
+            Operands::Immediate(value) => {
                 RegisterOperations::ld_register_with_value(&mut components.registers.a, value);
             }
             _ => error!("Wrong operands used for {}", self.assembly()),
@@ -540,6 +884,16 @@ impl Instruction for _0x3E {
 
 // #40 to 4F
 
+pub struct _0x40 {}
+impl Instruction for _0x40 {
+    // Loading B into itself leaves it unchanged.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        4
+    }
+
+    inst_metadata!(0, "40", "LD B,B");
+}
+
 pub struct _0x41 {}
 impl Instruction for _0x41 {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
@@ -551,636 +905,2609 @@ impl Instruction for _0x41 {
     inst_metadata!(0, "41", "LD B,C");
 }
 
-pub struct _0x47 {}
-impl Instruction for _0x47 {
+pub struct _0x42 {}
+impl Instruction for _0x42 {
+    // The contents of D are loaded into B.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        // The contents of A are loaded into B.
-        RegisterOperations::ld_register_from_register(&components.registers.a, &mut components.registers.b);
+        RegisterOperations::ld_register_from_register(&components.registers.d, &mut components.registers.b);
         4
     }
 
-    inst_metadata!(0, "47", "LD B,A");
+    inst_metadata!(0, "42", "LD B,D");
 }
 
-pub struct _0x4C {}
-impl Instruction for _0x4C {
+pub struct _0x43 {}
+impl Instruction for _0x43 {
+    // The contents of E are loaded into B.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        // Contents of h are loaded into c
-        RegisterOperations::ld_register_from_register(&components.registers.h, &mut components.registers.c);
+        RegisterOperations::ld_register_from_register(&components.registers.e, &mut components.registers.b);
         4
     }
 
-    inst_metadata!(0, "4C", "LD C,H");
+    inst_metadata!(0, "43", "LD B,E");
 }
 
-pub struct _0x4E {}
-impl Instruction for _0x4E {
+pub struct _0x44 {}
+impl Instruction for _0x44 {
+    // The contents of H are loaded into B.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        let reg = &mut components.registers;
-        RegisterOperations::ld_register_from_addr_with_register_pair(&components.mem, &mut reg.c, (&reg.h, &reg.l));
-        7
+        RegisterOperations::ld_register_from_register(&components.registers.h, &mut components.registers.b);
+        4
     }
 
-    inst_metadata!(0, "4E", "LD C,(HL)");
+    inst_metadata!(0, "44", "LD B,H");
 }
 
+pub struct _0x45 {}
+impl Instruction for _0x45 {
+    // The contents of L are loaded into B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.l, &mut components.registers.b);
+        4
+    }
 
+    inst_metadata!(0, "45", "LD B,L");
+}
 
-// #50 to 5E
-
-// ld d,(hl)
-pub struct _0x56 {}
-impl Instruction for _0x56 {
+pub struct _0x46 {}
+impl Instruction for _0x46 {
+    // The contents of (HL) are loaded into B.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         let reg = &mut components.registers;
-        RegisterOperations::ld_register_from_addr_with_register_pair(&components.mem, &mut reg.d, (&reg.h, &reg.l));
+        RegisterOperations::ld_register_from_addr_with_register_pair(&components.mem, &mut reg.b, (&reg.h, &reg.l));
         7
     }
 
-    inst_metadata!(0, "56", "LD D,(HL)");
+    inst_metadata!(0, "46", "LD B,(HL)");
 }
 
-pub struct _0x5E {}
-impl Instruction for _0x5E {
+pub struct _0x47 {}
+impl Instruction for _0x47 {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        let reg = &mut components.registers;
-        RegisterOperations::ld_register_from_addr_with_register_pair(&components.mem, &mut reg.e, (&reg.h, &reg.l));
-        7
+        // The contents of A are loaded into B.
+        RegisterOperations::ld_register_from_register(&components.registers.a, &mut components.registers.b);
+        4
     }
 
-    inst_metadata!(0, "5E", "LD E,(HL)");
+    inst_metadata!(0, "47", "LD B,A");
 }
 
+pub struct _0x48 {}
+impl Instruction for _0x48 {
+    // The contents of B are loaded into C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.b, &mut components.registers.c);
+        4
+    }
 
+    inst_metadata!(0, "48", "LD C,B");
+}
 
-// #60 to 6F
-
-pub struct _0x67 {}
-impl Instruction for _0x67 {
-    // The contents of A are loaded into H.
+pub struct _0x49 {}
+impl Instruction for _0x49 {
+    // Loading C into itself leaves it unchanged.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::ld_register_from_register(&components.registers.a, &mut components.registers.h);
         4
     }
 
-    inst_metadata!(0, "67", "LD H,A");
+    inst_metadata!(0, "49", "LD C,C");
 }
 
-pub struct _0x6F {}
-impl Instruction for _0x6F {
-    // The contents of A are loaded into L.
+pub struct _0x4A {}
+impl Instruction for _0x4A {
+    // The contents of D are loaded into C.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::ld_register_from_register(&components.registers.a, &mut components.registers.l);
+        RegisterOperations::ld_register_from_register(&components.registers.d, &mut components.registers.c);
         4
     }
 
-    inst_metadata!(0, "6F", "LD L,A");
+    inst_metadata!(0, "4A", "LD C,D");
 }
 
-// #70 to 7F
-
-//The contents of B are loaded into (HL).
-pub struct _0x70 {}
-impl Instruction for _0x70 {
-    // The contents of B are loaded into (HL).
+pub struct _0x4B {}
+impl Instruction for _0x4B {
+    // The contents of E are loaded into C.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::ld_addr_from_reg_pair_with_register(&mut components.mem, (&components.registers.h, &components.registers.l), &components.registers.b);
-        7
+        RegisterOperations::ld_register_from_register(&components.registers.e, &mut components.registers.c);
+        4
     }
 
-    inst_metadata!(0, "70", "LD (HL),B");
+    inst_metadata!(0, "4B", "LD C,E");
 }
 
-pub struct _0x71 {}
-impl Instruction for _0x71 {
-    // The contents of C are loaded into (HL).
+pub struct _0x4C {}
+impl Instruction for _0x4C {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::ld_addr_from_reg_pair_with_register(&mut components.mem, (&components.registers.h, &components.registers.l), &components.registers.c);
-        7
+        // Contents of h are loaded into c
+        RegisterOperations::ld_register_from_register(&components.registers.h, &mut components.registers.c);
+        4
     }
 
-    inst_metadata!(0, "71", "LD (HL),C");
+    inst_metadata!(0, "4C", "LD C,H");
 }
 
-
-pub struct _0x72 {}
-impl Instruction for _0x72 {
-    // The contents of D are loaded into (HL).
+pub struct _0x4D {}
+impl Instruction for _0x4D {
+    // The contents of L are loaded into C.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::ld_addr_from_reg_pair_with_register(&mut components.mem, (&components.registers.h, &components.registers.l), &components.registers.d);
-        7
+        RegisterOperations::ld_register_from_register(&components.registers.l, &mut components.registers.c);
+        4
     }
 
-    inst_metadata!(0, "72", "LD (HL),D");
+    inst_metadata!(0, "4D", "LD C,L");
 }
 
-pub struct _0x73 {}
-impl Instruction for _0x73 {
-    // The contents of E are loaded into (HL).
+pub struct _0x4E {}
+impl Instruction for _0x4E {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::ld_addr_from_reg_pair_with_register(&mut components.mem, (&components.registers.h, &components.registers.l), &components.registers.e);
+        let reg = &mut components.registers;
+        RegisterOperations::ld_register_from_addr_with_register_pair(&components.mem, &mut reg.c, (&reg.h, &reg.l));
         7
     }
 
-    inst_metadata!(0, "73", "LD (HL),E");
+    inst_metadata!(0, "4E", "LD C,(HL)");
 }
 
-pub struct _0x77 {}
-impl Instruction for _0x77 {
-    // The contents of A are loaded into (HL).
+pub struct _0x4F {}
+impl Instruction for _0x4F {
+    // The contents of A are loaded into C.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::ld_addr_from_reg_pair_with_register(&mut components.mem, (&components.registers.h, &components.registers.l), &components.registers.a);
-        7
+        RegisterOperations::ld_register_from_register(&components.registers.a, &mut components.registers.c);
+        4
     }
 
-    inst_metadata!(0, "77", "LD (HL),A");
+    inst_metadata!(0, "4F", "LD C,A");
 }
 
-pub struct _0x78 {}
-impl Instruction for _0x78 {
+
+// #50 to 5F
+
+pub struct _0x50 {}
+impl Instruction for _0x50 {
+    // The contents of B are loaded into D.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::ld_register_from_register(&components.registers.b, &mut components.registers.a);
+        RegisterOperations::ld_register_from_register(&components.registers.b, &mut components.registers.d);
         4
     }
 
-    inst_metadata!(0, "78", "LD A,B");
+    inst_metadata!(0, "50", "LD D,B");
 }
 
-pub struct _0x79 {}
-impl Instruction for _0x79 {
+pub struct _0x51 {}
+impl Instruction for _0x51 {
+    // The contents of C are loaded into D.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::ld_register_from_register(&components.registers.c, &mut components.registers.a);
+        RegisterOperations::ld_register_from_register(&components.registers.c, &mut components.registers.d);
         4
     }
 
-    inst_metadata!(0, "79", "LD A,C");
+    inst_metadata!(0, "51", "LD D,C");
 }
 
-pub struct _0x7C {}
-impl Instruction for _0x7C {
+pub struct _0x52 {}
+impl Instruction for _0x52 {
+    // Loading D into itself leaves it unchanged.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::ld_register_from_register(&components.registers.h, &mut components.registers.a);
         4
     }
 
-    inst_metadata!(0, "7C", "LD A,H");
+    inst_metadata!(0, "52", "LD D,D");
 }
 
-pub struct _0x7D {}
-impl Instruction for _0x7D {
+pub struct _0x53 {}
+impl Instruction for _0x53 {
+    // The contents of E are loaded into D.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::ld_register_from_register(&components.registers.l, &mut components.registers.a);
+        RegisterOperations::ld_register_from_register(&components.registers.e, &mut components.registers.d);
         4
     }
 
-    inst_metadata!(0, "7D", "LD A,L");
+    inst_metadata!(0, "53", "LD D,E");
 }
 
-pub struct _0x7E {}
-impl Instruction for _0x7E {
+pub struct _0x54 {}
+impl Instruction for _0x54 {
+    // The contents of H are loaded into D.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::ld_register_from_addr_with_register_pair(&components.mem, &mut components.registers.a, (&components.registers.h, &components.registers.l));
-        7
+        RegisterOperations::ld_register_from_register(&components.registers.h, &mut components.registers.d);
+        4
     }
 
-    inst_metadata!(0, "7E", "LD A,(HL)");
+    inst_metadata!(0, "54", "LD D,H");
 }
 
+pub struct _0x55 {}
+impl Instruction for _0x55 {
+    // The contents of L are loaded into D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.l, &mut components.registers.d);
+        4
+    }
 
+    inst_metadata!(0, "55", "LD D,L");
+}
 
-// #A0 to AF
+// ld d,(hl)
+pub struct _0x56 {}
+impl Instruction for _0x56 {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::ld_register_from_addr_with_register_pair(&components.mem, &mut reg.d, (&reg.h, &reg.l));
+        7
+    }
 
+    inst_metadata!(0, "56", "LD D,(HL)");
+}
 
-pub struct _0xA9 {}
-impl Instruction for _0xA9 {
-    // Bitwise XOR on A with C.
+pub struct _0x57 {}
+impl Instruction for _0x57 {
+    // The contents of A are loaded into D.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        let registers = &mut components.registers;
-        registers.a.xor(&registers.c, &mut registers.f);
+        RegisterOperations::ld_register_from_register(&components.registers.a, &mut components.registers.d);
         4
     }
 
-    inst_metadata!(0, "A9", "XOR C");
+    inst_metadata!(0, "57", "LD D,A");
 }
 
-
-pub struct _0xAF {}
-impl Instruction for _0xAF {
+pub struct _0x58 {}
+impl Instruction for _0x58 {
+    // The contents of B are loaded into E.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        components.registers.a.xor_a(&mut components.registers.f);
+        RegisterOperations::ld_register_from_register(&components.registers.b, &mut components.registers.e);
         4
     }
 
-    inst_metadata!(0, "AF", "XOR A");
+    inst_metadata!(0, "58", "LD E,B");
 }
 
+pub struct _0x59 {}
+impl Instruction for _0x59 {
+    // The contents of C are loaded into E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.c, &mut components.registers.e);
+        4
+    }
 
+    inst_metadata!(0, "59", "LD E,C");
+}
 
-// #B0 to BF
+pub struct _0x5A {}
+impl Instruction for _0x5A {
+    // The contents of D are loaded into E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.d, &mut components.registers.e);
+        4
+    }
 
+    inst_metadata!(0, "5A", "LD E,D");
+}
 
-pub struct _0xB6 {}
-impl Instruction for _0xB6 {
-    // OR a with (hl)
+pub struct _0x5B {}
+impl Instruction for _0x5B {
+    // Loading E into itself leaves it unchanged.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        components.registers.a.xor_address_from_reg_pair(&components.mem, (&components.registers.h, &components.registers.l), &mut components.registers.f);
-        7
+        4
     }
 
-    inst_metadata!(0, "B6", "OR (HL)");
+    inst_metadata!(0, "5B", "LD E,E");
 }
 
-
-pub struct _0xB7 {}
-impl Instruction for _0xB7 {
-    // Bitwise OR on A with A.
+pub struct _0x5C {}
+impl Instruction for _0x5C {
+    // The contents of H are loaded into E.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        components.registers.a.xor_address_from_reg_pair(&components.mem, (&components.registers.h, &components.registers.l), &mut components.registers.f);
+        RegisterOperations::ld_register_from_register(&components.registers.h, &mut components.registers.e);
         4
     }
 
-    inst_metadata!(0, "B7", "OR A");
+    inst_metadata!(0, "5C", "LD E,H");
 }
 
-pub struct _0xBB {}
-impl Instruction for _0xBB {
-    // Subtracts E from A and affects flags according to the result. A is not modified.
+pub struct _0x5D {}
+impl Instruction for _0x5D {
+    // The contents of L are loaded into E.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        components.registers.a.compare_reg(&components.registers.e, &mut components.registers.f);
+        RegisterOperations::ld_register_from_register(&components.registers.l, &mut components.registers.e);
         4
     }
 
-    inst_metadata!(0, "BB", "CP E");
+    inst_metadata!(0, "5D", "LD E,L");
 }
 
+pub struct _0x5E {}
+impl Instruction for _0x5E {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::ld_register_from_addr_with_register_pair(&components.mem, &mut reg.e, (&reg.h, &reg.l));
+        7
+    }
 
-// #C0 to CF
+    inst_metadata!(0, "5E", "LD E,(HL)");
+}
 
-pub struct _0xC0 {}
-impl Instruction for _0xC0 {
+pub struct _0x5F {}
+impl Instruction for _0x5F {
+    // The contents of A are loaded into E.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        // if zero flag is not set, pop sp value onto pc
-        if components.registers.f.get_zero() == FlagValue::Unset {
-            components.registers.pc.set(components.registers.sp.pop(&components.mem));
-            return 11;
-        }
-        5
+        RegisterOperations::ld_register_from_register(&components.registers.a, &mut components.registers.e);
+        4
     }
 
-    inst_metadata!(0, "C0", "RET NZ");
+    inst_metadata!(0, "5F", "LD E,A");
 }
 
-pub struct _0xC2 {}
-impl Instruction for _0xC2 {
-    
-    // Jump to address provided in operands if zero flag is set
+
+// #60 to 6F
+
+pub struct _0x60 {}
+impl Instruction for _0x60 {
+    // The contents of B are loaded into H.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        if components.registers.f.get_zero() ==  FlagValue::Unset {
-            if let Operands::Two(low, high) = operands {
-                components.registers.pc.set(utils::combine_to_double_byte(high, low));
-            }
-        }
-        10
+        RegisterOperations::ld_register_from_register(&components.registers.b, &mut components.registers.h);
+        4
     }
 
-    inst_metadata!(2, "C2 *1 *2", "JP NZ,*2*1");
+    inst_metadata!(0, "60", "LD H,B");
 }
 
-pub struct _0xC3 {}
-impl Instruction for _0xC3 {
-    
-    // Jump to address provided in operands
-    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16{
-        if let Operands::Two(low, high) = operands {
-            components.registers.pc.set(utils::combine_to_double_byte(high, low));
-        }
-        10
+pub struct _0x61 {}
+impl Instruction for _0x61 {
+    // The contents of C are loaded into H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.c, &mut components.registers.h);
+        4
     }
 
-    inst_metadata!(2, "C3 *1 *2", "JP *2*1");
+    inst_metadata!(0, "61", "LD H,C");
 }
 
-pub struct _0xC5 {}
-impl Instruction for _0xC5 {
-
-    // Push contents of B and C onto stack.
+pub struct _0x62 {}
+impl Instruction for _0x62 {
+    // The contents of D are loaded into H.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::push_register_pair((&components.registers.b, &components.registers.c), &mut components.registers.sp, &mut components.mem);
-        11
+        RegisterOperations::ld_register_from_register(&components.registers.d, &mut components.registers.h);
+        4
     }
 
-    inst_metadata!(0, "C5", "PUSH BC");
+    inst_metadata!(0, "62", "LD H,D");
 }
 
-pub struct _0xC8 {}
-impl Instruction for _0xC8 {
+pub struct _0x63 {}
+impl Instruction for _0x63 {
+    // The contents of E are loaded into H.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        // if zero flag is set, pop sp value onto pc
-        if components.registers.f.get_zero() == FlagValue::Set {
-            components.registers.pc.set(components.registers.sp.pop(&components.mem));
-            return 11;
-        }
-        5
+        RegisterOperations::ld_register_from_register(&components.registers.e, &mut components.registers.h);
+        4
     }
 
-    inst_metadata!(0, "C8", "RET Z");
+    inst_metadata!(0, "63", "LD H,E");
 }
 
-pub struct _0xC9 {}
-impl Instruction for _0xC9 {
+pub struct _0x64 {}
+impl Instruction for _0x64 {
+    // Loading H into itself leaves it unchanged.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        let addr = components.registers.sp.pop(&&components.mem);
-        components.registers.pc.set(addr);
-        10
+        4
     }
 
-    inst_metadata!(0, "C9", "RET");
+    inst_metadata!(0, "64", "LD H,H");
 }
 
-pub struct _0xCD {}
-impl Instruction for _0xCD {
-    
-    // The current PC value plus three is pushed onto the stack, then is loaded with nn.
-    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16{
-        if let Operands::Two(low, high) = operands {
-            RegisterOperations::call(utils::combine_to_double_byte(high, low), &mut components.registers.sp, &mut components.registers.pc, &mut components.mem);
-        }
-        17
+pub struct _0x65 {}
+impl Instruction for _0x65 {
+    // The contents of L are loaded into H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.l, &mut components.registers.h);
+        4
     }
 
-    inst_metadata!(2, "CD", "CALL *2*1");
+    inst_metadata!(0, "65", "LD H,L");
 }
 
+pub struct _0x66 {}
+impl Instruction for _0x66 {
+    // The contents of (HL) are loaded into H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let addr = combine_to_double_byte(components.registers.h.get(), components.registers.l.get());
+        RegisterOperations::ld_register_from_addr(&components.mem, &mut components.registers.h, addr);
+        7
+    }
 
-// #D0 to DF
+    inst_metadata!(0, "66", "LD H,(HL)");
+}
 
-pub struct _0xC1 {}
-impl Instruction for _0xC1 {
-    // The memory location pointed to by SP is stored into B and SP is incremented. 
-    // The memory location pointed to by SP is stored into C and SP is incremented again.   
+pub struct _0x67 {}
+impl Instruction for _0x67 {
+    // The contents of A are loaded into H.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::pop_register_pair((&mut components.registers.b, &mut components.registers.c), &mut components.registers.sp, &mut components.mem);
-        10
+        RegisterOperations::ld_register_from_register(&components.registers.a, &mut components.registers.h);
+        4
     }
 
-    inst_metadata!(0, "D1", "POP BC");
+    inst_metadata!(0, "67", "LD H,A");
 }
 
-pub struct _0xD1 {}
-impl Instruction for _0xD1 {
-    // The memory location pointed to by SP is stored into E and SP is incremented. 
-    // The memory location pointed to by SP is stored into D and SP is incremented again.   
+pub struct _0x68 {}
+impl Instruction for _0x68 {
+    // The contents of B are loaded into L.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::pop_register_pair((&mut components.registers.d, &mut components.registers.e), &mut components.registers.sp, &mut components.mem);
-        10
+        RegisterOperations::ld_register_from_register(&components.registers.b, &mut components.registers.l);
+        4
     }
 
-    inst_metadata!(0, "D1", "POP DE");
+    inst_metadata!(0, "68", "LD L,B");
 }
 
-pub struct _0xD5 {}
-impl Instruction for _0xD5 {
-    // Push contents of H and L onto stack.
+pub struct _0x69 {}
+impl Instruction for _0x69 {
+    // The contents of C are loaded into L.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::push_register_pair((&components.registers.d, &components.registers.e), &mut components.registers.sp, &mut components.mem);
-        11
+        RegisterOperations::ld_register_from_register(&components.registers.c, &mut components.registers.l);
+        4
     }
 
-    inst_metadata!(0, "D5", "PUSH DE");
+    inst_metadata!(0, "69", "LD L,C");
 }
 
-pub struct _0xD6 {}
-impl Instruction for _0xD6 {
-    // Subtract n from A
-    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16{
-        if let Operands::One(value) = operands {
-            components.registers.a.sub_value(value, &mut components.registers.f);
-        } else {
-            panic!("Wrong operand for {}", self.assembly());
-        }
-        17
+pub struct _0x6A {}
+impl Instruction for _0x6A {
+    // The contents of D are loaded into L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.d, &mut components.registers.l);
+        4
     }
 
-    inst_metadata!(1, "D6 *1", "SUB *1");
+    inst_metadata!(0, "6A", "LD L,D");
 }
 
+pub struct _0x6B {}
+impl Instruction for _0x6B {
+    // The contents of E are loaded into L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.e, &mut components.registers.l);
+        4
+    }
 
-pub struct _0xD8 {}
-impl Instruction for _0xD8 {
+    inst_metadata!(0, "6B", "LD L,E");
+}
+
+pub struct _0x6C {}
+impl Instruction for _0x6C {
+    // The contents of H are loaded into L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.h, &mut components.registers.l);
+        4
+    }
+
+    inst_metadata!(0, "6C", "LD L,H");
+}
+
+pub struct _0x6D {}
+impl Instruction for _0x6D {
+    // Loading L into itself leaves it unchanged.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        4
+    }
+
+    inst_metadata!(0, "6D", "LD L,L");
+}
+
+pub struct _0x6E {}
+impl Instruction for _0x6E {
+    // The contents of (HL) are loaded into L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let addr = combine_to_double_byte(components.registers.h.get(), components.registers.l.get());
+        RegisterOperations::ld_register_from_addr(&components.mem, &mut components.registers.l, addr);
+        7
+    }
+
+    inst_metadata!(0, "6E", "LD L,(HL)");
+}
+
+pub struct _0x6F {}
+impl Instruction for _0x6F {
+    // The contents of A are loaded into L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.a, &mut components.registers.l);
+        4
+    }
+
+    inst_metadata!(0, "6F", "LD L,A");
+}
+
+
+// #70 to 7F
+
+//The contents of B are loaded into (HL).
+pub struct _0x70 {}
+impl Instruction for _0x70 {
+    // The contents of B are loaded into (HL).
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_addr_from_reg_pair_with_register(&mut components.mem, (&components.registers.h, &components.registers.l), &components.registers.b);
+        7
+    }
+
+    inst_metadata!(0, "70", "LD (HL),B");
+}
+
+pub struct _0x71 {}
+impl Instruction for _0x71 {
+    // The contents of C are loaded into (HL).
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_addr_from_reg_pair_with_register(&mut components.mem, (&components.registers.h, &components.registers.l), &components.registers.c);
+        7
+    }
+
+    inst_metadata!(0, "71", "LD (HL),C");
+}
+
+pub struct _0x72 {}
+impl Instruction for _0x72 {
+    // The contents of D are loaded into (HL).
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_addr_from_reg_pair_with_register(&mut components.mem, (&components.registers.h, &components.registers.l), &components.registers.d);
+        7
+    }
+
+    inst_metadata!(0, "72", "LD (HL),D");
+}
+
+pub struct _0x73 {}
+impl Instruction for _0x73 {
+    // The contents of E are loaded into (HL).
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_addr_from_reg_pair_with_register(&mut components.mem, (&components.registers.h, &components.registers.l), &components.registers.e);
+        7
+    }
+
+    inst_metadata!(0, "73", "LD (HL),E");
+}
+
+pub struct _0x74 {}
+impl Instruction for _0x74 {
+    // The contents of H are loaded into (HL).
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_addr_from_reg_pair_with_register(&mut components.mem, (&components.registers.h, &components.registers.l), &components.registers.h);
+        7
+    }
+
+    inst_metadata!(0, "74", "LD (HL),H");
+}
+
+pub struct _0x75 {}
+impl Instruction for _0x75 {
+    // The contents of L are loaded into (HL).
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_addr_from_reg_pair_with_register(&mut components.mem, (&components.registers.h, &components.registers.l), &components.registers.l);
+        7
+    }
+
+    inst_metadata!(0, "75", "LD (HL),L");
+}
+
+pub struct _0x76 {}
+impl Instruction for _0x76 {
+    // Suspends the CPU until an interrupt arrives. PC is left on the following instruction,
+    // which is where execution resumes once the interrupt has been handled.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.halted = true;
+        4
+    }
+
+    inst_metadata!(0, "76", "HALT");
+}
+
+pub struct _0x77 {}
+impl Instruction for _0x77 {
+    // The contents of A are loaded into (HL).
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_addr_from_reg_pair_with_register(&mut components.mem, (&components.registers.h, &components.registers.l), &components.registers.a);
+        7
+    }
+
+    inst_metadata!(0, "77", "LD (HL),A");
+}
+
+pub struct _0x78 {}
+impl Instruction for _0x78 {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.b, &mut components.registers.a);
+        4
+    }
+
+    inst_metadata!(0, "78", "LD A,B");
+}
+
+pub struct _0x79 {}
+impl Instruction for _0x79 {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.c, &mut components.registers.a);
+        4
+    }
+
+    inst_metadata!(0, "79", "LD A,C");
+}
+
+pub struct _0x7A {}
+impl Instruction for _0x7A {
+    // The contents of D are loaded into A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.d, &mut components.registers.a);
+        4
+    }
+
+    inst_metadata!(0, "7A", "LD A,D");
+}
+
+pub struct _0x7B {}
+impl Instruction for _0x7B {
+    // The contents of E are loaded into A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.e, &mut components.registers.a);
+        4
+    }
+
+    inst_metadata!(0, "7B", "LD A,E");
+}
+
+pub struct _0x7C {}
+impl Instruction for _0x7C {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.h, &mut components.registers.a);
+        4
+    }
+
+    inst_metadata!(0, "7C", "LD A,H");
+}
+
+pub struct _0x7D {}
+impl Instruction for _0x7D {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_register(&components.registers.l, &mut components.registers.a);
+        4
+    }
+
+    inst_metadata!(0, "7D", "LD A,L");
+}
+
+pub struct _0x7E {}
+impl Instruction for _0x7E {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::ld_register_from_addr_with_register_pair(&components.mem, &mut components.registers.a, (&components.registers.h, &components.registers.l));
+        7
+    }
+
+    inst_metadata!(0, "7E", "LD A,(HL)");
+}
+
+pub struct _0x7F {}
+impl Instruction for _0x7F {
+    // Loading A into itself leaves it unchanged.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        4
+    }
+
+    inst_metadata!(0, "7F", "LD A,A");
+}
+
+
+// #80 to 8F
+
+pub struct _0x80 {}
+impl Instruction for _0x80 {
+    // B is added to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.add_a(&components.registers.b, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "80", "ADD A,B");
+}
+
+pub struct _0x81 {}
+impl Instruction for _0x81 {
+    // C is added to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.add_a(&components.registers.c, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "81", "ADD A,C");
+}
+
+pub struct _0x82 {}
+impl Instruction for _0x82 {
+    // D is added to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.add_a(&components.registers.d, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "82", "ADD A,D");
+}
+
+pub struct _0x83 {}
+impl Instruction for _0x83 {
+    // E is added to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.add_a(&components.registers.e, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "83", "ADD A,E");
+}
+
+pub struct _0x84 {}
+impl Instruction for _0x84 {
+    // H is added to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.add_a(&components.registers.h, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "84", "ADD A,H");
+}
+
+pub struct _0x85 {}
+impl Instruction for _0x85 {
+    // L is added to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.add_a(&components.registers.l, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "85", "ADD A,L");
+}
+
+pub struct _0x86 {}
+impl Instruction for _0x86 {
+    // The value pointed to by HL is added to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let addr = combine_to_double_byte(components.registers.h.get(), components.registers.l.get());
+        let value = components.mem.read_u8(addr);
+        components.registers.a.add_value(value, &mut components.registers.f);
+        7
+    }
+
+    inst_metadata!(0, "86", "ADD A,(HL)");
+}
+
+pub struct _0x87 {}
+impl Instruction for _0x87 {
+    // A is added to itself.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let value = components.registers.a.get();
+        components.registers.a.add_value(value, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "87", "ADD A,A");
+}
+
+pub struct _0x88 {}
+impl Instruction for _0x88 {
+    // B and the carry flag are added to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.adc_a(&components.registers.b, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "88", "ADC A,B");
+}
+
+pub struct _0x89 {}
+impl Instruction for _0x89 {
+    // C and the carry flag are added to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.adc_a(&components.registers.c, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "89", "ADC A,C");
+}
+
+pub struct _0x8A {}
+impl Instruction for _0x8A {
+    // D and the carry flag are added to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.adc_a(&components.registers.d, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "8A", "ADC A,D");
+}
+
+pub struct _0x8B {}
+impl Instruction for _0x8B {
+    // E and the carry flag are added to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.adc_a(&components.registers.e, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "8B", "ADC A,E");
+}
+
+pub struct _0x8C {}
+impl Instruction for _0x8C {
+    // H and the carry flag are added to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.adc_a(&components.registers.h, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "8C", "ADC A,H");
+}
+
+pub struct _0x8D {}
+impl Instruction for _0x8D {
+    // L and the carry flag are added to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.adc_a(&components.registers.l, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "8D", "ADC A,L");
+}
+
+pub struct _0x8E {}
+impl Instruction for _0x8E {
+    // The value pointed to by HL and the carry flag are added to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let addr = combine_to_double_byte(components.registers.h.get(), components.registers.l.get());
+        let value = components.mem.read_u8(addr);
+        components.registers.a.adc_value(value, &mut components.registers.f);
+        7
+    }
+
+    inst_metadata!(0, "8E", "ADC A,(HL)");
+}
+
+pub struct _0x8F {}
+impl Instruction for _0x8F {
+    // A and the carry flag are added to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let value = components.registers.a.get();
+        components.registers.a.adc_value(value, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "8F", "ADC A,A");
+}
+
+
+// #90 to 9F
+
+pub struct _0x90 {}
+impl Instruction for _0x90 {
+    // B is subtracted from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.sub_reg(&components.registers.b, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "90", "SUB B");
+}
+
+pub struct _0x91 {}
+impl Instruction for _0x91 {
+    // C is subtracted from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.sub_reg(&components.registers.c, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "91", "SUB C");
+}
+
+pub struct _0x92 {}
+impl Instruction for _0x92 {
+    // D is subtracted from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.sub_reg(&components.registers.d, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "92", "SUB D");
+}
+
+pub struct _0x93 {}
+impl Instruction for _0x93 {
+    // E is subtracted from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.sub_reg(&components.registers.e, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "93", "SUB E");
+}
+
+pub struct _0x94 {}
+impl Instruction for _0x94 {
+    // H is subtracted from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.sub_reg(&components.registers.h, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "94", "SUB H");
+}
+
+pub struct _0x95 {}
+impl Instruction for _0x95 {
+    // L is subtracted from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.sub_reg(&components.registers.l, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "95", "SUB L");
+}
+
+pub struct _0x96 {}
+impl Instruction for _0x96 {
+    // The value pointed to by HL is subtracted from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let addr = combine_to_double_byte(components.registers.h.get(), components.registers.l.get());
+        let value = components.mem.read_u8(addr);
+        components.registers.a.sub_value(value, &mut components.registers.f);
+        7
+    }
+
+    inst_metadata!(0, "96", "SUB (HL)");
+}
+
+pub struct _0x97 {}
+impl Instruction for _0x97 {
+    // A is subtracted from itself, leaving zero.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let value = components.registers.a.get();
+        components.registers.a.sub_value(value, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "97", "SUB A");
+}
+
+pub struct _0x98 {}
+impl Instruction for _0x98 {
+    // B and the carry flag are subtracted from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.sbc_a(&components.registers.b, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "98", "SBC A,B");
+}
+
+pub struct _0x99 {}
+impl Instruction for _0x99 {
+    // C and the carry flag are subtracted from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.sbc_a(&components.registers.c, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "99", "SBC A,C");
+}
+
+pub struct _0x9A {}
+impl Instruction for _0x9A {
+    // D and the carry flag are subtracted from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.sbc_a(&components.registers.d, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "9A", "SBC A,D");
+}
+
+pub struct _0x9B {}
+impl Instruction for _0x9B {
+    // E and the carry flag are subtracted from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.sbc_a(&components.registers.e, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "9B", "SBC A,E");
+}
+
+pub struct _0x9C {}
+impl Instruction for _0x9C {
+    // H and the carry flag are subtracted from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.sbc_a(&components.registers.h, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "9C", "SBC A,H");
+}
+
+pub struct _0x9D {}
+impl Instruction for _0x9D {
+    // L and the carry flag are subtracted from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.sbc_a(&components.registers.l, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "9D", "SBC A,L");
+}
+
+pub struct _0x9E {}
+impl Instruction for _0x9E {
+    // The value pointed to by HL and the carry flag are subtracted from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let addr = combine_to_double_byte(components.registers.h.get(), components.registers.l.get());
+        let value = components.mem.read_u8(addr);
+        components.registers.a.sub_value_and_carry(value, &mut components.registers.f);
+        7
+    }
+
+    inst_metadata!(0, "9E", "SBC A,(HL)");
+}
+
+pub struct _0x9F {}
+impl Instruction for _0x9F {
+    // A and the carry flag are subtracted from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let value = components.registers.a.get();
+        components.registers.a.sub_value_and_carry(value, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "9F", "SBC A,A");
+}
+
+
+// #A0 to AF
+
+pub struct _0xA0 {}
+impl Instruction for _0xA0 {
+    // Bitwise AND on A with B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let value = components.registers.b.get();
+        components.registers.a.and(value, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "A0", "AND B");
+}
+
+pub struct _0xA1 {}
+impl Instruction for _0xA1 {
+    // Bitwise AND on A with C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let value = components.registers.c.get();
+        components.registers.a.and(value, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "A1", "AND C");
+}
+
+pub struct _0xA2 {}
+impl Instruction for _0xA2 {
+    // Bitwise AND on A with D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let value = components.registers.d.get();
+        components.registers.a.and(value, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "A2", "AND D");
+}
+
+pub struct _0xA3 {}
+impl Instruction for _0xA3 {
+    // Bitwise AND on A with E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let value = components.registers.e.get();
+        components.registers.a.and(value, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "A3", "AND E");
+}
+
+pub struct _0xA4 {}
+impl Instruction for _0xA4 {
+    // Bitwise AND on A with H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let value = components.registers.h.get();
+        components.registers.a.and(value, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "A4", "AND H");
+}
+
+pub struct _0xA5 {}
+impl Instruction for _0xA5 {
+    // Bitwise AND on A with L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let value = components.registers.l.get();
+        components.registers.a.and(value, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "A5", "AND L");
+}
+
+pub struct _0xA6 {}
+impl Instruction for _0xA6 {
+    // Bitwise AND on A with the value pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let addr = combine_to_double_byte(components.registers.h.get(), components.registers.l.get());
+        let value = components.mem.read_u8(addr);
+        components.registers.a.and(value, &mut components.registers.f);
+        7
+    }
+
+    inst_metadata!(0, "A6", "AND (HL)");
+}
+
+pub struct _0xA7 {}
+impl Instruction for _0xA7 {
+    // Bitwise AND on A with A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let value = components.registers.a.get();
+        components.registers.a.and(value, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "A7", "AND A");
+}
+
+pub struct _0xA8 {}
+impl Instruction for _0xA8 {
+    // Bitwise XOR on A with B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.a.xor(&registers.b, &mut registers.f);
+        4
+    }
+
+    inst_metadata!(0, "A8", "XOR B");
+}
+
+pub struct _0xA9 {}
+impl Instruction for _0xA9 {
+    // Bitwise XOR on A with C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.a.xor(&registers.c, &mut registers.f);
+        4
+    }
+
+    inst_metadata!(0, "A9", "XOR C");
+}
+
+pub struct _0xAA {}
+impl Instruction for _0xAA {
+    // Bitwise XOR on A with D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.a.xor(&registers.d, &mut registers.f);
+        4
+    }
+
+    inst_metadata!(0, "AA", "XOR D");
+}
+
+pub struct _0xAB {}
+impl Instruction for _0xAB {
+    // Bitwise XOR on A with E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.a.xor(&registers.e, &mut registers.f);
+        4
+    }
+
+    inst_metadata!(0, "AB", "XOR E");
+}
+
+pub struct _0xAC {}
+impl Instruction for _0xAC {
+    // Bitwise XOR on A with H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.a.xor(&registers.h, &mut registers.f);
+        4
+    }
+
+    inst_metadata!(0, "AC", "XOR H");
+}
+
+pub struct _0xAD {}
+impl Instruction for _0xAD {
+    // Bitwise XOR on A with L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.a.xor(&registers.l, &mut registers.f);
+        4
+    }
+
+    inst_metadata!(0, "AD", "XOR L");
+}
+
+pub struct _0xAE {}
+impl Instruction for _0xAE {
+    // Bitwise XOR on A with the value pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.xor_address_from_reg_pair(&components.mem, (&components.registers.h, &components.registers.l), &mut components.registers.f);
+        7
+    }
+
+    inst_metadata!(0, "AE", "XOR (HL)");
+}
+
+
+pub struct _0xAF {}
+impl Instruction for _0xAF {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.xor_a(&mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "AF", "XOR A");
+}
+
+
+
+// #B0 to BF
+
+pub struct _0xB0 {}
+impl Instruction for _0xB0 {
+    // Bitwise OR on A with B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.a.or(&registers.b, &mut registers.f);
+        4
+    }
+
+    inst_metadata!(0, "B0", "OR B");
+}
+
+pub struct _0xB1 {}
+impl Instruction for _0xB1 {
+    // Bitwise OR on A with C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.a.or(&registers.c, &mut registers.f);
+        4
+    }
+
+    inst_metadata!(0, "B1", "OR C");
+}
+
+pub struct _0xB2 {}
+impl Instruction for _0xB2 {
+    // Bitwise OR on A with D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.a.or(&registers.d, &mut registers.f);
+        4
+    }
+
+    inst_metadata!(0, "B2", "OR D");
+}
+
+pub struct _0xB3 {}
+impl Instruction for _0xB3 {
+    // Bitwise OR on A with E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.a.or(&registers.e, &mut registers.f);
+        4
+    }
+
+    inst_metadata!(0, "B3", "OR E");
+}
+
+pub struct _0xB4 {}
+impl Instruction for _0xB4 {
+    // Bitwise OR on A with H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.a.or(&registers.h, &mut registers.f);
+        4
+    }
+
+    inst_metadata!(0, "B4", "OR H");
+}
+
+pub struct _0xB5 {}
+impl Instruction for _0xB5 {
+    // Bitwise OR on A with L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.a.or(&registers.l, &mut registers.f);
+        4
+    }
+
+    inst_metadata!(0, "B5", "OR L");
+}
+
+pub struct _0xB6 {}
+impl Instruction for _0xB6 {
+    // Bitwise OR on A with the value pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.or_address_from_reg_pair(&components.mem, (&components.registers.h, &components.registers.l), &mut components.registers.f);
+        7
+    }
+
+    inst_metadata!(0, "B6", "OR (HL)");
+}
+
+pub struct _0xB7 {}
+impl Instruction for _0xB7 {
+    // Bitwise OR on A with A. Leaves A unchanged but sets the flags from it.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.or_a(&mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "B7", "OR A");
+}
+
+pub struct _0xB8 {}
+impl Instruction for _0xB8 {
+    // Subtracts B from A and affects flags according to the result. A is not modified.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.compare_reg(&components.registers.b, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "B8", "CP B");
+}
+
+pub struct _0xB9 {}
+impl Instruction for _0xB9 {
+    // Subtracts C from A and affects flags according to the result. A is not modified.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.compare_reg(&components.registers.c, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "B9", "CP C");
+}
+
+pub struct _0xBA {}
+impl Instruction for _0xBA {
+    // Subtracts D from A and affects flags according to the result. A is not modified.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.compare_reg(&components.registers.d, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "BA", "CP D");
+}
+
+pub struct _0xBB {}
+impl Instruction for _0xBB {
+    // Subtracts E from A and affects flags according to the result. A is not modified.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.compare_reg(&components.registers.e, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "BB", "CP E");
+}
+
+pub struct _0xBC {}
+impl Instruction for _0xBC {
+    // Subtracts H from A and affects flags according to the result. A is not modified.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.compare_reg(&components.registers.h, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "BC", "CP H");
+}
+
+pub struct _0xBD {}
+impl Instruction for _0xBD {
+    // Subtracts L from A and affects flags according to the result. A is not modified.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.compare_reg(&components.registers.l, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "BD", "CP L");
+}
+
+pub struct _0xBE {}
+impl Instruction for _0xBE {
+    // Subtracts the value pointed to by HL from A and affects flags according to the result. A is not modified.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let addr = combine_to_double_byte(components.registers.h.get(), components.registers.l.get());
+        let value = components.mem.read_u8(addr);
+        components.registers.a.compare_val(value, &mut components.registers.f);
+        7
+    }
+
+    inst_metadata!(0, "BE", "CP (HL)");
+}
+
+pub struct _0xBF {}
+impl Instruction for _0xBF {
+    // Subtracts A from itself and affects flags according to the result. A is not modified.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let value = components.registers.a.get();
+        components.registers.a.compare_val(value, &mut components.registers.f);
+        4
+    }
+
+    inst_metadata!(0, "BF", "CP A");
+}
+
+
+// #C0 to CF
+
+pub struct _0xC0 {}
+impl Instruction for _0xC0 {
+    // If the zero flag is unset, the top stack entry is popped into PC.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::ret_if(|f| f.get_zero() == FlagValue::Unset, &mut registers.f, &mut registers.sp, &mut registers.pc, &components.mem)
+    }
+
+    inst_metadata!(0, "C0", "RET NZ");
+}
+
+pub struct _0xC2 {}
+impl Instruction for _0xC2 {
+    
+    // Jump to address provided in operands if zero flag is unset
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if components.registers.f.get_zero() ==  FlagValue::Unset {
+            if let Operands::Word(address) = operands {
+                components.registers.pc.set(address);
+            }
+        }
+        10
+    }
+
+    inst_metadata!(2, "C2 *1 *2", "JP NZ,*2*1");
+}
+
+pub struct _0xC3 {}
+impl Instruction for _0xC3 {
+    
+    // Jump to address provided in operands
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16{
+        if let Operands::Word(address) = operands {
+            components.registers.pc.set(address);
+        }
+        10
+    }
+
+    inst_metadata!(2, "C3 *1 *2", "JP *2*1");
+}
+
+pub struct _0xC4 {}
+impl Instruction for _0xC4 {
+    // If the zero flag is unset, the current PC value is pushed onto the stack, then is loaded with nn.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if components.registers.f.get_zero() == FlagValue::Unset {
+            if let Operands::Word(address) = operands {
+                RegisterOperations::call(address, &mut components.registers.sp, &mut components.registers.pc, &mut components.mem);
+                return 17;
+            }
+        }
+        10
+    }
+
+    inst_metadata!(2, "C4 *1 *2", "CALL NZ,*2*1");
+}
+
+pub struct _0xC5 {}
+impl Instruction for _0xC5 {
+
+    // Push contents of B and C onto stack.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::push_register_pair((&components.registers.b, &components.registers.c), &mut components.registers.sp, &mut components.mem);
+        11
+    }
+
+    inst_metadata!(0, "C5", "PUSH BC");
+}
+
+pub struct _0xC6 {}
+impl Instruction for _0xC6 {
+    // Adds n to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16{
+        if let Operands::Immediate(value) = operands {
+            components.registers.a.add_value(value, &mut components.registers.f);
+        } else {
+            panic!("Wrong operand for {}", self.assembly());
+        }
+        7
+    }
+
+    inst_metadata!(1, "C6 *1", "ADD A,*1");
+}
+
+pub struct _0xC7 {}
+impl Instruction for _0xC7 {
+    // The current PC value is pushed onto the stack, then PC is loaded with #00.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.sp.push(&mut components.mem, components.registers.pc.get());
+        components.registers.pc.set(0x0000);
+        11
+    }
+
+    inst_metadata!(0, "C7", "RST #00");
+}
+
+pub struct _0xC8 {}
+impl Instruction for _0xC8 {
+    // If the zero flag is set, the top stack entry is popped into PC.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::ret_if(|f| f.get_zero() == FlagValue::Set, &mut registers.f, &mut registers.sp, &mut registers.pc, &components.mem)
+    }
+
+    inst_metadata!(0, "C8", "RET Z");
+}
+
+pub struct _0xC9 {}
+impl Instruction for _0xC9 {
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        #[allow(clippy::needless_borrow)]
+        let addr = components.registers.sp.pop(&&components.mem);
+        components.registers.pc.set(addr);
+        10
+    }
+
+    inst_metadata!(0, "C9", "RET");
+}
+
+pub struct _0xCA {}
+impl Instruction for _0xCA {
+    
+    // Jump to address provided in operands if zero flag is set
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if components.registers.f.get_zero() ==  FlagValue::Set {
+            if let Operands::Word(address) = operands {
+                components.registers.pc.set(address);
+            }
+        }
+        10
+    }
+
+    inst_metadata!(2, "CA *1 *2", "JP Z,*2*1");
+}
+
+pub struct _0xCC {}
+impl Instruction for _0xCC {
+    // If the zero flag is set, the current PC value is pushed onto the stack, then is loaded with nn.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if components.registers.f.get_zero() == FlagValue::Set {
+            if let Operands::Word(address) = operands {
+                RegisterOperations::call(address, &mut components.registers.sp, &mut components.registers.pc, &mut components.mem);
+                return 17;
+            }
+        }
+        10
+    }
+
+    inst_metadata!(2, "CC *1 *2", "CALL Z,*2*1");
+}
+
+pub struct _0xCD {}
+impl Instruction for _0xCD {
+    
+    // The current PC value plus three is pushed onto the stack, then is loaded with nn.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16{
+        if let Operands::Word(address) = operands {
+            RegisterOperations::call(address, &mut components.registers.sp, &mut components.registers.pc, &mut components.mem);
+        }
+        17
+    }
+
+    inst_metadata!(2, "CD", "CALL *2*1");
+}
+
+pub struct _0xCE {}
+impl Instruction for _0xCE {
+    // Adds n and the carry flag to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16{
+        if let Operands::Immediate(value) = operands {
+            components.registers.a.adc_value(value, &mut components.registers.f);
+        } else {
+            panic!("Wrong operand for {}", self.assembly());
+        }
+        7
+    }
+
+    inst_metadata!(1, "CE *1", "ADC A,*1");
+}
+
+pub struct _0xCF {}
+impl Instruction for _0xCF {
+    // The current PC value is pushed onto the stack, then PC is loaded with #08.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.sp.push(&mut components.mem, components.registers.pc.get());
+        components.registers.pc.set(0x0008);
+        11
+    }
+
+    inst_metadata!(0, "CF", "RST #08");
+}
+
+
+// #D0 to DF
+
+pub struct _0xC1 {}
+impl Instruction for _0xC1 {
+    // The memory location pointed to by SP is stored into B and SP is incremented. 
+    // The memory location pointed to by SP is stored into C and SP is incremented again.   
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::pop_register_pair((&mut components.registers.b, &mut components.registers.c), &mut components.registers.sp, &mut components.mem);
+        10
+    }
+
+    inst_metadata!(0, "D1", "POP BC");
+}
+
+pub struct _0xD0 {}
+impl Instruction for _0xD0 {
+    // If the carry flag is unset, the top stack entry is popped into PC.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::ret_if(|f| f.get_carry() == FlagValue::Unset, &mut registers.f, &mut registers.sp, &mut registers.pc, &components.mem)
+    }
+
+    inst_metadata!(0, "D0", "RET NC");
+}
+
+pub struct _0xD1 {}
+impl Instruction for _0xD1 {
+    // The memory location pointed to by SP is stored into E and SP is incremented. 
+    // The memory location pointed to by SP is stored into D and SP is incremented again.   
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::pop_register_pair((&mut components.registers.d, &mut components.registers.e), &mut components.registers.sp, &mut components.mem);
+        10
+    }
+
+    inst_metadata!(0, "D1", "POP DE");
+}
+
+pub struct _0xD2 {}
+impl Instruction for _0xD2 {
+    
+    // Jump to address provided in operands if carry flag is unset
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if components.registers.f.get_carry() ==  FlagValue::Unset {
+            if let Operands::Word(address) = operands {
+                components.registers.pc.set(address);
+            }
+        }
+        10
+    }
+
+    inst_metadata!(2, "D2 *1 *2", "JP NC,*2*1");
+}
 
-    // //If the carry flag is set, the top stack entry is popped into PC.
+pub struct _0xD4 {}
+impl Instruction for _0xD4 {
+    // If the carry flag is unset, the current PC value is pushed onto the stack, then is loaded with nn.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if components.registers.f.get_carry() == FlagValue::Unset {
+            if let Operands::Word(address) = operands {
+                RegisterOperations::call(address, &mut components.registers.sp, &mut components.registers.pc, &mut components.mem);
+                return 17;
+            }
+        }
+        10
+    }
+
+    inst_metadata!(2, "D4 *1 *2", "CALL NC,*2*1");
+}
+
+pub struct _0xD5 {}
+impl Instruction for _0xD5 {
+    // Push contents of H and L onto stack.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::push_register_pair((&components.registers.d, &components.registers.e), &mut components.registers.sp, &mut components.mem);
+        11
+    }
+
+    inst_metadata!(0, "D5", "PUSH DE");
+}
+
+pub struct _0xD6 {}
+impl Instruction for _0xD6 {
+    // Subtract n from A
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16{
+        if let Operands::Immediate(value) = operands {
+            components.registers.a.sub_value(value, &mut components.registers.f);
+        } else {
+            panic!("Wrong operand for {}", self.assembly());
+        }
+        7
+    }
+
+    inst_metadata!(1, "D6 *1", "SUB *1");
+}
+
+
+pub struct _0xD7 {}
+impl Instruction for _0xD7 {
+    // The current PC value is pushed onto the stack, then PC is loaded with #10.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.sp.push(&mut components.mem, components.registers.pc.get());
+        components.registers.pc.set(0x0010);
+        11
+    }
+
+    inst_metadata!(0, "D7", "RST #10");
+}
+
+pub struct _0xD8 {}
+impl Instruction for _0xD8 {
+    // If the carry flag is set, the top stack entry is popped into PC.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::ret_if(|f| f.get_carry() == FlagValue::Set, &mut registers.f, &mut registers.sp, &mut registers.pc, &components.mem)
+    }
+
+    inst_metadata!(0, "D8", "RET C");
+}
+pub struct _0xD9 {}
+impl Instruction for _0xD9 {
+    // Bitwise AND a with operand. Set flags accordingly.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let b = components.registers.b.get();
+        let c = components.registers.c.get();
+        let d = components.registers.d.get();
+        let e = components.registers.e.get();
+        let h = components.registers.h.get();
+        let l = components.registers.l.get();
+        components.registers.b.set(components.registers.b_.get());
+        components.registers.c.set(components.registers.c_.get());
+        components.registers.d.set(components.registers.d_.get());
+        components.registers.e.set(components.registers.e_.get());
+        components.registers.h.set(components.registers.h_.get());
+        components.registers.l.set(components.registers.l_.get());
+        components.registers.b_.set(b);
+        components.registers.c_.set(c);
+        components.registers.d_.set(d);
+        components.registers.e_.set(e);
+        components.registers.h_.set(h);
+        components.registers.l_.set(l);
+        4
+    }
+
+    inst_metadata!(0, "D9", "EXX");
+}
+
+
+pub struct _0xDA {}
+impl Instruction for _0xDA {
+    
+    // Jump to address provided in operands if carry flag is set
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if components.registers.f.get_carry() ==  FlagValue::Set {
+            if let Operands::Word(address) = operands {
+                components.registers.pc.set(address);
+            }
+        }
+        10
+    }
+
+    inst_metadata!(2, "DA *1 *2", "JP C,*2*1");
+}
+
+pub struct _0xDC {}
+impl Instruction for _0xDC {
+    // If the carry flag is set, the current PC value is pushed onto the stack, then is loaded with nn.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         if components.registers.f.get_carry() == FlagValue::Set {
-            components.registers.pc.set(components.registers.sp.pop(&components.mem));
-            return 11;
+            if let Operands::Word(address) = operands {
+                RegisterOperations::call(address, &mut components.registers.sp, &mut components.registers.pc, &mut components.mem);
+                return 17;
+            }
+        }
+        10
+    }
+
+    inst_metadata!(2, "DC *1 *2", "CALL C,*2*1");
+}
+
+pub struct _0xDE {}
+impl Instruction for _0xDE {
+    //Subtracts n and the carry flag from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16{
+        if let Operands::Immediate(value) = operands {
+            components.registers.a.sub_value_and_carry(value, &mut components.registers.f);
+        } else {
+            panic!("Wrong operand for {}", self.assembly());
+        }
+        7
+    }
+
+    inst_metadata!(1, "DE *1", "SBC A,*1");
+}
+
+pub struct _0xDF {}
+impl Instruction for _0xDF {
+    // The current PC value is pushed onto the stack, then PC is loaded with #18.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.sp.push(&mut components.mem, components.registers.pc.get());
+        components.registers.pc.set(0x0018);
+        11
+    }
+
+    inst_metadata!(0, "DF", "RST #18");
+}
+
+// #E0 to EF
+
+pub struct _0xE0 {}
+impl Instruction for _0xE0 {
+    // If the parity/overflow flag is unset, the top stack entry is popped into PC.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::ret_if(|f| f.get_parity_overflow() == FlagValue::Unset, &mut registers.f, &mut registers.sp, &mut registers.pc, &components.mem)
+    }
+
+    inst_metadata!(0, "E0", "RET PO");
+}
+
+pub struct _0xE1 {}
+impl Instruction for _0xE1 {
+    // The memory location pointed to by SP is stored into L and SP is incremented. 
+    // The memory location pointed to by SP is stored into H and SP is incremented again.   
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::pop_register_pair((&mut components.registers.h, &mut components.registers.l), &mut components.registers.sp, &mut components.mem);
+        10
+    }
+
+    inst_metadata!(0, "E1", "POP HL");
+}
+
+pub struct _0xE2 {}
+impl Instruction for _0xE2 {
+    
+    // Jump to address provided in operands if parity/overflow flag is unset
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if components.registers.f.get_parity_overflow() ==  FlagValue::Unset {
+            if let Operands::Word(address) = operands {
+                components.registers.pc.set(address);
+            }
+        }
+        10
+    }
+
+    inst_metadata!(2, "E2 *1 *2", "JP PO,*2*1");
+}
+
+pub struct _0xE3 {}
+impl Instruction for _0xE3 {
+    // Exchanges the word on top of the stack with HL. SP itself is unchanged.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let top = registers.sp.pop(&components.mem);
+        registers.sp.push(&mut components.mem, combine_to_double_byte(registers.h.get(), registers.l.get()));
+        let (high, low) = split_double_byte(top);
+        registers.h.set(high);
+        registers.l.set(low);
+        19
+    }
+
+    inst_metadata!(0, "E3", "EX (SP),HL");
+}
+
+pub struct _0xE4 {}
+impl Instruction for _0xE4 {
+    // If the parity/overflow flag is unset, the current PC value is pushed onto the stack, then is loaded with nn.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if components.registers.f.get_parity_overflow() == FlagValue::Unset {
+            if let Operands::Word(address) = operands {
+                RegisterOperations::call(address, &mut components.registers.sp, &mut components.registers.pc, &mut components.mem);
+                return 17;
+            }
+        }
+        10
+    }
+
+    inst_metadata!(2, "E4 *1 *2", "CALL PO,*2*1");
+}
+
+pub struct _0xE5 {}
+impl Instruction for _0xE5 {
+
+    // Push contents of H and L onto stack.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::push_register_pair((&components.registers.h, &components.registers.l), &mut components.registers.sp, &mut components.mem);
+        11
+    }
+
+    inst_metadata!(0, "E5", "PUSH HL");
+}
+
+pub struct _0xE6 {}
+impl Instruction for _0xE6 {
+    
+    // Bitwise AND a with operand. Set flags accordingly.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if let Operands::Immediate(val) = operands {
+            components.registers.a.and(val, &mut components.registers.f)
+        }
+        7
+    }
+
+    inst_metadata!(1, "E6 *1", "AND *1");
+}
+
+pub struct _0xE7 {}
+impl Instruction for _0xE7 {
+    // The current PC value is pushed onto the stack, then PC is loaded with #20.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.sp.push(&mut components.mem, components.registers.pc.get());
+        components.registers.pc.set(0x0020);
+        11
+    }
+
+    inst_metadata!(0, "E7", "RST #20");
+}
+
+pub struct _0xE8 {}
+impl Instruction for _0xE8 {
+    // If the parity/overflow flag is set, the top stack entry is popped into PC.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::ret_if(|f| f.get_parity_overflow() == FlagValue::Set, &mut registers.f, &mut registers.sp, &mut registers.pc, &components.mem)
+    }
+
+    inst_metadata!(0, "E8", "RET PE");
+}
+
+pub struct _0xE9 {}
+impl Instruction for _0xE9 {
+    // Loads the value of HL into PC. Despite the mnemonic, memory at (HL) is not read.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.pc.set(combine_to_double_byte(registers.h.get(), registers.l.get()));
+        4
+    }
+
+    inst_metadata!(0, "E9", "JP (HL)");
+}
+
+pub struct _0xEA {}
+impl Instruction for _0xEA {
+    
+    // Jump to address provided in operands if parity/overflow flag is set
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if components.registers.f.get_parity_overflow() ==  FlagValue::Set {
+            if let Operands::Word(address) = operands {
+                components.registers.pc.set(address);
+            }
         }
-        5
+        10
     }
 
-    inst_metadata!(0, "D8", "RET C");
+    inst_metadata!(2, "EA *1 *2", "JP PE,*2*1");
 }
-pub struct _0xD9 {}
-impl Instruction for _0xD9 {
-    // Bitwise AND a with operand. Set flags accordingly.
+
+pub struct _0xEB {}
+impl Instruction for _0xEB {
+    // Exchanges the 16-bit contents of AF and AF'.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        let b = components.registers.b.get();
-        let c = components.registers.c.get();
-        let d = components.registers.d.get();
-        let e = components.registers.e.get();
-        let h = components.registers.h.get();
-        let l = components.registers.l.get();
-        components.registers.b.set(components.registers.b_.get());
-        components.registers.c.set(components.registers.c_.get());
-        components.registers.d.set(components.registers.d_.get());
-        components.registers.e.set(components.registers.e_.get());
-        components.registers.h.set(components.registers.h_.get());
-        components.registers.l.set(components.registers.l_.get());
-        components.registers.b_.set(b);
-        components.registers.c_.set(c);
-        components.registers.d_.set(d);
-        components.registers.e_.set(e);
-        components.registers.h_.set(h);
-        components.registers.l_.set(l);
+        let mut registers = &mut components.registers;
+        let d_val = registers.d.get();
+        let e_val = registers.e.get();
+        registers.d.set(registers.h.get());
+        registers.e.set(registers.l.get());
+        registers.h.set(d_val);
+        registers.l.set(e_val);
         4
     }
 
-    inst_metadata!(0, "D9", "EXX");
+    inst_metadata!(0, "EB", "EX DE,HL");
 }
 
+pub struct _0xEC {}
+impl Instruction for _0xEC {
+    // If the parity/overflow flag is set, the current PC value is pushed onto the stack, then is loaded with nn.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if components.registers.f.get_parity_overflow() == FlagValue::Set {
+            if let Operands::Word(address) = operands {
+                RegisterOperations::call(address, &mut components.registers.sp, &mut components.registers.pc, &mut components.mem);
+                return 17;
+            }
+        }
+        10
+    }
 
-pub struct _0xDE {}
-impl Instruction for _0xDE {
-    //Subtracts n and the carry flag from A.
-    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16{
-        if let Operands::One(value) = operands {
-            components.registers.a.sub_value_and_carry(value, &mut components.registers.f);
-        } else {
-            panic!("Wrong operand for {}", self.assembly());
+    inst_metadata!(2, "EC *1 *2", "CALL PE,*2*1");
+}
+
+pub struct _0xEE {}
+impl Instruction for _0xEE {
+    
+    // Bitwise XOR a with operand. Set flags accordingly.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if let Operands::Immediate(val) = operands {
+            components.registers.a.xor_value(val, &mut components.registers.f)
         }
         7
     }
 
-    inst_metadata!(1, "DE *1", "SBC A,*1");
+    inst_metadata!(1, "EE *1", "XOR *1");
 }
 
-// #E0 to EF
+pub struct _0xEF {}
+impl Instruction for _0xEF {
+    // The current PC value is pushed onto the stack, then PC is loaded with #28.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.sp.push(&mut components.mem, components.registers.pc.get());
+        components.registers.pc.set(0x0028);
+        11
+    }
 
-pub struct _0xE5 {}
-impl Instruction for _0xE5 {
+    inst_metadata!(0, "EF", "RST #28");
+}
 
-    // Push contents of H and L onto stack.
+
+// #F0 to FF
+
+pub struct _0xF0 {}
+impl Instruction for _0xF0 {
+    // If the sign flag is unset, the top stack entry is popped into PC.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::ret_if(|f| f.get_sign() == FlagValue::Unset, &mut registers.f, &mut registers.sp, &mut registers.pc, &components.mem)
+    }
+
+    inst_metadata!(0, "F0", "RET P");
+}
+
+pub struct _0xF1 {}
+impl Instruction for _0xF1 {
+    // The memory location pointed to by SP is stored into F and SP is incremented. 
+    // The memory location pointed to by SP is stored into A and SP is incremented again.   
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::pop_register_pair((&mut components.registers.a, &mut components.registers.f), &mut components.registers.sp, &mut components.mem);
+        10
+    }
+
+    inst_metadata!(0, "F1", "POP AF");
+}
+
+pub struct _0xF2 {}
+impl Instruction for _0xF2 {
+    
+    // Jump to address provided in operands if sign flag is unset
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if components.registers.f.get_sign() ==  FlagValue::Unset {
+            if let Operands::Word(address) = operands {
+                components.registers.pc.set(address);
+            }
+        }
+        10
+    }
+
+    inst_metadata!(2, "F2 *1 *2", "JP P,*2*1");
+}
+
+pub struct _0xF3 {}
+impl Instruction for _0xF3 {
+    
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.iff1 = false;
+        components.registers.iff2 = false;
+        4
+    }
+
+    inst_metadata!(0, "F3", "DI");
+}
+
+pub struct _0xF4 {}
+impl Instruction for _0xF4 {
+    // If the sign flag is unset, the current PC value is pushed onto the stack, then is loaded with nn.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if components.registers.f.get_sign() == FlagValue::Unset {
+            if let Operands::Word(address) = operands {
+                RegisterOperations::call(address, &mut components.registers.sp, &mut components.registers.pc, &mut components.mem);
+                return 17;
+            }
+        }
+        10
+    }
+
+    inst_metadata!(2, "F4 *1 *2", "CALL P,*2*1");
+}
+
+pub struct _0xF5 {}
+impl Instruction for _0xF5 {
+    
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::push_register_pair((&components.registers.h, &components.registers.h), &mut components.registers.sp, &mut components.mem);
+        RegisterOperations::push_register_pair((&components.registers.a, &components.registers.f), &mut components.registers.sp, &mut components.mem);
         11
     }
 
-    inst_metadata!(0, "E5", "PUSH HL");
+    inst_metadata!(0, "F5", "PUSH AF");
 }
 
-pub struct _0xE6 {}
-impl Instruction for _0xE6 {
+
+pub struct _0xF6 {}
+impl Instruction for _0xF6 {
     
-    // Bitwise AND a with operand. Set flags accordingly.
+    // Bitwise OR a with operand. Set flags accordingly.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        if let Operands::One(val) = operands {
-            components.registers.a.and(val, &mut components.registers.f)
+        if let Operands::Immediate(val) = operands {
+            components.registers.a.or_value(val, &mut components.registers.f)
+        }
+        7
+    }
+
+    inst_metadata!(1, "F6 *1", "OR *1");
+}
+
+pub struct _0xF7 {}
+impl Instruction for _0xF7 {
+    // The current PC value is pushed onto the stack, then PC is loaded with #30.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.sp.push(&mut components.mem, components.registers.pc.get());
+        components.registers.pc.set(0x0030);
+        11
+    }
+
+    inst_metadata!(0, "F7", "RST #30");
+}
+
+pub struct _0xF8 {}
+impl Instruction for _0xF8 {
+    // If the sign flag is set, the top stack entry is popped into PC.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::ret_if(|f| f.get_sign() == FlagValue::Set, &mut registers.f, &mut registers.sp, &mut registers.pc, &components.mem)
+    }
+
+    inst_metadata!(0, "F8", "RET M");
+}
+
+
+pub struct _0xF9 {}
+impl Instruction for _0xF9 {
+    // Loads the value of HL into SP.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let hl = combine_to_double_byte(components.registers.h.get(), components.registers.l.get());
+        components.registers.sp.set(hl as usize);
+        6
+    }
+
+    inst_metadata!(0, "F9", "LD SP,HL");
+}
+
+pub struct _0xFA {}
+impl Instruction for _0xFA {
+    
+    // Jump to address provided in operands if sign flag is set
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if components.registers.f.get_sign() ==  FlagValue::Set {
+            if let Operands::Word(address) = operands {
+                components.registers.pc.set(address);
+            }
+        }
+        10
+    }
+
+    inst_metadata!(2, "FA *1 *2", "JP M,*2*1");
+}
+
+pub struct _0xFB {}
+impl Instruction for _0xFB {
+    // Sets both interrupt flip-flops, thus allowing maskable interrupts to occur. 
+    // An interrupt will not occur until after the immediately following instruction.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.iff1 = true;
+        components.registers.iff2 = true;
+        components.registers.ei_pending = true;
+        4
+    }
+
+    inst_metadata!(0, "FB", "EI");
+}
+
+
+pub struct _0xFC {}
+impl Instruction for _0xFC {
+    // If the sign flag is set, the current PC value is pushed onto the stack, then is loaded with nn.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if components.registers.f.get_sign() == FlagValue::Set {
+            if let Operands::Word(address) = operands {
+                RegisterOperations::call(address, &mut components.registers.sp, &mut components.registers.pc, &mut components.mem);
+                return 17;
+            }
+        }
+        10
+    }
+
+    inst_metadata!(2, "FC *1 *2", "CALL M,*2*1");
+}
+
+pub struct _0xFE {}
+impl Instruction for _0xFE {
+    // Subtracts n from A and affects flags according to the result. 
+    // A is not modified.
+    #[allow(clippy::unnecessary_operation)]
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        if let Operands::Immediate(val) = operands {
+            &components.registers.a.compare_val(val, &mut components.registers.f);
         }
         7
     }
 
-    inst_metadata!(1, "E6 *1", "AND *1");
-}
+    inst_metadata!(1, "FE", "CP *1");
+}
+
+
+// Tests
+
+pub struct _0xFF {}
+impl Instruction for _0xFF {
+    // The current PC value is pushed onto the stack, then PC is loaded with #38.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.sp.push(&mut components.mem, components.registers.pc.get());
+        components.registers.pc.set(0x0038);
+        11
+    }
+
+    inst_metadata!(0, "FF", "RST #38");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{instruction_set::{Instruction, Operands, OperandKind, InstructionSet, self, basic::{_0xC9, _0xC5, _0xC2, _0xF5}}, memory::{Memory, Registers, AddressBus, DataBus, FlagValue, Register}, runtime::{Runtime, RuntimeComponents}, utils::{split_double_byte, signed}};
+
+    use super::{_0x00, _0x19, _0x29, _0x22, _0x2A, _0xD6, _0xC6, _0xCE, _0xEE, _0xF6, _0xF1, _0xE1, _0xE5, _0xE9, _0xE3, _0x0F, _0x17, _0x1F, _0x02, _0x0A, _0x12, _0x1A, _0xF9, _0x28, _0x38, _0xD0, _0xCA, _0xDA, _0xDF, _0xCC, _0xD4, _0x04, _0x16, _0x1E, _0x26, _0x2E, _0x05, _0x06, _0x07, _0x10, _0x18, _0x20, _0x21, _0x30, _0xE6, _0x0B, _0xDE};
+
+    fn runtime_components() -> RuntimeComponents {
+        RuntimeComponents { mem: Memory::default(), registers: Registers::default(), address_bus: AddressBus { value: 0 }, data_bus: DataBus::default() }
+    }
+
+    #[test]
+    fn operand_kinds() {
+        assert_eq!(_0x18 {}.operand_kind(), OperandKind::Relative8);
+        assert_eq!(_0x10 {}.operand_kind(), OperandKind::Relative8);
+        assert_eq!(_0x20 {}.operand_kind(), OperandKind::Relative8);
+        assert_eq!(_0x30 {}.operand_kind(), OperandKind::Relative8);
+        assert_eq!(_0x28 {}.operand_kind(), OperandKind::Relative8);
+        assert_eq!(_0x38 {}.operand_kind(), OperandKind::Relative8);
+        assert_eq!(_0x21 {}.operand_kind(), OperandKind::Word16);
+        assert_eq!(_0x06 {}.operand_kind(), OperandKind::Immediate8);
+        assert_eq!(_0x00 {}.operand_kind(), OperandKind::None);
+    }
+
+    // Register order used by the opcode encoding: B, C, D, E, H, L, (HL), A.
+    fn ld_operand(components: &mut RuntimeComponents, index: u8) -> u8 {
+        let registers = &components.registers;
+        match index {
+            0 => registers.b.get(),
+            1 => registers.c.get(),
+            2 => registers.d.get(),
+            3 => registers.e.get(),
+            4 => registers.h.get(),
+            5 => registers.l.get(),
+            6 => components.mem.locations[0x4060],
+            _ => registers.a.get()
+        }
+    }
+
+    // Runs every LD r,r' with the given destination and checks it ends up holding the source.
+    fn assert_ld_group(destination: u8) {
+        let instruction_set = InstructionSet::default();
+        for source in 0..8 {
+            if destination == 6 && source == 6 {
+                continue; // #76 is HALT
+            }
+            let mut components = runtime_components();
+            components.registers.b.set(0x0B);
+            components.registers.c.set(0x0C);
+            components.registers.d.set(0x0D);
+            components.registers.e.set(0x0E);
+            components.registers.h.set(0x40);
+            components.registers.l.set(0x60);
+            components.registers.a.set(0x0A);
+            components.mem.locations[0x4060] = 0x99;
+
+            let expected = ld_operand(&mut components, source);
+            let opcode = 0x40 + destination * 8 + source;
+            let cycles = instruction_set.instruction_for(opcode).unwrap().execute(&mut components, Operands::None);
+            assert_eq!(ld_operand(&mut components, destination), expected, "opcode {:02X}", opcode);
+            let expected_cycles = if destination == 6 || source == 6 { 7 } else { 4 };
+            assert_eq!(cycles, expected_cycles, "opcode {:02X}", opcode);
+        }
+    }
+
+    #[test]
+    fn ld_b_r() {
+        assert_ld_group(0);
+    }
+
+    #[test]
+    fn ld_c_r() {
+        assert_ld_group(1);
+    }
+
+    #[test]
+    fn ld_d_r() {
+        assert_ld_group(2);
+    }
+
+    #[test]
+    fn ld_e_r() {
+        assert_ld_group(3);
+    }
+
+    #[test]
+    fn ld_h_r() {
+        assert_ld_group(4);
+    }
+
+    #[test]
+    fn ld_l_r() {
+        assert_ld_group(5);
+    }
+
+    #[test]
+    fn ld_hl_r() {
+        assert_ld_group(6);
+    }
+
+    #[test]
+    fn ld_a_r() {
+        assert_ld_group(7);
+    }
+
+    #[test]
+    fn ld_d_n() {
+        let mut components = runtime_components();
+        let cycles = _0x16 {}.execute(&mut components, Operands::Immediate(0xA5));
+        assert_eq!(cycles, 7);
+        assert_eq!(components.registers.d.get(), 0xA5);
+    }
+
+    #[test]
+    fn ld_e_n() {
+        let mut components = runtime_components();
+        _0x1E {}.execute(&mut components, Operands::Immediate(0x5A));
+        assert_eq!(components.registers.e.get(), 0x5A);
+    }
+
+    #[test]
+    fn ld_h_n() {
+        let mut components = runtime_components();
+        _0x26 {}.execute(&mut components, Operands::Immediate(0xC3));
+        assert_eq!(components.registers.h.get(), 0xC3);
+    }
 
-pub struct _0xEB {}
-impl Instruction for _0xEB {
-    // Exchanges the 16-bit contents of AF and AF'.
-    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        let mut registers = &mut components.registers;
-        let d_val = registers.d.get();
-        let e_val = registers.e.get();
-        registers.d.set(registers.h.get());
-        registers.e.set(registers.l.get());
-        registers.h.set(d_val);
-        registers.l.set(e_val);
-        4
+    #[test]
+    fn ld_l_n() {
+        let mut components = runtime_components();
+        _0x2E {}.execute(&mut components, Operands::Immediate(0x3C));
+        assert_eq!(components.registers.l.get(), 0x3C);
     }
 
-    inst_metadata!(0, "EB", "EX DE,HL");
-}
+    #[test]
+    fn rst_18() {
+        let mut components = runtime_components();
+        components.registers.sp.set(0x8000);
+        components.registers.pc.set(0x1235);
+        let cycles = _0xDF {}.execute(&mut components, Operands::None);
+        assert_eq!(cycles, 11);
+        assert_eq!(components.registers.pc.get(), 0x0018);
+        assert_eq!(components.registers.sp.get(), 0x7FFE);
+        assert_eq!(components.registers.sp.pop(&components.mem), 0x1235);
+    }
 
+    #[test]
+    fn call_z() {
+        let mut components = runtime_components();
+        components.registers.sp.set(0x8000);
+        components.registers.pc.set(0x1003);
 
-// #F0 to FF
+        components.registers.f.set_zero(FlagValue::Unset);
+        let cycles = _0xCC {}.execute(&mut components, Operands::Word(0x4000));
+        assert_eq!(cycles, 10);
+        assert_eq!(components.registers.pc.get(), 0x1003);
+        assert_eq!(components.registers.sp.get(), 0x8000);
 
-pub struct _0xF0 {}
-impl Instruction for _0xF0 {
-    
-    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        if components.registers.f.get_sign() == FlagValue::Unset {
-            components.registers.pc.set(components.registers.sp.pop(&components.mem));
-            return 11;
-        }
-        5
+        components.registers.f.set_zero(FlagValue::Set);
+        let cycles = _0xCC {}.execute(&mut components, Operands::Word(0x4000));
+        assert_eq!(cycles, 17);
+        assert_eq!(components.registers.pc.get(), 0x4000);
+        assert_eq!(components.registers.sp.pop(&components.mem), 0x1003);
     }
 
-    inst_metadata!(0, "F0", "RET P");
-}
+    #[test]
+    fn call_nc() {
+        let mut components = runtime_components();
+        components.registers.sp.set(0x8000);
+        components.registers.pc.set(0x2003);
 
-pub struct _0xF2 {}
-impl Instruction for _0xF2 {
-    
-    // Jump to address provided in operands if sign flag is set
-    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        if components.registers.f.get_sign() ==  FlagValue::Set {
-            if let Operands::Two(low, high) = operands {
-                components.registers.pc.set(utils::combine_to_double_byte(high, low));
-            }
-        }
-        10
+        components.registers.f.set_carry(FlagValue::Set);
+        let cycles = _0xD4 {}.execute(&mut components, Operands::Word(0x5000));
+        assert_eq!(cycles, 10);
+        assert_eq!(components.registers.pc.get(), 0x2003);
+        assert_eq!(components.registers.sp.get(), 0x8000);
+
+        components.registers.f.set_carry(FlagValue::Unset);
+        let cycles = _0xD4 {}.execute(&mut components, Operands::Word(0x5000));
+        assert_eq!(cycles, 17);
+        assert_eq!(components.registers.pc.get(), 0x5000);
+        assert_eq!(components.registers.sp.pop(&components.mem), 0x2003);
     }
 
-    inst_metadata!(2, "F2 *1 *2", "JP P,*2*1");
-}
+    #[test]
+    fn jp_z_taken() {
+        let mut components = runtime_components();
+        components.registers.pc.set(0x0103);
+        components.registers.f.set_zero(FlagValue::Set);
+        let cycles = _0xCA {}.execute(&mut components, Operands::Word(0x3456));
+        assert_eq!(cycles, 10);
+        assert_eq!(components.registers.pc.get(), 0x3456);
+    }
 
-pub struct _0xF3 {}
-impl Instruction for _0xF3 {
-    
-    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        components.registers.iff1 = false;
-        components.registers.iff2 = false;
-        4
+    #[test]
+    fn jp_c_not_taken() {
+        let mut components = runtime_components();
+        components.registers.pc.set(0x0103);
+        components.registers.f.set_carry(FlagValue::Unset);
+        let cycles = _0xDA {}.execute(&mut components, Operands::Word(0x3456));
+        assert_eq!(cycles, 10);
+        assert_eq!(components.registers.pc.get(), 0x0103);
     }
 
-    inst_metadata!(0, "F3", "DI");
-}
+    #[test]
+    fn ret_nc() {
+        let mut components = runtime_components();
+        components.registers.sp.set(0x8000);
+        components.registers.sp.push(&mut components.mem, 0x1234);
+        components.registers.pc.set(0x0200);
 
-pub struct _0xF5 {}
-impl Instruction for _0xF5 {
-    
-    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        RegisterOperations::push_register_pair((&components.registers.a, &components.registers.f), &mut components.registers.sp, &mut components.mem);
-        11
-    }
+        components.registers.f.set_carry(FlagValue::Set);
+        let cycles = _0xD0 {}.execute(&mut components, Operands::None);
+        assert_eq!(cycles, 5);
+        assert_eq!(components.registers.pc.get(), 0x0200);
+        assert_eq!(components.registers.sp.get(), 0x7FFE);
 
-    inst_metadata!(0, "F5", "PUSH AF");
-}
+        components.registers.f.set_carry(FlagValue::Unset);
+        let cycles = _0xD0 {}.execute(&mut components, Operands::None);
+        assert_eq!(cycles, 11);
+        assert_eq!(components.registers.pc.get(), 0x1234);
+        assert_eq!(components.registers.sp.get(), 0x8000);
+    }
 
+    #[test]
+    fn jr_backwards() {
+        let mut components = runtime_components();
+        // JR #FE at #1000: PC has moved past the operand to #1002, so the jump lands back on the JR.
+        components.registers.pc.set(0x1002);
+        let cycles = _0x18 {}.execute(&mut components, Operands::Relative(signed(0xFE)));
+        assert_eq!(cycles, 12);
+        assert_eq!(components.registers.pc.get(), 0x1000);
 
-pub struct _0xF8 {}
-impl Instruction for _0xF8 {
-    // If the sign flag is set, the top stack entry is popped into PC.
-    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        if components.registers.f.get_sign() == FlagValue::Set {
-            components.registers.pc.set(components.registers.sp.pop(&components.mem));
-            return 11;
-        }
-        5
+        components.registers.pc.set(0x0001);
+        _0x18 {}.execute(&mut components, Operands::Relative(-0x80));
+        assert_eq!(components.registers.pc.get(), 0xFF81);
     }
 
-    inst_metadata!(0, "F8", "RET M");
-}
+    #[test]
+    fn djnz() {
+        let mut components = runtime_components();
+        components.registers.pc.set(0x0102);
 
+        components.registers.b.set(2);
+        assert_eq!(_0x10 {}.execute(&mut components, Operands::Relative(-2)), 13);
+        assert_eq!(components.registers.b.get(), 1);
+        assert_eq!(components.registers.pc.get(), 0x0100);
 
-pub struct _0xFB {}
-impl Instruction for _0xFB {
-    // Sets both interrupt flip-flops, thus allowing maskable interrupts to occur. 
-    // An interrupt will not occur until after the immediately following instruction.
-    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        components.registers.iff1 = true;
-        components.registers.iff2 = true;
-        4
+        components.registers.pc.set(0x0102);
+        assert_eq!(_0x10 {}.execute(&mut components, Operands::Relative(-2)), 8);
+        assert_eq!(components.registers.b.get(), 0);
+        assert_eq!(components.registers.pc.get(), 0x0102);
     }
 
-    inst_metadata!(0, "FB", "EI");
-}
+    #[test]
+    fn djnz_from_zero_loops_256_times() {
+        let mut components = runtime_components();
+        components.registers.b.set(0);
+
+        let mut jumps = 0;
+        loop {
+            components.registers.pc.set(0x0102);
+            let cycles = _0x10 {}.execute(&mut components, Operands::Relative(-2));
+            if cycles == 8 {
+                break;
+            }
+            jumps += 1;
+        }
+        assert_eq!(jumps, 255);
+        assert_eq!(components.registers.b.get(), 0);
+    }
 
+    #[test]
+    fn jr_z() {
+        let mut components = runtime_components();
+        components.registers.pc.set(0x0102);
 
-pub struct _0xFE {}
-impl Instruction for _0xFE {
-    // Subtracts n from A and affects flags according to the result. 
-    // A is not modified.
-    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        if let Operands::One(val) = operands {
-            &components.registers.a.compare_val(val, &mut components.registers.f);
-        }
-        7
+        components.registers.f.set_zero(FlagValue::Unset);
+        let cycles = _0x28 {}.execute(&mut components, Operands::Relative(-4));
+        assert_eq!(cycles, 7);
+        assert_eq!(components.registers.pc.get(), 0x0102);
+
+        components.registers.f.set_zero(FlagValue::Set);
+        let cycles = _0x28 {}.execute(&mut components, Operands::Relative(-4));
+        assert_eq!(cycles, 12);
+        assert_eq!(components.registers.pc.get(), 0x00FE);
     }
 
-    inst_metadata!(1, "FE", "CP *1");
-}
+    #[test]
+    fn jr_c() {
+        let mut components = runtime_components();
+        components.registers.pc.set(0x0102);
 
+        components.registers.f.set_carry(FlagValue::Unset);
+        let cycles = _0x38 {}.execute(&mut components, Operands::Relative(0x10));
+        assert_eq!(cycles, 7);
+        assert_eq!(components.registers.pc.get(), 0x0102);
 
-// Tests
+        components.registers.f.set_carry(FlagValue::Set);
+        let cycles = _0x38 {}.execute(&mut components, Operands::Relative(0x10));
+        assert_eq!(cycles, 12);
+        assert_eq!(components.registers.pc.get(), 0x0112);
+    }
 
-#[cfg(test)]
-mod tests {
-    use std::collections::HashMap;
+    #[test]
+    fn ld_sp_hl() {
+        let mut components = runtime_components();
+        components.registers.h.set(0xC0);
+        components.registers.l.set(0x00);
+        let cycles = _0xF9 {}.execute(&mut components, Operands::None);
+        assert_eq!(cycles, 6);
+        assert_eq!(components.registers.sp.get(), 0xC000);
+    }
 
-    use crate::{instruction_set::{Instruction, Operands, InstructionSet, self, basic::{_0xC9, _0xC5, _0xC2, _0xF5}}, memory::{Memory, Registers, AddressBus, DataBus, FlagValue, Register}, runtime::{Runtime, RuntimeComponents}, utils::split_double_byte};
+    #[test]
+    fn ld_a_via_bc_round_trip() {
+        let mut components = runtime_components();
+        components.registers.b.set(0x40);
+        components.registers.c.set(0x10);
+        components.registers.a.set(0x5C);
+        assert_eq!(_0x02 {}.execute(&mut components, Operands::None), 7);
+        assert_eq!(components.mem.read_u8(0x4010), 0x5C);
 
-    use super::{_0x04, _0x05, _0x07, _0xE6, _0x0B, _0xDE};
+        components.registers.a.set(0x00);
+        assert_eq!(_0x0A {}.execute(&mut components, Operands::None), 7);
+        assert_eq!(components.registers.a.get(), 0x5C);
+    }
 
-    fn runtime_components() -> RuntimeComponents {
-        RuntimeComponents { mem: Memory::default(), registers: Registers::default(), address_bus: AddressBus { value: 0 }, data_bus: DataBus { } }
+    #[test]
+    fn ld_a_via_de_round_trip() {
+        let mut components = runtime_components();
+        components.registers.d.set(0x80);
+        components.registers.e.set(0x22);
+        components.registers.b.set(0x40);
+        components.registers.c.set(0x10);
+        components.registers.a.set(0xA7);
+        assert_eq!(_0x12 {}.execute(&mut components, Operands::None), 7);
+        assert_eq!(components.mem.read_u8(0x8022), 0xA7);
+        assert_eq!(components.mem.read_u8(0x4010), 0x01);
+
+        components.registers.a.set(0x00);
+        assert_eq!(_0x1A {}.execute(&mut components, Operands::None), 7);
+        assert_eq!(components.registers.a.get(), 0xA7);
     }
 
     #[test]
@@ -1202,6 +3529,110 @@ mod tests {
         assert!(components.registers.b.get() == 0);
     }
 
+    #[test]
+    fn inc_b_wraps_from_ff() {
+        let mut components = runtime_components();
+        components.registers.b.set(0xFF);
+        _0x04 {}.execute(&mut components, Operands::None);
+        assert_eq!(components.registers.b.get(), 0x00);
+        assert!(components.registers.f.get_zero() == FlagValue::Set);
+        assert!(components.registers.f.get_half_carry() == FlagValue::Set);
+    }
+
+    #[test]
+    fn dec_b_wraps_from_00() {
+        let mut components = runtime_components();
+        components.registers.b.set(0x00);
+        _0x05 {}.execute(&mut components, Operands::None);
+        assert_eq!(components.registers.b.get(), 0xFF);
+        assert!(components.registers.f.get_sign() == FlagValue::Set);
+        assert!(components.registers.f.get_zero() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn dec_bc_wraps_from_0000() {
+        let mut components = runtime_components();
+        components.registers.b.set(0x00);
+        components.registers.c.set(0x00);
+        _0x0B {}.execute(&mut components, Operands::None);
+        assert_eq!(components.registers.b.get(), 0xFF);
+        assert_eq!(components.registers.c.get(), 0xFF);
+    }
+
+    #[test]
+    fn rotate_through_carry_differs_from_circular() {
+        let mut components = runtime_components();
+
+        // RLCA copies bit 7 into bit 0, ignoring the previous carry.
+        components.registers.a.set(0x81);
+        components.registers.f.set_carry(FlagValue::Unset);
+        _0x07 {}.execute(&mut components, Operands::None);
+        assert_eq!(components.registers.a.get(), 0x03);
+        assert!(components.registers.f.get_carry() == FlagValue::Set);
+
+        // RLA moves the previous carry into bit 0 instead.
+        components.registers.a.set(0x81);
+        components.registers.f.set_carry(FlagValue::Unset);
+        _0x17 {}.execute(&mut components, Operands::None);
+        assert_eq!(components.registers.a.get(), 0x02);
+        assert!(components.registers.f.get_carry() == FlagValue::Set);
+
+        // RRCA copies bit 0 into bit 7.
+        components.registers.a.set(0x01);
+        components.registers.f.set_carry(FlagValue::Unset);
+        _0x0F {}.execute(&mut components, Operands::None);
+        assert_eq!(components.registers.a.get(), 0x80);
+        assert!(components.registers.f.get_carry() == FlagValue::Set);
+
+        // RRA moves the previous carry into bit 7.
+        components.registers.a.set(0x02);
+        components.registers.f.set_carry(FlagValue::Set);
+        _0x1F {}.execute(&mut components, Operands::None);
+        assert_eq!(components.registers.a.get(), 0x81);
+        assert!(components.registers.f.get_carry() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn accumulator_rotates_only_touch_carry_h_and_n() {
+        let mut components = runtime_components();
+        components.registers.a.set(0x00);
+        components.registers.f.set(0xFF);
+        _0x1F {}.execute(&mut components, Operands::None);
+        // Carry was shifted into bit 7; zero, sign and parity keep their previous values.
+        assert_eq!(components.registers.a.get(), 0x80);
+        assert_eq!(components.registers.f.get() & 0xD7, 0xC4);
+    }
+
+    #[test]
+    fn ex_sp_hl() {
+        let mut components = runtime_components();
+        components.registers.sp.set(0x8000);
+        components.registers.sp.push(&mut components.mem, 0x1234);
+        components.registers.h.set(0xAB);
+        components.registers.l.set(0xCD);
+
+        let cycles = _0xE3 {}.execute(&mut components, Operands::None);
+
+        assert_eq!(cycles, 19);
+        assert_eq!(components.registers.h.get(), 0x12);
+        assert_eq!(components.registers.l.get(), 0x34);
+        assert_eq!(components.registers.sp.get(), 0x7FFE);
+        assert_eq!(components.registers.sp.pop(&components.mem), 0xABCD);
+    }
+
+    #[test]
+    fn jp_hl() {
+        let mut components = runtime_components();
+        components.registers.h.set(0x12);
+        components.registers.l.set(0x34);
+        components.mem.locations[0x1234] = 0xFF;
+
+        let cycles = _0xE9 {}.execute(&mut components, Operands::None);
+
+        assert_eq!(cycles, 4);
+        assert_eq!(components.registers.pc.get(), 0x1234);
+    }
+
     #[test]
     fn rlca_doubling() {
         // The contents of A are rotated left one bit position. 
@@ -1238,7 +3669,7 @@ mod tests {
         let mut components = runtime_components();
 
         components.registers.f.set_zero(FlagValue::Unset);
-        _0xC2 {}.execute(&mut components, Operands::Two(0xAA, 0xFF));
+        _0xC2 {}.execute(&mut components, Operands::Word(0xFFAA));
         assert!(components.registers.pc.get() == 0xFFAA);
     }
 
@@ -1281,13 +3712,51 @@ mod tests {
         assert!(low == 0x8C);
     }
 
+    #[test]
+    fn pop_af_restores_push_af() {
+        let mut components = runtime_components();
+        components.registers.sp.set(0x8000);
+
+        components.registers.a.set(0x5A);
+        components.registers.f.set(0xD7);
+        _0xF5 {}.execute(&mut components, Operands::None);
+
+        components.registers.a.set(0x00);
+        components.registers.f.set(0x00);
+        let cycles = _0xF1 {}.execute(&mut components, Operands::None);
+
+        assert_eq!(cycles, 10);
+        assert_eq!(components.registers.a.get(), 0x5A);
+        assert_eq!(components.registers.f.get(), 0xD7);
+        assert_eq!(components.registers.sp.get(), 0x8000);
+    }
+
+    #[test]
+    fn pop_hl_restores_push_hl() {
+        let mut components = runtime_components();
+        components.registers.sp.set(0x8000);
+
+        components.registers.h.set(0x12);
+        components.registers.l.set(0x34);
+        _0xE5 {}.execute(&mut components, Operands::None);
+
+        components.registers.h.set(0x00);
+        components.registers.l.set(0x00);
+        let cycles = _0xE1 {}.execute(&mut components, Operands::None);
+
+        assert_eq!(cycles, 10);
+        assert_eq!(components.registers.h.get(), 0x12);
+        assert_eq!(components.registers.l.get(), 0x34);
+        assert_eq!(components.registers.sp.get(), 0x8000);
+    }
+
     #[test]
     fn and_n() {
         let mut components = runtime_components();
 
         components.registers.a.set(120);
         components.registers.f.set(0);
-        _0xE6 {}.execute(&mut components, Operands::One(105));
+        _0xE6 {}.execute(&mut components, Operands::Immediate(105));
         assert!(components.registers.f.get_carry() == FlagValue::Unset);
         assert!(components.registers.f.get_add_subtract() == FlagValue::Unset);
         assert!(components.registers.f.get_parity_overflow() == FlagValue::Unset);
@@ -1295,15 +3764,23 @@ mod tests {
         assert!(components.registers.f.get_zero() == FlagValue::Unset);
         assert!(components.registers.f.get_sign() == FlagValue::Unset);
 
+        // 0x80 has a single bit set, so parity is odd.
         components.registers.a.set(128);
         components.registers.f.set(0);
-        _0xE6 {}.execute(&mut components, Operands::One(135));
+        _0xE6 {}.execute(&mut components, Operands::Immediate(135));
         assert!(components.registers.f.get_carry() == FlagValue::Unset);
         assert!(components.registers.f.get_add_subtract() == FlagValue::Unset);
-        assert!(components.registers.f.get_parity_overflow() == FlagValue::Set);
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Unset);
         assert!(components.registers.f.get_half_carry() == FlagValue::Set);
         assert!(components.registers.f.get_zero() == FlagValue::Unset);
-        assert!(components.registers.f.get_sign() == FlagValue::Unset);
+        assert!(components.registers.f.get_sign() == FlagValue::Set);
+
+        // 0x0F & 0x3C = 0x0C has two bits set, so parity is even.
+        components.registers.a.set(0x0F);
+        components.registers.f.set(0);
+        _0xE6 {}.execute(&mut components, Operands::Immediate(0x3C));
+        assert_eq!(components.registers.a.get(), 0x0C);
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Set);
     }
 
 
@@ -1319,22 +3796,142 @@ mod tests {
         assert!(components.registers.c.get() == 0x3E);
     }
 
+    #[test]
+    fn ld_nn_hl_stores_low_byte_first() {
+        let mut components = runtime_components();
+        components.registers.h.set(0xAB);
+        components.registers.l.set(0xCD);
+        let cycles = _0x22 {}.execute(&mut components, Operands::Word(0x4000));
+        assert_eq!(cycles, 16);
+        assert_eq!(components.mem.locations[0x4000], 0xCD);
+        assert_eq!(components.mem.locations[0x4001], 0xAB);
+
+        components.registers.h.set(0x00);
+        components.registers.l.set(0x00);
+        assert_eq!(_0x2A {}.execute(&mut components, Operands::Word(0x4000)), 16);
+        assert_eq!(components.registers.h.get(), 0xAB);
+        assert_eq!(components.registers.l.get(), 0xCD);
+    }
+
+    #[test]
+    fn add_hl_de_half_carry_from_bit_11() {
+        let mut components = runtime_components();
+        components.registers.h.set(0x0F);
+        components.registers.l.set(0xFF);
+        components.registers.d.set(0x00);
+        components.registers.e.set(0x01);
+        components.registers.f.set_add_subtract(FlagValue::Set);
+        let cycles = _0x19 {}.execute(&mut components, Operands::None);
+        assert_eq!(cycles, 11);
+        assert_eq!(components.registers.h.get(), 0x10);
+        assert_eq!(components.registers.l.get(), 0x00);
+        assert!(components.registers.f.get_half_carry() == FlagValue::Set);
+        assert!(components.registers.f.get_add_subtract() == FlagValue::Unset);
+        assert!(components.registers.f.get_carry() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn add_hl_hl_carries_out_of_bit_15() {
+        let mut components = runtime_components();
+        components.registers.h.set(0x88);
+        components.registers.l.set(0x00);
+        _0x29 {}.execute(&mut components, Operands::None);
+        assert_eq!(components.registers.h.get(), 0x10);
+        assert_eq!(components.registers.l.get(), 0x00);
+        assert!(components.registers.f.get_carry() == FlagValue::Set);
+        assert!(components.registers.f.get_half_carry() == FlagValue::Set);
+    }
+
+    #[test]
+    fn sub_n_borrows() {
+        let mut components = runtime_components();
+        components.registers.a.set(0x10);
+        components.registers.f.set(0x00);
+        let cycles = _0xD6 {}.execute(&mut components, Operands::Immediate(0x20));
+        assert_eq!(cycles, 7);
+        assert_eq!(components.registers.a.get(), 0xF0);
+        assert!(components.registers.f.get_carry() == FlagValue::Set);
+        assert!(components.registers.f.get_half_carry() == FlagValue::Unset);
+        assert!(components.registers.f.get_add_subtract() == FlagValue::Set);
+        assert!(components.registers.f.get_sign() == FlagValue::Set);
+        assert!(components.registers.f.get_zero() == FlagValue::Unset);
+    }
+
     #[test]
     fn sbc_a_n() {
         let mut components = runtime_components();
         components.registers.a.set(0x11);
         components.registers.f.set(0x01);
-        let cycles = _0xDE {}.execute(&mut components, Operands::One(0x01));
+        let cycles = _0xDE {}.execute(&mut components, Operands::Immediate(0x01));
         assert_eq!(cycles, 7);
         assert_eq!(components.registers.a.get(), 0x0F);
 
         components.registers.a.set(0x12);
         components.registers.f.set(0x00);
-        let cycles = _0xDE {}.execute(&mut components, Operands::One(0x01));
+        let cycles = _0xDE {}.execute(&mut components, Operands::Immediate(0x01));
         assert_eq!(cycles, 7);
         assert_eq!(components.registers.a.get(), 0x11);
     }
 
+    #[test]
+    fn add_a_n_carries_past_ff() {
+        let mut components = runtime_components();
+        components.registers.a.set(0xF0);
+        components.registers.f.set(0x00);
+        let cycles = _0xC6 {}.execute(&mut components, Operands::Immediate(0x20));
+        assert_eq!(cycles, 7);
+        assert_eq!(components.registers.a.get(), 0x10);
+        assert!(components.registers.f.get_carry() == FlagValue::Set);
+        assert!(components.registers.f.get_zero() == FlagValue::Unset);
+    }
 
-}
+    #[test]
+    fn adc_a_n_adds_carry_in() {
+        let mut components = runtime_components();
+        components.registers.a.set(0x0E);
+        components.registers.f.set(0x01);
+        let cycles = _0xCE {}.execute(&mut components, Operands::Immediate(0x01));
+        assert_eq!(cycles, 7);
+        assert_eq!(components.registers.a.get(), 0x10);
+        assert!(components.registers.f.get_half_carry() == FlagValue::Set);
+        assert!(components.registers.f.get_carry() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn or_n() {
+        let mut components = runtime_components();
+        components.registers.a.set(0x42);
+        components.registers.f.set(0xFF);
+        let cycles = _0xF6 {}.execute(&mut components, Operands::Immediate(0x00));
+        assert_eq!(cycles, 7);
+        assert_eq!(components.registers.a.get(), 0x42);
+        assert!(components.registers.f.get_zero() == FlagValue::Unset);
+        assert!(components.registers.f.get_carry() == FlagValue::Unset);
+        assert!(components.registers.f.get_half_carry() == FlagValue::Unset);
+
+        components.registers.a.set(0x00);
+        _0xF6 {}.execute(&mut components, Operands::Immediate(0x00));
+        assert_eq!(components.registers.a.get(), 0x00);
+        assert!(components.registers.f.get_zero() == FlagValue::Set);
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Set);
+
+        _0xF6 {}.execute(&mut components, Operands::Immediate(0x80));
+        assert_eq!(components.registers.a.get(), 0x80);
+        assert!(components.registers.f.get_sign() == FlagValue::Set);
+    }
 
+    #[test]
+    fn xor_n() {
+        let mut components = runtime_components();
+        components.registers.a.set(0xFF);
+        components.registers.f.set(0xFF);
+        let cycles = _0xEE {}.execute(&mut components, Operands::Immediate(0x0F));
+        assert_eq!(cycles, 7);
+        assert_eq!(components.registers.a.get(), 0xF0);
+        assert!(components.registers.f.get_sign() == FlagValue::Set);
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Set);
+        assert!(components.registers.f.get_add_subtract() == FlagValue::Unset);
+        assert!(components.registers.f.get_carry() == FlagValue::Unset);
+        assert!(components.registers.f.get_half_carry() == FlagValue::Unset);
+    }
+}
@@ -3,15 +3,3343 @@ use log::error;
 use crate::{memory::{Memory, Registers, AddressBus, DataBus, Register, RegisterOperations}, utils::{self, combine_to_double_byte, split_double_byte}, runtime::{Runtime, RuntimeComponents}, inst_metadata};
 use super::{Instruction, Operands};
 
+pub struct _0xCB00 {}
+impl Instruction for _0xCB00 {
+    // The contents of B are rotated left one bit position.
+    // The contents of bit 7 are copied to the carry flag and bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rlc_value(reg.b.get(), &mut reg.f);
+        reg.b.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 00", "RLC B");
+}
+
+pub struct _0xCB01 {}
+impl Instruction for _0xCB01 {
+    // The contents of C are rotated left one bit position.
+    // The contents of bit 7 are copied to the carry flag and bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rlc_value(reg.c.get(), &mut reg.f);
+        reg.c.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 01", "RLC C");
+}
+
+pub struct _0xCB02 {}
+impl Instruction for _0xCB02 {
+    // The contents of D are rotated left one bit position.
+    // The contents of bit 7 are copied to the carry flag and bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rlc_value(reg.d.get(), &mut reg.f);
+        reg.d.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 02", "RLC D");
+}
+
+pub struct _0xCB03 {}
+impl Instruction for _0xCB03 {
+    // The contents of E are rotated left one bit position.
+    // The contents of bit 7 are copied to the carry flag and bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rlc_value(reg.e.get(), &mut reg.f);
+        reg.e.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 03", "RLC E");
+}
+
+pub struct _0xCB04 {}
+impl Instruction for _0xCB04 {
+    // The contents of H are rotated left one bit position.
+    // The contents of bit 7 are copied to the carry flag and bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rlc_value(reg.h.get(), &mut reg.f);
+        reg.h.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 04", "RLC H");
+}
+
+pub struct _0xCB05 {}
+impl Instruction for _0xCB05 {
+    // The contents of L are rotated left one bit position.
+    // The contents of bit 7 are copied to the carry flag and bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rlc_value(reg.l.get(), &mut reg.f);
+        reg.l.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 05", "RLC L");
+}
+
+pub struct _0xCB06 {}
+impl Instruction for _0xCB06 {
+    // The contents of the memory location pointed to by HL are rotated left one bit position.
+    // The contents of bit 7 are copied to the carry flag and bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, RegisterOperations::rlc_value);
+        15
+    }
+
+    inst_metadata!(0, "CB 06", "RLC (HL)");
+}
+
+pub struct _0xCB07 {}
+impl Instruction for _0xCB07 {
+    // The contents of A are rotated left one bit position.
+    // The contents of bit 7 are copied to the carry flag and bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rlc_value(reg.a.get(), &mut reg.f);
+        reg.a.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 07", "RLC A");
+}
+
+pub struct _0xCB08 {}
+impl Instruction for _0xCB08 {
+    // The contents of B are rotated right one bit position.
+    // The contents of bit 0 are copied to the carry flag and bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rrc_value(reg.b.get(), &mut reg.f);
+        reg.b.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 08", "RRC B");
+}
+
+pub struct _0xCB09 {}
+impl Instruction for _0xCB09 {
+    // The contents of C are rotated right one bit position.
+    // The contents of bit 0 are copied to the carry flag and bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rrc_value(reg.c.get(), &mut reg.f);
+        reg.c.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 09", "RRC C");
+}
+
+pub struct _0xCB0A {}
+impl Instruction for _0xCB0A {
+    // The contents of D are rotated right one bit position.
+    // The contents of bit 0 are copied to the carry flag and bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rrc_value(reg.d.get(), &mut reg.f);
+        reg.d.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 0A", "RRC D");
+}
+
+pub struct _0xCB0B {}
+impl Instruction for _0xCB0B {
+    // The contents of E are rotated right one bit position.
+    // The contents of bit 0 are copied to the carry flag and bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rrc_value(reg.e.get(), &mut reg.f);
+        reg.e.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 0B", "RRC E");
+}
+
+pub struct _0xCB0C {}
+impl Instruction for _0xCB0C {
+    // The contents of H are rotated right one bit position.
+    // The contents of bit 0 are copied to the carry flag and bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rrc_value(reg.h.get(), &mut reg.f);
+        reg.h.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 0C", "RRC H");
+}
+
+pub struct _0xCB0D {}
+impl Instruction for _0xCB0D {
+    // The contents of L are rotated right one bit position.
+    // The contents of bit 0 are copied to the carry flag and bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rrc_value(reg.l.get(), &mut reg.f);
+        reg.l.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 0D", "RRC L");
+}
+
+pub struct _0xCB0E {}
+impl Instruction for _0xCB0E {
+    // The contents of the memory location pointed to by HL are rotated right one bit position.
+    // The contents of bit 0 are copied to the carry flag and bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, RegisterOperations::rrc_value);
+        15
+    }
+
+    inst_metadata!(0, "CB 0E", "RRC (HL)");
+}
+
+pub struct _0xCB0F {}
+impl Instruction for _0xCB0F {
+    // The contents of A are rotated right one bit position.
+    // The contents of bit 0 are copied to the carry flag and bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rrc_value(reg.a.get(), &mut reg.f);
+        reg.a.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 0F", "RRC A");
+}
+
+pub struct _0xCB10 {}
+impl Instruction for _0xCB10 {
+    // The contents of B are rotated left one bit position through the carry flag.
+    // The previous carry is copied to bit 0 and bit 7 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rl_value(reg.b.get(), &mut reg.f);
+        reg.b.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 10", "RL B");
+}
+
+pub struct _0xCB11 {}
+impl Instruction for _0xCB11 {
+    // The contents of C are rotated left one bit position through the carry flag.
+    // The previous carry is copied to bit 0 and bit 7 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rl_value(reg.c.get(), &mut reg.f);
+        reg.c.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 11", "RL C");
+}
+
+pub struct _0xCB12 {}
+impl Instruction for _0xCB12 {
+    // The contents of D are rotated left one bit position through the carry flag.
+    // The previous carry is copied to bit 0 and bit 7 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rl_value(reg.d.get(), &mut reg.f);
+        reg.d.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 12", "RL D");
+}
+
+pub struct _0xCB13 {}
+impl Instruction for _0xCB13 {
+    // The contents of E are rotated left one bit position through the carry flag.
+    // The previous carry is copied to bit 0 and bit 7 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rl_value(reg.e.get(), &mut reg.f);
+        reg.e.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 13", "RL E");
+}
+
+pub struct _0xCB14 {}
+impl Instruction for _0xCB14 {
+    // The contents of H are rotated left one bit position through the carry flag.
+    // The previous carry is copied to bit 0 and bit 7 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rl_value(reg.h.get(), &mut reg.f);
+        reg.h.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 14", "RL H");
+}
+
+pub struct _0xCB15 {}
+impl Instruction for _0xCB15 {
+    // The contents of L are rotated left one bit position through the carry flag.
+    // The previous carry is copied to bit 0 and bit 7 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rl_value(reg.l.get(), &mut reg.f);
+        reg.l.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 15", "RL L");
+}
+
+pub struct _0xCB16 {}
+impl Instruction for _0xCB16 {
+    // The contents of the memory location pointed to by HL are rotated left one bit position through the carry flag.
+    // The previous carry is copied to bit 0 and bit 7 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, RegisterOperations::rl_value);
+        15
+    }
+
+    inst_metadata!(0, "CB 16", "RL (HL)");
+}
+
+pub struct _0xCB17 {}
+impl Instruction for _0xCB17 {
+    // The contents of A are rotated left one bit position through the carry flag.
+    // The previous carry is copied to bit 0 and bit 7 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rl_value(reg.a.get(), &mut reg.f);
+        reg.a.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 17", "RL A");
+}
+
+pub struct _0xCB18 {}
+impl Instruction for _0xCB18 {
+    // The contents of B are rotated right one bit position through the carry flag.
+    // The previous carry is copied to bit 7 and bit 0 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rr_value(reg.b.get(), &mut reg.f);
+        reg.b.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 18", "RR B");
+}
+
+pub struct _0xCB19 {}
+impl Instruction for _0xCB19 {
+    // The contents of C are rotated right one bit position through the carry flag.
+    // The previous carry is copied to bit 7 and bit 0 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rr_value(reg.c.get(), &mut reg.f);
+        reg.c.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 19", "RR C");
+}
+
+pub struct _0xCB1A {}
+impl Instruction for _0xCB1A {
+    // The contents of D are rotated right one bit position through the carry flag.
+    // The previous carry is copied to bit 7 and bit 0 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rr_value(reg.d.get(), &mut reg.f);
+        reg.d.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 1A", "RR D");
+}
+
+pub struct _0xCB1B {}
+impl Instruction for _0xCB1B {
+    // The contents of E are rotated right one bit position through the carry flag.
+    // The previous carry is copied to bit 7 and bit 0 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rr_value(reg.e.get(), &mut reg.f);
+        reg.e.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 1B", "RR E");
+}
+
+pub struct _0xCB1C {}
+impl Instruction for _0xCB1C {
+    // The contents of H are rotated right one bit position through the carry flag.
+    // The previous carry is copied to bit 7 and bit 0 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rr_value(reg.h.get(), &mut reg.f);
+        reg.h.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 1C", "RR H");
+}
+
+pub struct _0xCB1D {}
+impl Instruction for _0xCB1D {
+    // The contents of L are rotated right one bit position through the carry flag.
+    // The previous carry is copied to bit 7 and bit 0 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rr_value(reg.l.get(), &mut reg.f);
+        reg.l.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 1D", "RR L");
+}
+
+pub struct _0xCB1E {}
+impl Instruction for _0xCB1E {
+    // The contents of the memory location pointed to by HL are rotated right one bit position through the carry flag.
+    // The previous carry is copied to bit 7 and bit 0 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, RegisterOperations::rr_value);
+        15
+    }
+
+    inst_metadata!(0, "CB 1E", "RR (HL)");
+}
+
+pub struct _0xCB1F {}
+impl Instruction for _0xCB1F {
+    // The contents of A are rotated right one bit position through the carry flag.
+    // The previous carry is copied to bit 7 and bit 0 is copied to the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::rr_value(reg.a.get(), &mut reg.f);
+        reg.a.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 1F", "RR A");
+}
+
+pub struct _0xCB20 {}
+impl Instruction for _0xCB20 {
+    // The contents of B are shifted left one bit position.
+    // The contents of bit 7 are copied to the carry flag and a zero is put into bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sla_value(reg.b.get(), &mut reg.f);
+        reg.b.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 20", "SLA B");
+}
+
+pub struct _0xCB21 {}
+impl Instruction for _0xCB21 {
+    // The contents of C are shifted left one bit position.
+    // The contents of bit 7 are copied to the carry flag and a zero is put into bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sla_value(reg.c.get(), &mut reg.f);
+        reg.c.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 21", "SLA C");
+}
+
+pub struct _0xCB22 {}
+impl Instruction for _0xCB22 {
+    // The contents of D are shifted left one bit position.
+    // The contents of bit 7 are copied to the carry flag and a zero is put into bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sla_value(reg.d.get(), &mut reg.f);
+        reg.d.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 22", "SLA D");
+}
+
+pub struct _0xCB23 {}
+impl Instruction for _0xCB23 {
+    // The contents of E are shifted left one bit position.
+    // The contents of bit 7 are copied to the carry flag and a zero is put into bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sla_value(reg.e.get(), &mut reg.f);
+        reg.e.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 23", "SLA E");
+}
+
+pub struct _0xCB24 {}
+impl Instruction for _0xCB24 {
+    // The contents of H are shifted left one bit position.
+    // The contents of bit 7 are copied to the carry flag and a zero is put into bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sla_value(reg.h.get(), &mut reg.f);
+        reg.h.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 24", "SLA H");
+}
+
+pub struct _0xCB25 {}
+impl Instruction for _0xCB25 {
+    // The contents of L are shifted left one bit position.
+    // The contents of bit 7 are copied to the carry flag and a zero is put into bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sla_value(reg.l.get(), &mut reg.f);
+        reg.l.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 25", "SLA L");
+}
+
+pub struct _0xCB26 {}
+impl Instruction for _0xCB26 {
+    // The contents of the memory location pointed to by HL are shifted left one bit position.
+    // The contents of bit 7 are copied to the carry flag and a zero is put into bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, RegisterOperations::sla_value);
+        15
+    }
+
+    inst_metadata!(0, "CB 26", "SLA (HL)");
+}
+
+pub struct _0xCB27 {}
+impl Instruction for _0xCB27 {
+    // The contents of A are shifted left one bit position.
+    // The contents of bit 7 are copied to the carry flag and a zero is put into bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sla_value(reg.a.get(), &mut reg.f);
+        reg.a.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 27", "SLA A");
+}
+
+pub struct _0xCB28 {}
+impl Instruction for _0xCB28 {
+    // The contents of B are shifted right one bit position.
+    // The contents of bit 0 are copied to the carry flag and the previous contents of bit 7 are unchanged.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sra_value(reg.b.get(), &mut reg.f);
+        reg.b.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 28", "SRA B");
+}
+
+pub struct _0xCB29 {}
+impl Instruction for _0xCB29 {
+    // The contents of C are shifted right one bit position.
+    // The contents of bit 0 are copied to the carry flag and the previous contents of bit 7 are unchanged.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sra_value(reg.c.get(), &mut reg.f);
+        reg.c.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 29", "SRA C");
+}
+
+pub struct _0xCB2A {}
+impl Instruction for _0xCB2A {
+    // The contents of D are shifted right one bit position.
+    // The contents of bit 0 are copied to the carry flag and the previous contents of bit 7 are unchanged.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sra_value(reg.d.get(), &mut reg.f);
+        reg.d.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 2A", "SRA D");
+}
+
+pub struct _0xCB2B {}
+impl Instruction for _0xCB2B {
+    // The contents of E are shifted right one bit position.
+    // The contents of bit 0 are copied to the carry flag and the previous contents of bit 7 are unchanged.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sra_value(reg.e.get(), &mut reg.f);
+        reg.e.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 2B", "SRA E");
+}
+
+pub struct _0xCB2C {}
+impl Instruction for _0xCB2C {
+    // The contents of H are shifted right one bit position.
+    // The contents of bit 0 are copied to the carry flag and the previous contents of bit 7 are unchanged.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sra_value(reg.h.get(), &mut reg.f);
+        reg.h.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 2C", "SRA H");
+}
+
+pub struct _0xCB2D {}
+impl Instruction for _0xCB2D {
+    // The contents of L are shifted right one bit position.
+    // The contents of bit 0 are copied to the carry flag and the previous contents of bit 7 are unchanged.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sra_value(reg.l.get(), &mut reg.f);
+        reg.l.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 2D", "SRA L");
+}
+
+pub struct _0xCB2E {}
+impl Instruction for _0xCB2E {
+    // The contents of the memory location pointed to by HL are shifted right one bit position.
+    // The contents of bit 0 are copied to the carry flag and the previous contents of bit 7 are unchanged.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, RegisterOperations::sra_value);
+        15
+    }
+
+    inst_metadata!(0, "CB 2E", "SRA (HL)");
+}
+
+pub struct _0xCB2F {}
+impl Instruction for _0xCB2F {
+    // The contents of A are shifted right one bit position.
+    // The contents of bit 0 are copied to the carry flag and the previous contents of bit 7 are unchanged.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sra_value(reg.a.get(), &mut reg.f);
+        reg.a.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 2F", "SRA A");
+}
+
+pub struct _0xCB30 {}
+impl Instruction for _0xCB30 {
+    // The contents of B are shifted left one bit position.
+    // The contents of bit 7 are copied to the carry flag and a one is put into bit 0. Undocumented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sll_value(reg.b.get(), &mut reg.f);
+        reg.b.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 30", "SLL B");
+}
+
+pub struct _0xCB31 {}
+impl Instruction for _0xCB31 {
+    // The contents of C are shifted left one bit position.
+    // The contents of bit 7 are copied to the carry flag and a one is put into bit 0. Undocumented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sll_value(reg.c.get(), &mut reg.f);
+        reg.c.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 31", "SLL C");
+}
+
+pub struct _0xCB32 {}
+impl Instruction for _0xCB32 {
+    // The contents of D are shifted left one bit position.
+    // The contents of bit 7 are copied to the carry flag and a one is put into bit 0. Undocumented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sll_value(reg.d.get(), &mut reg.f);
+        reg.d.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 32", "SLL D");
+}
+
+pub struct _0xCB33 {}
+impl Instruction for _0xCB33 {
+    // The contents of E are shifted left one bit position.
+    // The contents of bit 7 are copied to the carry flag and a one is put into bit 0. Undocumented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sll_value(reg.e.get(), &mut reg.f);
+        reg.e.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 33", "SLL E");
+}
+
+pub struct _0xCB34 {}
+impl Instruction for _0xCB34 {
+    // The contents of H are shifted left one bit position.
+    // The contents of bit 7 are copied to the carry flag and a one is put into bit 0. Undocumented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sll_value(reg.h.get(), &mut reg.f);
+        reg.h.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 34", "SLL H");
+}
+
+pub struct _0xCB35 {}
+impl Instruction for _0xCB35 {
+    // The contents of L are shifted left one bit position.
+    // The contents of bit 7 are copied to the carry flag and a one is put into bit 0. Undocumented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sll_value(reg.l.get(), &mut reg.f);
+        reg.l.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 35", "SLL L");
+}
+
+pub struct _0xCB36 {}
+impl Instruction for _0xCB36 {
+    // The contents of the memory location pointed to by HL are shifted left one bit position.
+    // The contents of bit 7 are copied to the carry flag and a one is put into bit 0. Undocumented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, RegisterOperations::sll_value);
+        15
+    }
+
+    inst_metadata!(0, "CB 36", "SLL (HL)");
+}
+
+pub struct _0xCB37 {}
+impl Instruction for _0xCB37 {
+    // The contents of A are shifted left one bit position.
+    // The contents of bit 7 are copied to the carry flag and a one is put into bit 0. Undocumented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::sll_value(reg.a.get(), &mut reg.f);
+        reg.a.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 37", "SLL A");
+}
+
 pub struct _0xCB38 {}
 impl Instruction for _0xCB38 {
-    // The contents of B are shifted right one bit position. 
+    // The contents of B are shifted right one bit position.
+    // The contents of bit 0 are copied to the carry flag and a zero is put into bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::srl_value(reg.b.get(), &mut reg.f);
+        reg.b.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 38", "SRL B");
+}
+
+pub struct _0xCB39 {}
+impl Instruction for _0xCB39 {
+    // The contents of C are shifted right one bit position.
+    // The contents of bit 0 are copied to the carry flag and a zero is put into bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::srl_value(reg.c.get(), &mut reg.f);
+        reg.c.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 39", "SRL C");
+}
+
+pub struct _0xCB3A {}
+impl Instruction for _0xCB3A {
+    // The contents of D are shifted right one bit position.
+    // The contents of bit 0 are copied to the carry flag and a zero is put into bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::srl_value(reg.d.get(), &mut reg.f);
+        reg.d.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 3A", "SRL D");
+}
+
+pub struct _0xCB3B {}
+impl Instruction for _0xCB3B {
+    // The contents of E are shifted right one bit position.
+    // The contents of bit 0 are copied to the carry flag and a zero is put into bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::srl_value(reg.e.get(), &mut reg.f);
+        reg.e.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 3B", "SRL E");
+}
+
+pub struct _0xCB3C {}
+impl Instruction for _0xCB3C {
+    // The contents of H are shifted right one bit position.
+    // The contents of bit 0 are copied to the carry flag and a zero is put into bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::srl_value(reg.h.get(), &mut reg.f);
+        reg.h.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 3C", "SRL H");
+}
+
+pub struct _0xCB3D {}
+impl Instruction for _0xCB3D {
+    // The contents of L are shifted right one bit position.
     // The contents of bit 0 are copied to the carry flag and a zero is put into bit 7.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         let reg = &mut components.registers;
-        RegisterOperations::srl(&mut reg.b, &mut reg.f);
+        let value = RegisterOperations::srl_value(reg.l.get(), &mut reg.f);
+        reg.l.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 3D", "SRL L");
+}
+
+pub struct _0xCB3E {}
+impl Instruction for _0xCB3E {
+    // The contents of the memory location pointed to by HL are shifted right one bit position.
+    // The contents of bit 0 are copied to the carry flag and a zero is put into bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, RegisterOperations::srl_value);
+        15
+    }
+
+    inst_metadata!(0, "CB 3E", "SRL (HL)");
+}
+
+pub struct _0xCB3F {}
+impl Instruction for _0xCB3F {
+    // The contents of A are shifted right one bit position.
+    // The contents of bit 0 are copied to the carry flag and a zero is put into bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        let value = RegisterOperations::srl_value(reg.a.get(), &mut reg.f);
+        reg.a.set(value);
+        8
+    }
+
+    inst_metadata!(0, "CB 3F", "SRL A");
+}
+
+pub struct _0xCB40 {}
+impl Instruction for _0xCB40 {
+    // Tests bit 0 of B, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(0, reg.b.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 40", "BIT 0,B");
+}
+
+pub struct _0xCB41 {}
+impl Instruction for _0xCB41 {
+    // Tests bit 0 of C, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(0, reg.c.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 41", "BIT 0,C");
+}
+
+pub struct _0xCB42 {}
+impl Instruction for _0xCB42 {
+    // Tests bit 0 of D, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(0, reg.d.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 42", "BIT 0,D");
+}
+
+pub struct _0xCB43 {}
+impl Instruction for _0xCB43 {
+    // Tests bit 0 of E, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(0, reg.e.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 43", "BIT 0,E");
+}
+
+pub struct _0xCB44 {}
+impl Instruction for _0xCB44 {
+    // Tests bit 0 of H, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(0, reg.h.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 44", "BIT 0,H");
+}
+
+pub struct _0xCB45 {}
+impl Instruction for _0xCB45 {
+    // Tests bit 0 of L, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(0, reg.l.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 45", "BIT 0,L");
+}
+
+pub struct _0xCB46 {}
+impl Instruction for _0xCB46 {
+    // Tests bit 0 of the memory location pointed to by HL, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, flags| {
+            RegisterOperations::bit(0, value, flags);
+            value
+        });
+        12
+    }
+
+    inst_metadata!(0, "CB 46", "BIT 0,(HL)");
+}
+
+pub struct _0xCB47 {}
+impl Instruction for _0xCB47 {
+    // Tests bit 0 of A, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(0, reg.a.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 47", "BIT 0,A");
+}
+
+pub struct _0xCB48 {}
+impl Instruction for _0xCB48 {
+    // Tests bit 1 of B, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(1, reg.b.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 48", "BIT 1,B");
+}
+
+pub struct _0xCB49 {}
+impl Instruction for _0xCB49 {
+    // Tests bit 1 of C, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(1, reg.c.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 49", "BIT 1,C");
+}
+
+pub struct _0xCB4A {}
+impl Instruction for _0xCB4A {
+    // Tests bit 1 of D, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(1, reg.d.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 4A", "BIT 1,D");
+}
+
+pub struct _0xCB4B {}
+impl Instruction for _0xCB4B {
+    // Tests bit 1 of E, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(1, reg.e.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 4B", "BIT 1,E");
+}
+
+pub struct _0xCB4C {}
+impl Instruction for _0xCB4C {
+    // Tests bit 1 of H, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(1, reg.h.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 4C", "BIT 1,H");
+}
+
+pub struct _0xCB4D {}
+impl Instruction for _0xCB4D {
+    // Tests bit 1 of L, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(1, reg.l.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 4D", "BIT 1,L");
+}
+
+pub struct _0xCB4E {}
+impl Instruction for _0xCB4E {
+    // Tests bit 1 of the memory location pointed to by HL, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, flags| {
+            RegisterOperations::bit(1, value, flags);
+            value
+        });
+        12
+    }
+
+    inst_metadata!(0, "CB 4E", "BIT 1,(HL)");
+}
+
+pub struct _0xCB4F {}
+impl Instruction for _0xCB4F {
+    // Tests bit 1 of A, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(1, reg.a.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 4F", "BIT 1,A");
+}
+
+pub struct _0xCB50 {}
+impl Instruction for _0xCB50 {
+    // Tests bit 2 of B, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(2, reg.b.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 50", "BIT 2,B");
+}
+
+pub struct _0xCB51 {}
+impl Instruction for _0xCB51 {
+    // Tests bit 2 of C, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(2, reg.c.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 51", "BIT 2,C");
+}
+
+pub struct _0xCB52 {}
+impl Instruction for _0xCB52 {
+    // Tests bit 2 of D, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(2, reg.d.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 52", "BIT 2,D");
+}
+
+pub struct _0xCB53 {}
+impl Instruction for _0xCB53 {
+    // Tests bit 2 of E, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(2, reg.e.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 53", "BIT 2,E");
+}
+
+pub struct _0xCB54 {}
+impl Instruction for _0xCB54 {
+    // Tests bit 2 of H, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(2, reg.h.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 54", "BIT 2,H");
+}
+
+pub struct _0xCB55 {}
+impl Instruction for _0xCB55 {
+    // Tests bit 2 of L, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(2, reg.l.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 55", "BIT 2,L");
+}
+
+pub struct _0xCB56 {}
+impl Instruction for _0xCB56 {
+    // Tests bit 2 of the memory location pointed to by HL, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, flags| {
+            RegisterOperations::bit(2, value, flags);
+            value
+        });
+        12
+    }
+
+    inst_metadata!(0, "CB 56", "BIT 2,(HL)");
+}
+
+pub struct _0xCB57 {}
+impl Instruction for _0xCB57 {
+    // Tests bit 2 of A, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(2, reg.a.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 57", "BIT 2,A");
+}
+
+pub struct _0xCB58 {}
+impl Instruction for _0xCB58 {
+    // Tests bit 3 of B, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(3, reg.b.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 58", "BIT 3,B");
+}
+
+pub struct _0xCB59 {}
+impl Instruction for _0xCB59 {
+    // Tests bit 3 of C, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(3, reg.c.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 59", "BIT 3,C");
+}
+
+pub struct _0xCB5A {}
+impl Instruction for _0xCB5A {
+    // Tests bit 3 of D, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(3, reg.d.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 5A", "BIT 3,D");
+}
+
+pub struct _0xCB5B {}
+impl Instruction for _0xCB5B {
+    // Tests bit 3 of E, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(3, reg.e.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 5B", "BIT 3,E");
+}
+
+pub struct _0xCB5C {}
+impl Instruction for _0xCB5C {
+    // Tests bit 3 of H, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(3, reg.h.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 5C", "BIT 3,H");
+}
+
+pub struct _0xCB5D {}
+impl Instruction for _0xCB5D {
+    // Tests bit 3 of L, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(3, reg.l.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 5D", "BIT 3,L");
+}
+
+pub struct _0xCB5E {}
+impl Instruction for _0xCB5E {
+    // Tests bit 3 of the memory location pointed to by HL, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, flags| {
+            RegisterOperations::bit(3, value, flags);
+            value
+        });
+        12
+    }
+
+    inst_metadata!(0, "CB 5E", "BIT 3,(HL)");
+}
+
+pub struct _0xCB5F {}
+impl Instruction for _0xCB5F {
+    // Tests bit 3 of A, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(3, reg.a.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 5F", "BIT 3,A");
+}
+
+pub struct _0xCB60 {}
+impl Instruction for _0xCB60 {
+    // Tests bit 4 of B, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(4, reg.b.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 60", "BIT 4,B");
+}
+
+pub struct _0xCB61 {}
+impl Instruction for _0xCB61 {
+    // Tests bit 4 of C, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(4, reg.c.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 61", "BIT 4,C");
+}
+
+pub struct _0xCB62 {}
+impl Instruction for _0xCB62 {
+    // Tests bit 4 of D, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(4, reg.d.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 62", "BIT 4,D");
+}
+
+pub struct _0xCB63 {}
+impl Instruction for _0xCB63 {
+    // Tests bit 4 of E, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(4, reg.e.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 63", "BIT 4,E");
+}
+
+pub struct _0xCB64 {}
+impl Instruction for _0xCB64 {
+    // Tests bit 4 of H, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(4, reg.h.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 64", "BIT 4,H");
+}
+
+pub struct _0xCB65 {}
+impl Instruction for _0xCB65 {
+    // Tests bit 4 of L, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(4, reg.l.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 65", "BIT 4,L");
+}
+
+pub struct _0xCB66 {}
+impl Instruction for _0xCB66 {
+    // Tests bit 4 of the memory location pointed to by HL, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, flags| {
+            RegisterOperations::bit(4, value, flags);
+            value
+        });
+        12
+    }
+
+    inst_metadata!(0, "CB 66", "BIT 4,(HL)");
+}
+
+pub struct _0xCB67 {}
+impl Instruction for _0xCB67 {
+    // Tests bit 4 of A, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(4, reg.a.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 67", "BIT 4,A");
+}
+
+pub struct _0xCB68 {}
+impl Instruction for _0xCB68 {
+    // Tests bit 5 of B, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(5, reg.b.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 68", "BIT 5,B");
+}
+
+pub struct _0xCB69 {}
+impl Instruction for _0xCB69 {
+    // Tests bit 5 of C, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(5, reg.c.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 69", "BIT 5,C");
+}
+
+pub struct _0xCB6A {}
+impl Instruction for _0xCB6A {
+    // Tests bit 5 of D, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(5, reg.d.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 6A", "BIT 5,D");
+}
+
+pub struct _0xCB6B {}
+impl Instruction for _0xCB6B {
+    // Tests bit 5 of E, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(5, reg.e.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 6B", "BIT 5,E");
+}
+
+pub struct _0xCB6C {}
+impl Instruction for _0xCB6C {
+    // Tests bit 5 of H, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(5, reg.h.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 6C", "BIT 5,H");
+}
+
+pub struct _0xCB6D {}
+impl Instruction for _0xCB6D {
+    // Tests bit 5 of L, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(5, reg.l.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 6D", "BIT 5,L");
+}
+
+pub struct _0xCB6E {}
+impl Instruction for _0xCB6E {
+    // Tests bit 5 of the memory location pointed to by HL, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, flags| {
+            RegisterOperations::bit(5, value, flags);
+            value
+        });
+        12
+    }
+
+    inst_metadata!(0, "CB 6E", "BIT 5,(HL)");
+}
+
+pub struct _0xCB6F {}
+impl Instruction for _0xCB6F {
+    // Tests bit 5 of A, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(5, reg.a.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 6F", "BIT 5,A");
+}
+
+pub struct _0xCB70 {}
+impl Instruction for _0xCB70 {
+    // Tests bit 6 of B, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(6, reg.b.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 70", "BIT 6,B");
+}
+
+pub struct _0xCB71 {}
+impl Instruction for _0xCB71 {
+    // Tests bit 6 of C, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(6, reg.c.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 71", "BIT 6,C");
+}
+
+pub struct _0xCB72 {}
+impl Instruction for _0xCB72 {
+    // Tests bit 6 of D, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(6, reg.d.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 72", "BIT 6,D");
+}
+
+pub struct _0xCB73 {}
+impl Instruction for _0xCB73 {
+    // Tests bit 6 of E, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(6, reg.e.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 73", "BIT 6,E");
+}
+
+pub struct _0xCB74 {}
+impl Instruction for _0xCB74 {
+    // Tests bit 6 of H, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(6, reg.h.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 74", "BIT 6,H");
+}
+
+pub struct _0xCB75 {}
+impl Instruction for _0xCB75 {
+    // Tests bit 6 of L, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(6, reg.l.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 75", "BIT 6,L");
+}
+
+pub struct _0xCB76 {}
+impl Instruction for _0xCB76 {
+    // Tests bit 6 of the memory location pointed to by HL, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, flags| {
+            RegisterOperations::bit(6, value, flags);
+            value
+        });
+        12
+    }
+
+    inst_metadata!(0, "CB 76", "BIT 6,(HL)");
+}
+
+pub struct _0xCB77 {}
+impl Instruction for _0xCB77 {
+    // Tests bit 6 of A, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(6, reg.a.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 77", "BIT 6,A");
+}
+
+pub struct _0xCB78 {}
+impl Instruction for _0xCB78 {
+    // Tests bit 7 of B, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(7, reg.b.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 78", "BIT 7,B");
+}
+
+pub struct _0xCB79 {}
+impl Instruction for _0xCB79 {
+    // Tests bit 7 of C, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(7, reg.c.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 79", "BIT 7,C");
+}
+
+pub struct _0xCB7A {}
+impl Instruction for _0xCB7A {
+    // Tests bit 7 of D, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(7, reg.d.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 7A", "BIT 7,D");
+}
+
+pub struct _0xCB7B {}
+impl Instruction for _0xCB7B {
+    // Tests bit 7 of E, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(7, reg.e.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 7B", "BIT 7,E");
+}
+
+pub struct _0xCB7C {}
+impl Instruction for _0xCB7C {
+    // Tests bit 7 of H, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(7, reg.h.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 7C", "BIT 7,H");
+}
+
+pub struct _0xCB7D {}
+impl Instruction for _0xCB7D {
+    // Tests bit 7 of L, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(7, reg.l.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 7D", "BIT 7,L");
+}
+
+pub struct _0xCB7E {}
+impl Instruction for _0xCB7E {
+    // Tests bit 7 of the memory location pointed to by HL, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, flags| {
+            RegisterOperations::bit(7, value, flags);
+            value
+        });
+        12
+    }
+
+    inst_metadata!(0, "CB 7E", "BIT 7,(HL)");
+}
+
+pub struct _0xCB7F {}
+impl Instruction for _0xCB7F {
+    // Tests bit 7 of A, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        RegisterOperations::bit(7, reg.a.get(), &mut reg.f);
+        8
+    }
+
+    inst_metadata!(0, "CB 7F", "BIT 7,A");
+}
+
+pub struct _0xCB80 {}
+impl Instruction for _0xCB80 {
+    // Resets bit 0 of B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.b.set(RegisterOperations::change_bit(0, reg.b.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 80", "RES 0,B");
+}
+
+pub struct _0xCB81 {}
+impl Instruction for _0xCB81 {
+    // Resets bit 0 of C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.c.set(RegisterOperations::change_bit(0, reg.c.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 81", "RES 0,C");
+}
+
+pub struct _0xCB82 {}
+impl Instruction for _0xCB82 {
+    // Resets bit 0 of D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.d.set(RegisterOperations::change_bit(0, reg.d.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 82", "RES 0,D");
+}
+
+pub struct _0xCB83 {}
+impl Instruction for _0xCB83 {
+    // Resets bit 0 of E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.e.set(RegisterOperations::change_bit(0, reg.e.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 83", "RES 0,E");
+}
+
+pub struct _0xCB84 {}
+impl Instruction for _0xCB84 {
+    // Resets bit 0 of H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.h.set(RegisterOperations::change_bit(0, reg.h.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 84", "RES 0,H");
+}
+
+pub struct _0xCB85 {}
+impl Instruction for _0xCB85 {
+    // Resets bit 0 of L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.l.set(RegisterOperations::change_bit(0, reg.l.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 85", "RES 0,L");
+}
+
+pub struct _0xCB86 {}
+impl Instruction for _0xCB86 {
+    // Resets bit 0 of the memory location pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, _| RegisterOperations::change_bit(0, value, false));
+        15
+    }
+
+    inst_metadata!(0, "CB 86", "RES 0,(HL)");
+}
+
+pub struct _0xCB87 {}
+impl Instruction for _0xCB87 {
+    // Resets bit 0 of A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.a.set(RegisterOperations::change_bit(0, reg.a.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 87", "RES 0,A");
+}
+
+pub struct _0xCB88 {}
+impl Instruction for _0xCB88 {
+    // Resets bit 1 of B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.b.set(RegisterOperations::change_bit(1, reg.b.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 88", "RES 1,B");
+}
+
+pub struct _0xCB89 {}
+impl Instruction for _0xCB89 {
+    // Resets bit 1 of C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.c.set(RegisterOperations::change_bit(1, reg.c.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 89", "RES 1,C");
+}
+
+pub struct _0xCB8A {}
+impl Instruction for _0xCB8A {
+    // Resets bit 1 of D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.d.set(RegisterOperations::change_bit(1, reg.d.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 8A", "RES 1,D");
+}
+
+pub struct _0xCB8B {}
+impl Instruction for _0xCB8B {
+    // Resets bit 1 of E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.e.set(RegisterOperations::change_bit(1, reg.e.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 8B", "RES 1,E");
+}
+
+pub struct _0xCB8C {}
+impl Instruction for _0xCB8C {
+    // Resets bit 1 of H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.h.set(RegisterOperations::change_bit(1, reg.h.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 8C", "RES 1,H");
+}
+
+pub struct _0xCB8D {}
+impl Instruction for _0xCB8D {
+    // Resets bit 1 of L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.l.set(RegisterOperations::change_bit(1, reg.l.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 8D", "RES 1,L");
+}
+
+pub struct _0xCB8E {}
+impl Instruction for _0xCB8E {
+    // Resets bit 1 of the memory location pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, _| RegisterOperations::change_bit(1, value, false));
+        15
+    }
+
+    inst_metadata!(0, "CB 8E", "RES 1,(HL)");
+}
+
+pub struct _0xCB8F {}
+impl Instruction for _0xCB8F {
+    // Resets bit 1 of A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.a.set(RegisterOperations::change_bit(1, reg.a.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 8F", "RES 1,A");
+}
+
+pub struct _0xCB90 {}
+impl Instruction for _0xCB90 {
+    // Resets bit 2 of B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.b.set(RegisterOperations::change_bit(2, reg.b.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 90", "RES 2,B");
+}
+
+pub struct _0xCB91 {}
+impl Instruction for _0xCB91 {
+    // Resets bit 2 of C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.c.set(RegisterOperations::change_bit(2, reg.c.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 91", "RES 2,C");
+}
+
+pub struct _0xCB92 {}
+impl Instruction for _0xCB92 {
+    // Resets bit 2 of D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.d.set(RegisterOperations::change_bit(2, reg.d.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 92", "RES 2,D");
+}
+
+pub struct _0xCB93 {}
+impl Instruction for _0xCB93 {
+    // Resets bit 2 of E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.e.set(RegisterOperations::change_bit(2, reg.e.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 93", "RES 2,E");
+}
+
+pub struct _0xCB94 {}
+impl Instruction for _0xCB94 {
+    // Resets bit 2 of H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.h.set(RegisterOperations::change_bit(2, reg.h.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 94", "RES 2,H");
+}
+
+pub struct _0xCB95 {}
+impl Instruction for _0xCB95 {
+    // Resets bit 2 of L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.l.set(RegisterOperations::change_bit(2, reg.l.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 95", "RES 2,L");
+}
+
+pub struct _0xCB96 {}
+impl Instruction for _0xCB96 {
+    // Resets bit 2 of the memory location pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, _| RegisterOperations::change_bit(2, value, false));
+        15
+    }
+
+    inst_metadata!(0, "CB 96", "RES 2,(HL)");
+}
+
+pub struct _0xCB97 {}
+impl Instruction for _0xCB97 {
+    // Resets bit 2 of A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.a.set(RegisterOperations::change_bit(2, reg.a.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 97", "RES 2,A");
+}
+
+pub struct _0xCB98 {}
+impl Instruction for _0xCB98 {
+    // Resets bit 3 of B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.b.set(RegisterOperations::change_bit(3, reg.b.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 98", "RES 3,B");
+}
+
+pub struct _0xCB99 {}
+impl Instruction for _0xCB99 {
+    // Resets bit 3 of C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.c.set(RegisterOperations::change_bit(3, reg.c.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 99", "RES 3,C");
+}
+
+pub struct _0xCB9A {}
+impl Instruction for _0xCB9A {
+    // Resets bit 3 of D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.d.set(RegisterOperations::change_bit(3, reg.d.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 9A", "RES 3,D");
+}
+
+pub struct _0xCB9B {}
+impl Instruction for _0xCB9B {
+    // Resets bit 3 of E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.e.set(RegisterOperations::change_bit(3, reg.e.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 9B", "RES 3,E");
+}
+
+pub struct _0xCB9C {}
+impl Instruction for _0xCB9C {
+    // Resets bit 3 of H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.h.set(RegisterOperations::change_bit(3, reg.h.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 9C", "RES 3,H");
+}
+
+pub struct _0xCB9D {}
+impl Instruction for _0xCB9D {
+    // Resets bit 3 of L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.l.set(RegisterOperations::change_bit(3, reg.l.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 9D", "RES 3,L");
+}
+
+pub struct _0xCB9E {}
+impl Instruction for _0xCB9E {
+    // Resets bit 3 of the memory location pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, _| RegisterOperations::change_bit(3, value, false));
+        15
+    }
+
+    inst_metadata!(0, "CB 9E", "RES 3,(HL)");
+}
+
+pub struct _0xCB9F {}
+impl Instruction for _0xCB9F {
+    // Resets bit 3 of A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.a.set(RegisterOperations::change_bit(3, reg.a.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB 9F", "RES 3,A");
+}
+
+pub struct _0xCBA0 {}
+impl Instruction for _0xCBA0 {
+    // Resets bit 4 of B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.b.set(RegisterOperations::change_bit(4, reg.b.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB A0", "RES 4,B");
+}
+
+pub struct _0xCBA1 {}
+impl Instruction for _0xCBA1 {
+    // Resets bit 4 of C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.c.set(RegisterOperations::change_bit(4, reg.c.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB A1", "RES 4,C");
+}
+
+pub struct _0xCBA2 {}
+impl Instruction for _0xCBA2 {
+    // Resets bit 4 of D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.d.set(RegisterOperations::change_bit(4, reg.d.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB A2", "RES 4,D");
+}
+
+pub struct _0xCBA3 {}
+impl Instruction for _0xCBA3 {
+    // Resets bit 4 of E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.e.set(RegisterOperations::change_bit(4, reg.e.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB A3", "RES 4,E");
+}
+
+pub struct _0xCBA4 {}
+impl Instruction for _0xCBA4 {
+    // Resets bit 4 of H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.h.set(RegisterOperations::change_bit(4, reg.h.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB A4", "RES 4,H");
+}
+
+pub struct _0xCBA5 {}
+impl Instruction for _0xCBA5 {
+    // Resets bit 4 of L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.l.set(RegisterOperations::change_bit(4, reg.l.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB A5", "RES 4,L");
+}
+
+pub struct _0xCBA6 {}
+impl Instruction for _0xCBA6 {
+    // Resets bit 4 of the memory location pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, _| RegisterOperations::change_bit(4, value, false));
+        15
+    }
+
+    inst_metadata!(0, "CB A6", "RES 4,(HL)");
+}
+
+pub struct _0xCBA7 {}
+impl Instruction for _0xCBA7 {
+    // Resets bit 4 of A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.a.set(RegisterOperations::change_bit(4, reg.a.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB A7", "RES 4,A");
+}
+
+pub struct _0xCBA8 {}
+impl Instruction for _0xCBA8 {
+    // Resets bit 5 of B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.b.set(RegisterOperations::change_bit(5, reg.b.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB A8", "RES 5,B");
+}
+
+pub struct _0xCBA9 {}
+impl Instruction for _0xCBA9 {
+    // Resets bit 5 of C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.c.set(RegisterOperations::change_bit(5, reg.c.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB A9", "RES 5,C");
+}
+
+pub struct _0xCBAA {}
+impl Instruction for _0xCBAA {
+    // Resets bit 5 of D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.d.set(RegisterOperations::change_bit(5, reg.d.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB AA", "RES 5,D");
+}
+
+pub struct _0xCBAB {}
+impl Instruction for _0xCBAB {
+    // Resets bit 5 of E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.e.set(RegisterOperations::change_bit(5, reg.e.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB AB", "RES 5,E");
+}
+
+pub struct _0xCBAC {}
+impl Instruction for _0xCBAC {
+    // Resets bit 5 of H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.h.set(RegisterOperations::change_bit(5, reg.h.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB AC", "RES 5,H");
+}
+
+pub struct _0xCBAD {}
+impl Instruction for _0xCBAD {
+    // Resets bit 5 of L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.l.set(RegisterOperations::change_bit(5, reg.l.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB AD", "RES 5,L");
+}
+
+pub struct _0xCBAE {}
+impl Instruction for _0xCBAE {
+    // Resets bit 5 of the memory location pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, _| RegisterOperations::change_bit(5, value, false));
+        15
+    }
+
+    inst_metadata!(0, "CB AE", "RES 5,(HL)");
+}
+
+pub struct _0xCBAF {}
+impl Instruction for _0xCBAF {
+    // Resets bit 5 of A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.a.set(RegisterOperations::change_bit(5, reg.a.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB AF", "RES 5,A");
+}
+
+pub struct _0xCBB0 {}
+impl Instruction for _0xCBB0 {
+    // Resets bit 6 of B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.b.set(RegisterOperations::change_bit(6, reg.b.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB B0", "RES 6,B");
+}
+
+pub struct _0xCBB1 {}
+impl Instruction for _0xCBB1 {
+    // Resets bit 6 of C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.c.set(RegisterOperations::change_bit(6, reg.c.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB B1", "RES 6,C");
+}
+
+pub struct _0xCBB2 {}
+impl Instruction for _0xCBB2 {
+    // Resets bit 6 of D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.d.set(RegisterOperations::change_bit(6, reg.d.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB B2", "RES 6,D");
+}
+
+pub struct _0xCBB3 {}
+impl Instruction for _0xCBB3 {
+    // Resets bit 6 of E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.e.set(RegisterOperations::change_bit(6, reg.e.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB B3", "RES 6,E");
+}
+
+pub struct _0xCBB4 {}
+impl Instruction for _0xCBB4 {
+    // Resets bit 6 of H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.h.set(RegisterOperations::change_bit(6, reg.h.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB B4", "RES 6,H");
+}
+
+pub struct _0xCBB5 {}
+impl Instruction for _0xCBB5 {
+    // Resets bit 6 of L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.l.set(RegisterOperations::change_bit(6, reg.l.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB B5", "RES 6,L");
+}
+
+pub struct _0xCBB6 {}
+impl Instruction for _0xCBB6 {
+    // Resets bit 6 of the memory location pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, _| RegisterOperations::change_bit(6, value, false));
+        15
+    }
+
+    inst_metadata!(0, "CB B6", "RES 6,(HL)");
+}
+
+pub struct _0xCBB7 {}
+impl Instruction for _0xCBB7 {
+    // Resets bit 6 of A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.a.set(RegisterOperations::change_bit(6, reg.a.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB B7", "RES 6,A");
+}
+
+pub struct _0xCBB8 {}
+impl Instruction for _0xCBB8 {
+    // Resets bit 7 of B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.b.set(RegisterOperations::change_bit(7, reg.b.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB B8", "RES 7,B");
+}
+
+pub struct _0xCBB9 {}
+impl Instruction for _0xCBB9 {
+    // Resets bit 7 of C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.c.set(RegisterOperations::change_bit(7, reg.c.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB B9", "RES 7,C");
+}
+
+pub struct _0xCBBA {}
+impl Instruction for _0xCBBA {
+    // Resets bit 7 of D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.d.set(RegisterOperations::change_bit(7, reg.d.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB BA", "RES 7,D");
+}
+
+pub struct _0xCBBB {}
+impl Instruction for _0xCBBB {
+    // Resets bit 7 of E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.e.set(RegisterOperations::change_bit(7, reg.e.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB BB", "RES 7,E");
+}
+
+pub struct _0xCBBC {}
+impl Instruction for _0xCBBC {
+    // Resets bit 7 of H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.h.set(RegisterOperations::change_bit(7, reg.h.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB BC", "RES 7,H");
+}
+
+pub struct _0xCBBD {}
+impl Instruction for _0xCBBD {
+    // Resets bit 7 of L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.l.set(RegisterOperations::change_bit(7, reg.l.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB BD", "RES 7,L");
+}
+
+pub struct _0xCBBE {}
+impl Instruction for _0xCBBE {
+    // Resets bit 7 of the memory location pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, _| RegisterOperations::change_bit(7, value, false));
+        15
+    }
+
+    inst_metadata!(0, "CB BE", "RES 7,(HL)");
+}
+
+pub struct _0xCBBF {}
+impl Instruction for _0xCBBF {
+    // Resets bit 7 of A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.a.set(RegisterOperations::change_bit(7, reg.a.get(), false));
+        8
+    }
+
+    inst_metadata!(0, "CB BF", "RES 7,A");
+}
+
+pub struct _0xCBC0 {}
+impl Instruction for _0xCBC0 {
+    // Sets bit 0 of B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.b.set(RegisterOperations::change_bit(0, reg.b.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB C0", "SET 0,B");
+}
+
+pub struct _0xCBC1 {}
+impl Instruction for _0xCBC1 {
+    // Sets bit 0 of C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.c.set(RegisterOperations::change_bit(0, reg.c.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB C1", "SET 0,C");
+}
+
+pub struct _0xCBC2 {}
+impl Instruction for _0xCBC2 {
+    // Sets bit 0 of D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.d.set(RegisterOperations::change_bit(0, reg.d.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB C2", "SET 0,D");
+}
+
+pub struct _0xCBC3 {}
+impl Instruction for _0xCBC3 {
+    // Sets bit 0 of E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.e.set(RegisterOperations::change_bit(0, reg.e.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB C3", "SET 0,E");
+}
+
+pub struct _0xCBC4 {}
+impl Instruction for _0xCBC4 {
+    // Sets bit 0 of H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.h.set(RegisterOperations::change_bit(0, reg.h.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB C4", "SET 0,H");
+}
+
+pub struct _0xCBC5 {}
+impl Instruction for _0xCBC5 {
+    // Sets bit 0 of L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.l.set(RegisterOperations::change_bit(0, reg.l.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB C5", "SET 0,L");
+}
+
+pub struct _0xCBC6 {}
+impl Instruction for _0xCBC6 {
+    // Sets bit 0 of the memory location pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, _| RegisterOperations::change_bit(0, value, true));
+        15
+    }
+
+    inst_metadata!(0, "CB C6", "SET 0,(HL)");
+}
+
+pub struct _0xCBC7 {}
+impl Instruction for _0xCBC7 {
+    // Sets bit 0 of A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.a.set(RegisterOperations::change_bit(0, reg.a.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB C7", "SET 0,A");
+}
+
+pub struct _0xCBC8 {}
+impl Instruction for _0xCBC8 {
+    // Sets bit 1 of B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.b.set(RegisterOperations::change_bit(1, reg.b.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB C8", "SET 1,B");
+}
+
+pub struct _0xCBC9 {}
+impl Instruction for _0xCBC9 {
+    // Sets bit 1 of C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.c.set(RegisterOperations::change_bit(1, reg.c.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB C9", "SET 1,C");
+}
+
+pub struct _0xCBCA {}
+impl Instruction for _0xCBCA {
+    // Sets bit 1 of D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.d.set(RegisterOperations::change_bit(1, reg.d.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB CA", "SET 1,D");
+}
+
+pub struct _0xCBCB {}
+impl Instruction for _0xCBCB {
+    // Sets bit 1 of E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.e.set(RegisterOperations::change_bit(1, reg.e.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB CB", "SET 1,E");
+}
+
+pub struct _0xCBCC {}
+impl Instruction for _0xCBCC {
+    // Sets bit 1 of H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.h.set(RegisterOperations::change_bit(1, reg.h.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB CC", "SET 1,H");
+}
+
+pub struct _0xCBCD {}
+impl Instruction for _0xCBCD {
+    // Sets bit 1 of L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.l.set(RegisterOperations::change_bit(1, reg.l.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB CD", "SET 1,L");
+}
+
+pub struct _0xCBCE {}
+impl Instruction for _0xCBCE {
+    // Sets bit 1 of the memory location pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, _| RegisterOperations::change_bit(1, value, true));
+        15
+    }
+
+    inst_metadata!(0, "CB CE", "SET 1,(HL)");
+}
+
+pub struct _0xCBCF {}
+impl Instruction for _0xCBCF {
+    // Sets bit 1 of A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.a.set(RegisterOperations::change_bit(1, reg.a.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB CF", "SET 1,A");
+}
+
+pub struct _0xCBD0 {}
+impl Instruction for _0xCBD0 {
+    // Sets bit 2 of B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.b.set(RegisterOperations::change_bit(2, reg.b.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB D0", "SET 2,B");
+}
+
+pub struct _0xCBD1 {}
+impl Instruction for _0xCBD1 {
+    // Sets bit 2 of C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.c.set(RegisterOperations::change_bit(2, reg.c.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB D1", "SET 2,C");
+}
+
+pub struct _0xCBD2 {}
+impl Instruction for _0xCBD2 {
+    // Sets bit 2 of D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.d.set(RegisterOperations::change_bit(2, reg.d.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB D2", "SET 2,D");
+}
+
+pub struct _0xCBD3 {}
+impl Instruction for _0xCBD3 {
+    // Sets bit 2 of E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.e.set(RegisterOperations::change_bit(2, reg.e.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB D3", "SET 2,E");
+}
+
+pub struct _0xCBD4 {}
+impl Instruction for _0xCBD4 {
+    // Sets bit 2 of H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.h.set(RegisterOperations::change_bit(2, reg.h.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB D4", "SET 2,H");
+}
+
+pub struct _0xCBD5 {}
+impl Instruction for _0xCBD5 {
+    // Sets bit 2 of L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.l.set(RegisterOperations::change_bit(2, reg.l.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB D5", "SET 2,L");
+}
+
+pub struct _0xCBD6 {}
+impl Instruction for _0xCBD6 {
+    // Sets bit 2 of the memory location pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, _| RegisterOperations::change_bit(2, value, true));
+        15
+    }
+
+    inst_metadata!(0, "CB D6", "SET 2,(HL)");
+}
+
+pub struct _0xCBD7 {}
+impl Instruction for _0xCBD7 {
+    // Sets bit 2 of A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.a.set(RegisterOperations::change_bit(2, reg.a.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB D7", "SET 2,A");
+}
+
+pub struct _0xCBD8 {}
+impl Instruction for _0xCBD8 {
+    // Sets bit 3 of B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.b.set(RegisterOperations::change_bit(3, reg.b.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB D8", "SET 3,B");
+}
+
+pub struct _0xCBD9 {}
+impl Instruction for _0xCBD9 {
+    // Sets bit 3 of C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.c.set(RegisterOperations::change_bit(3, reg.c.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB D9", "SET 3,C");
+}
+
+pub struct _0xCBDA {}
+impl Instruction for _0xCBDA {
+    // Sets bit 3 of D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.d.set(RegisterOperations::change_bit(3, reg.d.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB DA", "SET 3,D");
+}
+
+pub struct _0xCBDB {}
+impl Instruction for _0xCBDB {
+    // Sets bit 3 of E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.e.set(RegisterOperations::change_bit(3, reg.e.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB DB", "SET 3,E");
+}
+
+pub struct _0xCBDC {}
+impl Instruction for _0xCBDC {
+    // Sets bit 3 of H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.h.set(RegisterOperations::change_bit(3, reg.h.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB DC", "SET 3,H");
+}
+
+pub struct _0xCBDD {}
+impl Instruction for _0xCBDD {
+    // Sets bit 3 of L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.l.set(RegisterOperations::change_bit(3, reg.l.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB DD", "SET 3,L");
+}
+
+pub struct _0xCBDE {}
+impl Instruction for _0xCBDE {
+    // Sets bit 3 of the memory location pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, _| RegisterOperations::change_bit(3, value, true));
+        15
+    }
+
+    inst_metadata!(0, "CB DE", "SET 3,(HL)");
+}
+
+pub struct _0xCBDF {}
+impl Instruction for _0xCBDF {
+    // Sets bit 3 of A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.a.set(RegisterOperations::change_bit(3, reg.a.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB DF", "SET 3,A");
+}
+
+pub struct _0xCBE0 {}
+impl Instruction for _0xCBE0 {
+    // Sets bit 4 of B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.b.set(RegisterOperations::change_bit(4, reg.b.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB E0", "SET 4,B");
+}
+
+pub struct _0xCBE1 {}
+impl Instruction for _0xCBE1 {
+    // Sets bit 4 of C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.c.set(RegisterOperations::change_bit(4, reg.c.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB E1", "SET 4,C");
+}
+
+pub struct _0xCBE2 {}
+impl Instruction for _0xCBE2 {
+    // Sets bit 4 of D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.d.set(RegisterOperations::change_bit(4, reg.d.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB E2", "SET 4,D");
+}
+
+pub struct _0xCBE3 {}
+impl Instruction for _0xCBE3 {
+    // Sets bit 4 of E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.e.set(RegisterOperations::change_bit(4, reg.e.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB E3", "SET 4,E");
+}
+
+pub struct _0xCBE4 {}
+impl Instruction for _0xCBE4 {
+    // Sets bit 4 of H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.h.set(RegisterOperations::change_bit(4, reg.h.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB E4", "SET 4,H");
+}
+
+pub struct _0xCBE5 {}
+impl Instruction for _0xCBE5 {
+    // Sets bit 4 of L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.l.set(RegisterOperations::change_bit(4, reg.l.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB E5", "SET 4,L");
+}
+
+pub struct _0xCBE6 {}
+impl Instruction for _0xCBE6 {
+    // Sets bit 4 of the memory location pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, _| RegisterOperations::change_bit(4, value, true));
+        15
+    }
+
+    inst_metadata!(0, "CB E6", "SET 4,(HL)");
+}
+
+pub struct _0xCBE7 {}
+impl Instruction for _0xCBE7 {
+    // Sets bit 4 of A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.a.set(RegisterOperations::change_bit(4, reg.a.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB E7", "SET 4,A");
+}
+
+pub struct _0xCBE8 {}
+impl Instruction for _0xCBE8 {
+    // Sets bit 5 of B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.b.set(RegisterOperations::change_bit(5, reg.b.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB E8", "SET 5,B");
+}
+
+pub struct _0xCBE9 {}
+impl Instruction for _0xCBE9 {
+    // Sets bit 5 of C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.c.set(RegisterOperations::change_bit(5, reg.c.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB E9", "SET 5,C");
+}
+
+pub struct _0xCBEA {}
+impl Instruction for _0xCBEA {
+    // Sets bit 5 of D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.d.set(RegisterOperations::change_bit(5, reg.d.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB EA", "SET 5,D");
+}
+
+pub struct _0xCBEB {}
+impl Instruction for _0xCBEB {
+    // Sets bit 5 of E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.e.set(RegisterOperations::change_bit(5, reg.e.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB EB", "SET 5,E");
+}
+
+pub struct _0xCBEC {}
+impl Instruction for _0xCBEC {
+    // Sets bit 5 of H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.h.set(RegisterOperations::change_bit(5, reg.h.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB EC", "SET 5,H");
+}
+
+pub struct _0xCBED {}
+impl Instruction for _0xCBED {
+    // Sets bit 5 of L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.l.set(RegisterOperations::change_bit(5, reg.l.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB ED", "SET 5,L");
+}
+
+pub struct _0xCBEE {}
+impl Instruction for _0xCBEE {
+    // Sets bit 5 of the memory location pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, _| RegisterOperations::change_bit(5, value, true));
+        15
+    }
+
+    inst_metadata!(0, "CB EE", "SET 5,(HL)");
+}
+
+pub struct _0xCBEF {}
+impl Instruction for _0xCBEF {
+    // Sets bit 5 of A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.a.set(RegisterOperations::change_bit(5, reg.a.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB EF", "SET 5,A");
+}
+
+pub struct _0xCBF0 {}
+impl Instruction for _0xCBF0 {
+    // Sets bit 6 of B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.b.set(RegisterOperations::change_bit(6, reg.b.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB F0", "SET 6,B");
+}
+
+pub struct _0xCBF1 {}
+impl Instruction for _0xCBF1 {
+    // Sets bit 6 of C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.c.set(RegisterOperations::change_bit(6, reg.c.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB F1", "SET 6,C");
+}
+
+pub struct _0xCBF2 {}
+impl Instruction for _0xCBF2 {
+    // Sets bit 6 of D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.d.set(RegisterOperations::change_bit(6, reg.d.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB F2", "SET 6,D");
+}
+
+pub struct _0xCBF3 {}
+impl Instruction for _0xCBF3 {
+    // Sets bit 6 of E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.e.set(RegisterOperations::change_bit(6, reg.e.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB F3", "SET 6,E");
+}
+
+pub struct _0xCBF4 {}
+impl Instruction for _0xCBF4 {
+    // Sets bit 6 of H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.h.set(RegisterOperations::change_bit(6, reg.h.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB F4", "SET 6,H");
+}
+
+pub struct _0xCBF5 {}
+impl Instruction for _0xCBF5 {
+    // Sets bit 6 of L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.l.set(RegisterOperations::change_bit(6, reg.l.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB F5", "SET 6,L");
+}
+
+pub struct _0xCBF6 {}
+impl Instruction for _0xCBF6 {
+    // Sets bit 6 of the memory location pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, _| RegisterOperations::change_bit(6, value, true));
+        15
+    }
+
+    inst_metadata!(0, "CB F6", "SET 6,(HL)");
+}
+
+pub struct _0xCBF7 {}
+impl Instruction for _0xCBF7 {
+    // Sets bit 6 of A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.a.set(RegisterOperations::change_bit(6, reg.a.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB F7", "SET 6,A");
+}
+
+pub struct _0xCBF8 {}
+impl Instruction for _0xCBF8 {
+    // Sets bit 7 of B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.b.set(RegisterOperations::change_bit(7, reg.b.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB F8", "SET 7,B");
+}
+
+pub struct _0xCBF9 {}
+impl Instruction for _0xCBF9 {
+    // Sets bit 7 of C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.c.set(RegisterOperations::change_bit(7, reg.c.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB F9", "SET 7,C");
+}
+
+pub struct _0xCBFA {}
+impl Instruction for _0xCBFA {
+    // Sets bit 7 of D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.d.set(RegisterOperations::change_bit(7, reg.d.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB FA", "SET 7,D");
+}
+
+pub struct _0xCBFB {}
+impl Instruction for _0xCBFB {
+    // Sets bit 7 of E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.e.set(RegisterOperations::change_bit(7, reg.e.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB FB", "SET 7,E");
+}
+
+pub struct _0xCBFC {}
+impl Instruction for _0xCBFC {
+    // Sets bit 7 of H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.h.set(RegisterOperations::change_bit(7, reg.h.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB FC", "SET 7,H");
+}
+
+pub struct _0xCBFD {}
+impl Instruction for _0xCBFD {
+    // Sets bit 7 of L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.l.set(RegisterOperations::change_bit(7, reg.l.get(), true));
+        8
+    }
+
+    inst_metadata!(0, "CB FD", "SET 7,L");
+}
+
+pub struct _0xCBFE {}
+impl Instruction for _0xCBFE {
+    // Sets bit 7 of the memory location pointed to by HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        RegisterOperations::read_modify_write(&mut components.mem, (&registers.h, &registers.l), &mut registers.f, |value, _| RegisterOperations::change_bit(7, value, true));
+        15
+    }
+
+    inst_metadata!(0, "CB FE", "SET 7,(HL)");
+}
+
+pub struct _0xCBFF {}
+impl Instruction for _0xCBFF {
+    // Sets bit 7 of A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let reg = &mut components.registers;
+        reg.a.set(RegisterOperations::change_bit(7, reg.a.get(), true));
         8
     }
 
-    inst_metadata!(1, "CB 38", "SRL B");
-}
\ No newline at end of file
+    inst_metadata!(0, "CB FF", "SET 7,A");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::instruction_set::{Instruction, Operands};
+    use crate::memory::{Memory, Registers, AddressBus, DataBus, Register, FlagValue};
+    use crate::runtime::RuntimeComponents;
+    use super::{_0xCB00, _0xCB08, _0xCB10, _0xCB18, _0xCB20, _0xCB28, _0xCB30, _0xCB38, _0xCB16, _0xCB26, _0xCB40, _0xCB78, _0xCB9F, _0xCBDF, _0xCBDE, _0xCB9E};
+
+    fn runtime_components() -> RuntimeComponents {
+        RuntimeComponents { mem: Memory::default(), registers: Registers::default(), address_bus: AddressBus { value: 0 }, data_bus: DataBus::default() }
+    }
+
+    #[test]
+    fn rotates_and_shifts() {
+        // (instruction, B before, carry before, B after, carry after)
+        let cases: [(&dyn Instruction, u8, FlagValue, u8, FlagValue); 10] = [
+            (&_0xCB00 {}, 0x81, FlagValue::Unset, 0x03, FlagValue::Set),   // RLC B
+            (&_0xCB08 {}, 0x01, FlagValue::Unset, 0x80, FlagValue::Set),   // RRC B
+            (&_0xCB10 {}, 0x81, FlagValue::Unset, 0x02, FlagValue::Set),   // RL B
+            (&_0xCB10 {}, 0x01, FlagValue::Set, 0x03, FlagValue::Unset),   // RL B, carry in
+            (&_0xCB18 {}, 0x01, FlagValue::Unset, 0x00, FlagValue::Set),   // RR B
+            (&_0xCB18 {}, 0x02, FlagValue::Set, 0x81, FlagValue::Unset),   // RR B, carry in
+            (&_0xCB20 {}, 0xC1, FlagValue::Set, 0x82, FlagValue::Set),     // SLA B
+            (&_0xCB28 {}, 0x81, FlagValue::Unset, 0xC0, FlagValue::Set),   // SRA B
+            (&_0xCB30 {}, 0x40, FlagValue::Unset, 0x81, FlagValue::Unset), // SLL B
+            (&_0xCB38 {}, 0x81, FlagValue::Set, 0x40, FlagValue::Set),     // SRL B
+        ];
+
+        for (instruction, before, carry_before, after, carry_after) in cases {
+            let mut components = runtime_components();
+            components.registers.b.set(before);
+            components.registers.f.set_carry(carry_before);
+            let cycles = instruction.execute(&mut components, Operands::None);
+            assert_eq!(cycles, 8, "{}", instruction.assembly());
+            assert_eq!(components.registers.b.get(), after, "{}", instruction.assembly());
+            assert!(components.registers.f.get_carry() == carry_after, "{}", instruction.assembly());
+        }
+    }
+
+    #[test]
+    fn shifts_set_flags_from_result() {
+        let mut components = runtime_components();
+        components.registers.b.set(0x01);
+        components.registers.f.set(0xFF);
+        _0xCB38 {}.execute(&mut components, Operands::None);
+        // Zero and parity set, sign, H and N cleared, carry from bit 0.
+        assert_eq!(components.registers.f.get() & 0xD7, 0x45);
+    }
+
+    #[test]
+    fn rl_hl_writes_back_to_memory() {
+        let mut components = runtime_components();
+        components.registers.h.set(0x40);
+        components.registers.l.set(0x00);
+        components.mem.locations[0x4000] = 0x80;
+        components.registers.f.set_carry(FlagValue::Set);
+
+        let cycles = _0xCB16 {}.execute(&mut components, Operands::None);
+
+        assert_eq!(cycles, 15);
+        assert_eq!(components.mem.read_u8(0x4000), 0x01);
+        assert!(components.registers.f.get_carry() == FlagValue::Set);
+    }
+
+    #[test]
+    fn sla_hl_writes_back_to_memory() {
+        let mut components = runtime_components();
+        components.registers.h.set(0x40);
+        components.registers.l.set(0x10);
+        components.mem.locations[0x4010] = 0x40;
+
+        _0xCB26 {}.execute(&mut components, Operands::None);
+
+        assert_eq!(components.mem.read_u8(0x4010), 0x80);
+        assert!(components.registers.f.get_sign() == FlagValue::Set);
+        assert!(components.registers.f.get_carry() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn bit_sets_zero_from_complement_of_bit() {
+        let mut components = runtime_components();
+        components.registers.b.set(0x80);
+        components.registers.f.set_carry(FlagValue::Set);
+
+        _0xCB78 {}.execute(&mut components, Operands::None); // BIT 7,B
+        assert!(components.registers.f.get_zero() == FlagValue::Unset);
+        assert!(components.registers.f.get_sign() == FlagValue::Set);
+
+        _0xCB40 {}.execute(&mut components, Operands::None); // BIT 0,B
+        assert!(components.registers.f.get_zero() == FlagValue::Set);
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Set);
+        assert!(components.registers.f.get_sign() == FlagValue::Unset);
+
+        assert!(components.registers.f.get_half_carry() == FlagValue::Set);
+        assert!(components.registers.f.get_add_subtract() == FlagValue::Unset);
+        assert!(components.registers.f.get_carry() == FlagValue::Set);
+        assert_eq!(components.registers.b.get(), 0x80);
+    }
+
+    #[test]
+    fn set_then_res_restores_value() {
+        let mut components = runtime_components();
+        components.registers.a.set(0x55);
+        components.registers.f.set(0xA5);
+
+        assert_eq!(_0xCBDF {}.execute(&mut components, Operands::None), 8); // SET 3,A
+        assert_eq!(components.registers.a.get(), 0x5D);
+        assert_eq!(_0xCB9F {}.execute(&mut components, Operands::None), 8); // RES 3,A
+        assert_eq!(components.registers.a.get(), 0x55);
+        assert_eq!(components.registers.f.get(), 0xA5);
+    }
+
+    #[test]
+    fn set_and_res_hl() {
+        let mut components = runtime_components();
+        components.registers.h.set(0x40);
+        components.registers.l.set(0x00);
+        components.mem.locations[0x4000] = 0x00;
+
+        assert_eq!(_0xCBDE {}.execute(&mut components, Operands::None), 15); // SET 3,(HL)
+        assert_eq!(components.mem.read_u8(0x4000), 0x08);
+        assert_eq!(_0xCB9E {}.execute(&mut components, Operands::None), 15); // RES 3,(HL)
+        assert_eq!(components.mem.read_u8(0x4000), 0x00);
+    }
+}
@@ -5,17 +5,121 @@ use log::error;
 use crate::{memory::{Memory, Registers, AddressBus, DataBus, Register, RegisterOperations}, utils::{self, combine_to_double_byte, split_double_byte}, runtime::{Runtime, RuntimeComponents}, inst_metadata};
 use super::{Instruction, Operands};
 
+pub struct _0xED40 {}
+impl Instruction for _0xED40 {
+    // A byte from port BC is written to b.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let port = combine_to_double_byte(registers.b.get(), registers.c.get());
+        let value = RegisterOperations::read_port(&mut components.data_bus, port, &mut registers.f);
+        registers.b.set(value);
+        12
+    }
+
+    inst_metadata!(0, "ED 40", "IN B,(C)");
+}
+
+pub struct _0xED41 {}
+impl Instruction for _0xED41 {
+    // The value of b is written to port BC.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let port = combine_to_double_byte(components.registers.b.get(), components.registers.c.get());
+        components.data_bus.write(port, components.registers.b.get());
+        12
+    }
+
+    inst_metadata!(0, "ED 41", "OUT (C),B");
+}
+
+pub struct _0xED42 {}
+impl Instruction for _0xED42 {
+    // The value of BC and the carry flag are subtracted from HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let value = combine_to_double_byte(registers.b.get(), registers.c.get());
+        RegisterOperations::sbc_value_from_register_pair((&mut registers.h, &mut registers.l), value, &mut registers.f);
+        15
+    }
+
+    inst_metadata!(0, "ED 42", "SBC HL,BC");
+}
+
+pub struct _0xED43 {}
+impl Instruction for _0xED43 {
+    // Stores BC into the memory location pointed to by nn.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Word(address) => {
+                RegisterOperations::ld_addr_from_value_with_register_pair(&mut components.mem, address, (&components.registers.b, &components.registers.c));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        20
+    }
+
+    inst_metadata!(2, "ED 43 *1 *2", "LD (*2*1),BC");
+}
+
+pub struct _0xED44 {}
+impl Instruction for _0xED44 {
+    // The contents of A are negated (two's complement).
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.a.neg(&mut components.registers.f);
+        8
+    }
+
+    inst_metadata!(0, "ED 44", "NEG");
+}
+
+pub struct _0xED45 {}
+impl Instruction for _0xED45 {
+    // Returns from a non-maskable interrupt: PC is popped from the stack and IFF2 is copied back into IFF1.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.pc.set(registers.sp.pop(&components.mem));
+        registers.iff1 = registers.iff2;
+        14
+    }
+
+    inst_metadata!(0, "ED 45", "RETN");
+}
+
 pub struct _0xED46 {}
 impl Instruction for _0xED46 {
     // Set interrupt mode 0
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         components.registers.interrupt_mode = 0;
-        10
+        8
     }
 
     inst_metadata!(0, "ED 46", "IM 0");
 }
 
+pub struct _0xED47 {}
+impl Instruction for _0xED47 {
+    // The contents of A are loaded into I.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.i.set(components.registers.a.get());
+        9
+    }
+
+    inst_metadata!(0, "ED 47", "LD I,A");
+}
+
+pub struct _0xED48 {}
+impl Instruction for _0xED48 {
+    // A byte from port BC is written to c.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let port = combine_to_double_byte(registers.b.get(), registers.c.get());
+        let value = RegisterOperations::read_port(&mut components.data_bus, port, &mut registers.f);
+        registers.c.set(value);
+        12
+    }
+
+    inst_metadata!(0, "ED 48", "IN C,(C)");
+}
+
 pub struct _0xED49 {}
 impl Instruction for _0xED49 {
     // The value of c or written to port bc
@@ -30,24 +134,182 @@ impl Instruction for _0xED49 {
     inst_metadata!(0, "ED 49", "OUT (C),C");
 }
 
+pub struct _0xED4A {}
+impl Instruction for _0xED4A {
+    // The value of BC and the carry flag are added to HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let value = combine_to_double_byte(registers.b.get(), registers.c.get());
+        RegisterOperations::adc_value_to_register_pair((&mut registers.h, &mut registers.l), value, &mut registers.f);
+        15
+    }
+
+    inst_metadata!(0, "ED 4A", "ADC HL,BC");
+}
+
+pub struct _0xED4B {}
+impl Instruction for _0xED4B {
+    // Loads the value pointed to by nn into BC.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Word(address) => {
+                RegisterOperations::ld_register_pair_from_addr(&components.mem, (&mut components.registers.b, &mut components.registers.c), address);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        20
+    }
+
+    inst_metadata!(2, "ED 4B *1 *2", "LD BC,(*2*1)");
+}
+
+pub struct _0xED4D {}
+impl Instruction for _0xED4D {
+    // Returns from a maskable interrupt: PC is popped from the stack.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.pc.set(registers.sp.pop(&components.mem));
+        14
+    }
+
+    inst_metadata!(0, "ED 4D", "RETI");
+}
+
+pub struct _0xED4F {}
+impl Instruction for _0xED4F {
+    // The contents of A are loaded into R.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.r.set(components.registers.a.get());
+        9
+    }
+
+    inst_metadata!(0, "ED 4F", "LD R,A");
+}
+
+pub struct _0xED50 {}
+impl Instruction for _0xED50 {
+    // A byte from port BC is written to d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let port = combine_to_double_byte(registers.b.get(), registers.c.get());
+        let value = RegisterOperations::read_port(&mut components.data_bus, port, &mut registers.f);
+        registers.d.set(value);
+        12
+    }
+
+    inst_metadata!(0, "ED 50", "IN D,(C)");
+}
+
+pub struct _0xED51 {}
+impl Instruction for _0xED51 {
+    // The value of d is written to port BC.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let port = combine_to_double_byte(components.registers.b.get(), components.registers.c.get());
+        components.data_bus.write(port, components.registers.d.get());
+        12
+    }
+
+    inst_metadata!(0, "ED 51", "OUT (C),D");
+}
+
+pub struct _0xED52 {}
+impl Instruction for _0xED52 {
+    // The value of DE and the carry flag are subtracted from HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let value = combine_to_double_byte(registers.d.get(), registers.e.get());
+        RegisterOperations::sbc_value_from_register_pair((&mut registers.h, &mut registers.l), value, &mut registers.f);
+        15
+    }
+
+    inst_metadata!(0, "ED 52", "SBC HL,DE");
+}
+
+pub struct _0xED53 {}
+impl Instruction for _0xED53 {
+    // Stores DE into the memory location pointed to by nn.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Word(address) => {
+                RegisterOperations::ld_addr_from_value_with_register_pair(&mut components.mem, address, (&components.registers.d, &components.registers.e));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        20
+    }
+
+    inst_metadata!(2, "ED 53 *1 *2", "LD (*2*1),DE");
+}
+
 pub struct _0xED56 {}
 impl Instruction for _0xED56 {
     // Set interrupt mode 1
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         components.registers.interrupt_mode = 1;
-        10
+        8
     }
 
     inst_metadata!(0, "ED 56", "IM 1");
 }
 
+pub struct _0xED57 {}
+impl Instruction for _0xED57 {
+    // The contents of I are loaded into A. P/V is set from IFF2.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.a.ld_interrupt_state(registers.i.get(), registers.iff2, &mut registers.f);
+        9
+    }
+
+    inst_metadata!(0, "ED 57", "LD A,I");
+}
+
+pub struct _0xED58 {}
+impl Instruction for _0xED58 {
+    // A byte from port BC is written to e.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let port = combine_to_double_byte(registers.b.get(), registers.c.get());
+        let value = RegisterOperations::read_port(&mut components.data_bus, port, &mut registers.f);
+        registers.e.set(value);
+        12
+    }
+
+    inst_metadata!(0, "ED 58", "IN E,(C)");
+}
+
+pub struct _0xED59 {}
+impl Instruction for _0xED59 {
+    // The value of e is written to port BC.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let port = combine_to_double_byte(components.registers.b.get(), components.registers.c.get());
+        components.data_bus.write(port, components.registers.e.get());
+        12
+    }
+
+    inst_metadata!(0, "ED 59", "OUT (C),E");
+}
+
+pub struct _0xED5A {}
+impl Instruction for _0xED5A {
+    // The value of DE and the carry flag are added to HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let value = combine_to_double_byte(registers.d.get(), registers.e.get());
+        RegisterOperations::adc_value_to_register_pair((&mut registers.h, &mut registers.l), value, &mut registers.f);
+        15
+    }
+
+    inst_metadata!(0, "ED 5A", "ADC HL,DE");
+}
+
 pub struct _0xED5B {}
 impl Instruction for _0xED5B {
     // Loads the value pointed to by nn into DE.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
         match operands {
-            Operands::Two(op1, op2) => {
-                RegisterOperations::ld_register_pair_from_addr(&components.mem, (&mut components.registers.d, &mut components.registers.e), combine_to_double_byte(op2, op1));
+            Operands::Word(address) => {
+                RegisterOperations::ld_register_pair_from_addr(&components.mem, (&mut components.registers.d, &mut components.registers.e), address);
             }
             _ => error!("Wrong operands used for {}", self.assembly()),
         }
@@ -58,14 +320,229 @@ impl Instruction for _0xED5B {
 }
 
 
+pub struct _0xED5E {}
+impl Instruction for _0xED5E {
+    // Set interrupt mode 2
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        components.registers.interrupt_mode = 2;
+        8
+    }
+
+    inst_metadata!(0, "ED 5E", "IM 2");
+}
+
+pub struct _0xED5F {}
+impl Instruction for _0xED5F {
+    // The contents of R are loaded into A. P/V is set from IFF2.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        registers.a.ld_interrupt_state(registers.r.get(), registers.iff2, &mut registers.f);
+        9
+    }
+
+    inst_metadata!(0, "ED 5F", "LD A,R");
+}
+
+pub struct _0xED60 {}
+impl Instruction for _0xED60 {
+    // A byte from port BC is written to h.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let port = combine_to_double_byte(registers.b.get(), registers.c.get());
+        let value = RegisterOperations::read_port(&mut components.data_bus, port, &mut registers.f);
+        registers.h.set(value);
+        12
+    }
+
+    inst_metadata!(0, "ED 60", "IN H,(C)");
+}
+
+pub struct _0xED61 {}
+impl Instruction for _0xED61 {
+    // The value of h is written to port BC.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let port = combine_to_double_byte(components.registers.b.get(), components.registers.c.get());
+        components.data_bus.write(port, components.registers.h.get());
+        12
+    }
+
+    inst_metadata!(0, "ED 61", "OUT (C),H");
+}
+
+pub struct _0xED62 {}
+impl Instruction for _0xED62 {
+    // The value of HL and the carry flag are subtracted from HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let value = combine_to_double_byte(registers.h.get(), registers.l.get());
+        RegisterOperations::sbc_value_from_register_pair((&mut registers.h, &mut registers.l), value, &mut registers.f);
+        15
+    }
+
+    inst_metadata!(0, "ED 62", "SBC HL,HL");
+}
+
+pub struct _0xED63 {}
+impl Instruction for _0xED63 {
+    // Stores HL into the memory location pointed to by nn.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Word(address) => {
+                RegisterOperations::ld_addr_from_value_with_register_pair(&mut components.mem, address, (&components.registers.h, &components.registers.l));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        20
+    }
+
+    inst_metadata!(2, "ED 63 *1 *2", "LD (*2*1),HL");
+}
+
+pub struct _0xED67 {}
+impl Instruction for _0xED67 {
+    // The low nibble of (HL) is copied to the low nibble of A, the previous low nibble of A to the
+    // high nibble of (HL), and the previous high nibble of (HL) to its low nibble.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let addr = combine_to_double_byte(registers.h.get(), registers.l.get());
+        registers.a.rrd(&mut components.mem, addr, &mut registers.f);
+        18
+    }
+
+    inst_metadata!(0, "ED 67", "RRD");
+}
+
+pub struct _0xED68 {}
+impl Instruction for _0xED68 {
+    // A byte from port BC is written to l.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let port = combine_to_double_byte(registers.b.get(), registers.c.get());
+        let value = RegisterOperations::read_port(&mut components.data_bus, port, &mut registers.f);
+        registers.l.set(value);
+        12
+    }
+
+    inst_metadata!(0, "ED 68", "IN L,(C)");
+}
+
+pub struct _0xED69 {}
+impl Instruction for _0xED69 {
+    // The value of l is written to port BC.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let port = combine_to_double_byte(components.registers.b.get(), components.registers.c.get());
+        components.data_bus.write(port, components.registers.l.get());
+        12
+    }
+
+    inst_metadata!(0, "ED 69", "OUT (C),L");
+}
+
+pub struct _0xED6A {}
+impl Instruction for _0xED6A {
+    // The value of HL and the carry flag are added to HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let value = combine_to_double_byte(registers.h.get(), registers.l.get());
+        RegisterOperations::adc_value_to_register_pair((&mut registers.h, &mut registers.l), value, &mut registers.f);
+        15
+    }
+
+    inst_metadata!(0, "ED 6A", "ADC HL,HL");
+}
+
+pub struct _0xED6B {}
+impl Instruction for _0xED6B {
+    // Loads the value pointed to by nn into HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Word(address) => {
+                RegisterOperations::ld_register_pair_from_addr(&components.mem, (&mut components.registers.h, &mut components.registers.l), address);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        20
+    }
+
+    inst_metadata!(2, "ED 6B *1 *2", "LD HL,(*2*1)");
+}
+
+pub struct _0xED6F {}
+impl Instruction for _0xED6F {
+    // The high nibble of (HL) is copied to the low nibble of A, the previous low nibble of (HL) to
+    // its high nibble, and the previous low nibble of A to the low nibble of (HL).
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let addr = combine_to_double_byte(registers.h.get(), registers.l.get());
+        registers.a.rld(&mut components.mem, addr, &mut registers.f);
+        18
+    }
+
+    inst_metadata!(0, "ED 6F", "RLD");
+}
+
+pub struct _0xED70 {}
+impl Instruction for _0xED70 {
+    // A byte is read from port BC and only used to set the flags.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let port = combine_to_double_byte(registers.b.get(), registers.c.get());
+        RegisterOperations::read_port(&mut components.data_bus, port, &mut registers.f);
+        12
+    }
+
+    inst_metadata!(0, "ED 70", "IN (C)");
+}
+
+pub struct _0xED71 {}
+impl Instruction for _0xED71 {
+    // 0 is written to port BC.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let port = combine_to_double_byte(components.registers.b.get(), components.registers.c.get());
+        components.data_bus.write(port, 0);
+        12
+    }
+
+    inst_metadata!(0, "ED 71", "OUT (C),0");
+}
+
+pub struct _0xED72 {}
+impl Instruction for _0xED72 {
+    // The value of SP and the carry flag are subtracted from HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let value = registers.sp.get() as u16;
+        RegisterOperations::sbc_value_from_register_pair((&mut registers.h, &mut registers.l), value, &mut registers.f);
+        15
+    }
+
+    inst_metadata!(0, "ED 72", "SBC HL,SP");
+}
+
+pub struct _0xED73 {}
+impl Instruction for _0xED73 {
+    // Stores SP into the memory location pointed to by nn.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Word(address) => {
+                RegisterOperations::ld_addr_with_word(&mut components.mem, address, components.registers.sp.get() as u16);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        20
+    }
+
+    inst_metadata!(2, "ED 73 *1 *2", "LD (*2*1),SP");
+}
+
 pub struct _0xED78 {}
 impl Instruction for _0xED78 {
-    // A byte from port bc is written to a
+    // A byte from port BC is written to a.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        let addr_low_and_val = components.registers.c.get(); 
-        let b_val = components.registers.b.get();
-        let port = utils::combine_to_double_byte(b_val, addr_low_and_val);
-        components.registers.a.set(components.data_bus.read(port));
+        let registers = &mut components.registers;
+        let port = combine_to_double_byte(registers.b.get(), registers.c.get());
+        let value = RegisterOperations::read_port(&mut components.data_bus, port, &mut registers.f);
+        registers.a.set(value);
         12
     }
 
@@ -89,6 +566,131 @@ impl Instruction for _0xED79 {
 
 
 
+pub struct _0xED7A {}
+impl Instruction for _0xED7A {
+    // The value of SP and the carry flag are added to HL.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        let registers = &mut components.registers;
+        let value = registers.sp.get() as u16;
+        RegisterOperations::adc_value_to_register_pair((&mut registers.h, &mut registers.l), value, &mut registers.f);
+        15
+    }
+
+    inst_metadata!(0, "ED 7A", "ADC HL,SP");
+}
+
+pub struct _0xED7B {}
+impl Instruction for _0xED7B {
+    // Loads the value pointed to by nn into SP.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Word(address) => {
+                components.registers.sp.set(components.mem.read_u16(address) as usize);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        20
+    }
+
+    inst_metadata!(2, "ED 7B *1 *2", "LD SP,(*2*1)");
+}
+
+pub struct _0xEDA0 {}
+impl Instruction for _0xEDA0 {
+    // Transfers a byte of data from the memory location pointed to by HL to the memory location pointed to by DE. 
+    // Then HL and DE are incremented and BC is decremented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::block_load(&mut components.mem, &mut components.registers, true);
+        16
+    }
+
+    inst_metadata!(0, "ED A0", "LDI");
+}
+
+pub struct _0xEDA1 {}
+impl Instruction for _0xEDA1 {
+    // Compares A with the memory location pointed to by HL, setting the flags like CP except for carry.
+    // Then HL is incremented and BC is decremented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::block_compare(&components.mem, &mut components.registers, true);
+        16
+    }
+
+    inst_metadata!(0, "ED A1", "CPI");
+}
+
+pub struct _0xEDA2 {}
+impl Instruction for _0xEDA2 {
+    // A byte from port BC is written to the memory location pointed to by HL.
+    // Then HL is incremented and B is decremented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::block_in(&mut components.mem, &mut components.registers, &mut components.data_bus, true);
+        16
+    }
+
+    inst_metadata!(0, "ED A2", "INI");
+}
+
+pub struct _0xEDA3 {}
+impl Instruction for _0xEDA3 {
+    // B is decremented, then the byte at the memory location pointed to by HL is written to port BC.
+    // Then HL is incremented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::block_out(&components.mem, &mut components.registers, &mut components.data_bus, true);
+        16
+    }
+
+    inst_metadata!(0, "ED A3", "OUTI");
+}
+
+pub struct _0xEDA8 {}
+impl Instruction for _0xEDA8 {
+    // Transfers a byte of data from the memory location pointed to by HL to the memory location pointed to by DE. 
+    // Then HL, DE and BC are decremented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::block_load(&mut components.mem, &mut components.registers, false);
+        16
+    }
+
+    inst_metadata!(0, "ED A8", "LDD");
+}
+
+pub struct _0xEDA9 {}
+impl Instruction for _0xEDA9 {
+    // Compares A with the memory location pointed to by HL, setting the flags like CP except for carry.
+    // Then HL and BC are decremented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::block_compare(&components.mem, &mut components.registers, false);
+        16
+    }
+
+    inst_metadata!(0, "ED A9", "CPD");
+}
+
+pub struct _0xEDAA {}
+impl Instruction for _0xEDAA {
+    // A byte from port BC is written to the memory location pointed to by HL.
+    // Then HL and B are decremented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::block_in(&mut components.mem, &mut components.registers, &mut components.data_bus, false);
+        16
+    }
+
+    inst_metadata!(0, "ED AA", "IND");
+}
+
+pub struct _0xEDAB {}
+impl Instruction for _0xEDAB {
+    // B is decremented, then the byte at the memory location pointed to by HL is written to port BC.
+    // Then HL is decremented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        RegisterOperations::block_out(&components.mem, &mut components.registers, &mut components.data_bus, false);
+        16
+    }
+
+    inst_metadata!(0, "ED AB", "OUTD");
+}
+
 pub struct _0xEDB0 {}
 impl Instruction for _0xEDB0 {
     // Transfers a byte of data from the memory location pointed to by HL to the memory location pointed to by DE. 
@@ -96,24 +698,539 @@ impl Instruction for _0xEDB0 {
     // If BC is not zero, this operation is repeated. 
     // Interrupts can trigger while this instruction is processing.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        let mut repeats: u16 = 0;
-        loop {
-            let source_addr = combine_to_double_byte(components.registers.h.get(), components.registers.l.get());
-            let target_addr = combine_to_double_byte(components.registers.d.get(), components.registers.e.get());
-            components.mem.locations[target_addr as usize] = components.mem.locations[source_addr as usize];
-            let mut bc = combine_to_double_byte(components.registers.b.get(), components.registers.c.get());
-            bc -= 1;
-            let (b, c) = split_double_byte(bc);
-            components.registers.b.set(b);
-            components.registers.c.set(c);
-            if bc == 0  { break; }
-            repeats += 1;
-        } 
+        // One byte per execution: while BC is non-zero PC is wound back so this runs again.
+        if RegisterOperations::block_load(&mut components.mem, &mut components.registers, true) != 0 {
+            components.registers.pc.set(components.registers.pc.get().wrapping_sub(2));
+            return 21;
+        }
+        16
+    }
 
-        16 + (repeats * 21)
+    inst_metadata!(0, "ED B0", "LDIR");
+}
+
+pub struct _0xEDB1 {}
+impl Instruction for _0xEDB1 {
+    // Compares A with the memory location pointed to by HL, setting the flags like CP except for carry.
+    // Then HL is incremented and BC is decremented.
+    // This is repeated until a match is found or BC is zero.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        // One byte per execution: until there's a match or BC runs out PC is wound back so this runs again.
+        if RegisterOperations::block_compare(&components.mem, &mut components.registers, true) {
+            components.registers.pc.set(components.registers.pc.get().wrapping_sub(2));
+            return 21;
+        }
+        16
     }
 
-    inst_metadata!(0, "BED 0", "LDIR");
+    inst_metadata!(0, "ED B1", "CPIR");
 }
 
+pub struct _0xEDB2 {}
+impl Instruction for _0xEDB2 {
+    // A byte from port BC is written to the memory location pointed to by HL.
+    // Then HL is incremented and B is decremented.
+    // This is repeated until B is zero.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        // One byte per execution: while B is non-zero PC is wound back so this runs again.
+        if RegisterOperations::block_in(&mut components.mem, &mut components.registers, &mut components.data_bus, true) != 0 {
+            components.registers.pc.set(components.registers.pc.get().wrapping_sub(2));
+            return 21;
+        }
+        16
+    }
 
+    inst_metadata!(0, "ED B2", "INIR");
+}
+
+pub struct _0xEDB3 {}
+impl Instruction for _0xEDB3 {
+    // B is decremented, then the byte at the memory location pointed to by HL is written to port BC.
+    // Then HL is incremented.
+    // This is repeated until B is zero.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        // One byte per execution: while B is non-zero PC is wound back so this runs again.
+        if RegisterOperations::block_out(&components.mem, &mut components.registers, &mut components.data_bus, true) != 0 {
+            components.registers.pc.set(components.registers.pc.get().wrapping_sub(2));
+            return 21;
+        }
+        16
+    }
+
+    inst_metadata!(0, "ED B3", "OTIR");
+}
+
+pub struct _0xEDB8 {}
+impl Instruction for _0xEDB8 {
+    // Transfers a byte of data from the memory location pointed to by HL to the memory location pointed to by DE. 
+    // Then HL, DE and BC are decremented. 
+    // If BC is not zero, this operation is repeated. 
+    // Interrupts can trigger while this instruction is processing.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        // One byte per execution: while BC is non-zero PC is wound back so this runs again.
+        if RegisterOperations::block_load(&mut components.mem, &mut components.registers, false) != 0 {
+            components.registers.pc.set(components.registers.pc.get().wrapping_sub(2));
+            return 21;
+        }
+        16
+    }
+
+    inst_metadata!(0, "ED B8", "LDDR");
+}
+
+pub struct _0xEDB9 {}
+impl Instruction for _0xEDB9 {
+    // Compares A with the memory location pointed to by HL, setting the flags like CP except for carry.
+    // Then HL and BC are decremented.
+    // This is repeated until a match is found or BC is zero.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        // One byte per execution: until there's a match or BC runs out PC is wound back so this runs again.
+        if RegisterOperations::block_compare(&components.mem, &mut components.registers, false) {
+            components.registers.pc.set(components.registers.pc.get().wrapping_sub(2));
+            return 21;
+        }
+        16
+    }
+
+    inst_metadata!(0, "ED B9", "CPDR");
+}
+
+pub struct _0xEDBA {}
+impl Instruction for _0xEDBA {
+    // A byte from port BC is written to the memory location pointed to by HL.
+    // Then HL and B are decremented.
+    // This is repeated until B is zero.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        // One byte per execution: while B is non-zero PC is wound back so this runs again.
+        if RegisterOperations::block_in(&mut components.mem, &mut components.registers, &mut components.data_bus, false) != 0 {
+            components.registers.pc.set(components.registers.pc.get().wrapping_sub(2));
+            return 21;
+        }
+        16
+    }
+
+    inst_metadata!(0, "ED BA", "INDR");
+}
+
+pub struct _0xEDBB {}
+impl Instruction for _0xEDBB {
+    // B is decremented, then the byte at the memory location pointed to by HL is written to port BC.
+    // Then HL is decremented.
+    // This is repeated until B is zero.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        // One byte per execution: while B is non-zero PC is wound back so this runs again.
+        if RegisterOperations::block_out(&components.mem, &mut components.registers, &mut components.data_bus, false) != 0 {
+            components.registers.pc.set(components.registers.pc.get().wrapping_sub(2));
+            return 21;
+        }
+        16
+    }
+
+    inst_metadata!(0, "ED BB", "OTDR");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::instruction_set::{Instruction, Operands};
+    use crate::memory::{Memory, Registers, AddressBus, DataBus, Register, FlagValue, RegisterOperations};
+    use crate::runtime::RuntimeComponents;
+    use super::{_0xED47, _0xED4F, _0xED57, _0xED5F, _0xEDA0, _0xEDA8, _0xEDB0, _0xEDB8, _0xEDB1, _0xEDB9, _0xEDA2, _0xEDB2, _0xEDB3, _0xED5A, _0xED62, _0xED43, _0xED5B, _0xED73, _0xED7B, _0xED44, _0xED45, _0xED4D, _0xED5E, _0xED67, _0xED6F, _0xED58, _0xED70, _0xED71};
+
+    fn runtime_components() -> RuntimeComponents {
+        RuntimeComponents { mem: Memory::default(), registers: Registers::default(), address_bus: AddressBus { value: 0 }, data_bus: DataBus::default() }
+    }
+
+    fn set_pairs(components: &mut RuntimeComponents, hl: u16, de: u16, bc: u16) {
+        let registers = &mut components.registers;
+        RegisterOperations::ld_register_pair_with_value((&mut registers.h, &mut registers.l), hl);
+        RegisterOperations::ld_register_pair_with_value((&mut registers.d, &mut registers.e), de);
+        RegisterOperations::ld_register_pair_with_value((&mut registers.b, &mut registers.c), bc);
+    }
+
+    // Runs a repeating block instruction at #0000 until it stops winding PC back onto itself,
+    // returning the total cycles.
+    fn run_to_completion(instruction: &dyn Instruction, components: &mut RuntimeComponents) -> u32 {
+        let mut cycles = 0;
+        loop {
+            components.registers.pc.set(0x0002);
+            cycles += instruction.execute(components, Operands::None) as u32;
+            if components.registers.pc.get() != 0x0000 {
+                return cycles;
+            }
+        }
+    }
+
+    #[test]
+    fn ldi_and_ldd_move_one_byte() {
+        let mut components = runtime_components();
+        components.mem.locations[0x4000] = 0xAA;
+        components.mem.locations[0x4001] = 0xBB;
+        set_pairs(&mut components, 0x4000, 0x5000, 0x0002);
+
+        _0xEDA0 {}.execute(&mut components, Operands::None);
+        assert_eq!(components.mem.read_u8(0x5000), 0xAA);
+        assert_eq!(components.registers.l.get(), 0x01);
+        assert_eq!(components.registers.e.get(), 0x01);
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Set);
+
+        set_pairs(&mut components, 0x4001, 0x5001, 0x0001);
+        _0xEDA8 {}.execute(&mut components, Operands::None);
+        assert_eq!(components.mem.read_u8(0x5001), 0xBB);
+        assert_eq!(components.registers.l.get(), 0x00);
+        assert_eq!(components.registers.e.get(), 0x00);
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn ldir_copies_forward() {
+        let mut components = runtime_components();
+        let source = [0x10, 0x20, 0x30, 0x40];
+        components.mem.locations[0x4000..0x4004].copy_from_slice(&source);
+        set_pairs(&mut components, 0x4000, 0x5000, 0x0004);
+
+        components.registers.pc.set(0x0002);
+        assert_eq!(_0xEDB0 {}.execute(&mut components, Operands::None), 21);
+        assert_eq!(components.registers.pc.get(), 0x0000);
+        let cycles = 21 + run_to_completion(&_0xEDB0 {}, &mut components);
+
+        assert_eq!(cycles, 16 + 3 * 21);
+        assert_eq!(components.mem.locations[0x5000..0x5004], source);
+        assert_eq!((components.registers.h.get(), components.registers.l.get()), (0x40, 0x04));
+        assert_eq!((components.registers.d.get(), components.registers.e.get()), (0x50, 0x04));
+        assert_eq!((components.registers.b.get(), components.registers.c.get()), (0x00, 0x00));
+    }
+
+    #[test]
+    fn lddr_copies_backward() {
+        let mut components = runtime_components();
+        let source = [0x10, 0x20, 0x30, 0x40];
+        components.mem.locations[0x4000..0x4004].copy_from_slice(&source);
+        set_pairs(&mut components, 0x4003, 0x5003, 0x0004);
+
+        let cycles = run_to_completion(&_0xEDB8 {}, &mut components);
+
+        assert_eq!(cycles, 16 + 3 * 21);
+        assert_eq!(components.mem.locations[0x5000..0x5004], source);
+        assert_eq!((components.registers.h.get(), components.registers.l.get()), (0x3F, 0xFF));
+        assert_eq!((components.registers.d.get(), components.registers.e.get()), (0x4F, 0xFF));
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn ldir_copies_16k() {
+        let mut components = runtime_components();
+        components.mem.locations[0x4000..0x8000].fill(0x5A);
+        set_pairs(&mut components, 0x4000, 0x8000, 0x4000);
+
+        let cycles = run_to_completion(&_0xEDB0 {}, &mut components);
+
+        assert_eq!(cycles, 16 + 0x3FFF * 21);
+        assert!(components.mem.locations[0x8000..0xC000].iter().all(|byte| *byte == 0x5A));
+    }
+
+    #[test]
+    fn cpir_stops_after_match() {
+        let mut components = runtime_components();
+        components.mem.locations[0x4000..0x4005].copy_from_slice(&[0x01, 0x02, 0x03, 0x04, 0x05]);
+        components.registers.a.set(0x03);
+        components.registers.f.set_carry(FlagValue::Set);
+        set_pairs(&mut components, 0x4000, 0x0000, 0x0005);
+
+        let cycles = run_to_completion(&_0xEDB1 {}, &mut components);
+
+        assert_eq!(cycles, 16 + 2 * 21);
+        assert_eq!((components.registers.h.get(), components.registers.l.get()), (0x40, 0x03));
+        assert_eq!((components.registers.b.get(), components.registers.c.get()), (0x00, 0x02));
+        assert!(components.registers.f.get_zero() == FlagValue::Set);
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Set);
+        assert!(components.registers.f.get_add_subtract() == FlagValue::Set);
+        assert!(components.registers.f.get_carry() == FlagValue::Set);
+    }
+
+    #[test]
+    fn cpdr_stops_when_bc_runs_out() {
+        let mut components = runtime_components();
+        components.mem.locations[0x4000..0x4003].copy_from_slice(&[0x01, 0x02, 0x03]);
+        components.registers.a.set(0x09);
+        set_pairs(&mut components, 0x4002, 0x0000, 0x0003);
+
+        let cycles = run_to_completion(&_0xEDB9 {}, &mut components);
+
+        assert_eq!(cycles, 16 + 2 * 21);
+        assert_eq!((components.registers.h.get(), components.registers.l.get()), (0x3F, 0xFF));
+        assert!(components.registers.f.get_zero() == FlagValue::Unset);
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn cpir_searches_a_large_block_without_a_match() {
+        let mut components = runtime_components();
+        components.mem.locations[0x4000..0x8000].fill(0x00);
+        components.registers.a.set(0xFF);
+        set_pairs(&mut components, 0x4000, 0x0000, 0x4000);
+
+        let cycles = run_to_completion(&_0xEDB1 {}, &mut components);
+
+        assert_eq!(cycles, 16 + 0x3FFF * 21);
+        assert_eq!((components.registers.h.get(), components.registers.l.get()), (0x80, 0x00));
+        assert_eq!((components.registers.b.get(), components.registers.c.get()), (0x00, 0x00));
+        assert!(components.registers.f.get_zero() == FlagValue::Unset);
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn ini_reads_port_into_memory() {
+        let mut components = runtime_components();
+        set_pairs(&mut components, 0x4000, 0x0000, 0x02F5);
+
+        let cycles = _0xEDA2 {}.execute(&mut components, Operands::None);
+
+        assert_eq!(cycles, 16);
+        assert_eq!(components.mem.read_u8(0x4000), components.data_bus.read(0x02F5));
+        assert_eq!((components.registers.h.get(), components.registers.l.get()), (0x40, 0x01));
+        assert_eq!(components.registers.b.get(), 0x01);
+        assert!(components.registers.f.get_zero() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn inir_fills_buffer_until_b_is_zero() {
+        let mut components = runtime_components();
+        set_pairs(&mut components, 0x4000, 0x0000, 0x04F5);
+
+        let cycles = run_to_completion(&_0xEDB2 {}, &mut components);
+
+        assert_eq!(cycles, 16 + 3 * 21);
+        let expected = components.data_bus.read(0x00F5);
+        assert!(components.mem.locations[0x4000..0x4004].iter().all(|byte| *byte == expected));
+        assert_eq!(components.mem.read_u8(0x4004), 0x01);
+        assert_eq!(components.registers.b.get(), 0x00);
+        assert!(components.registers.f.get_zero() == FlagValue::Set);
+    }
+
+    #[test]
+    fn otir_walks_buffer_until_b_is_zero() {
+        let mut components = runtime_components();
+        components.mem.locations[0x4000..0x4003].copy_from_slice(&[0x80, 0x02, 0x03]);
+        set_pairs(&mut components, 0x4000, 0x0000, 0x037F);
+
+        let cycles = run_to_completion(&_0xEDB3 {}, &mut components);
+
+        assert_eq!(cycles, 16 + 2 * 21);
+        assert_eq!((components.registers.h.get(), components.registers.l.get()), (0x40, 0x03));
+        assert_eq!(components.registers.b.get(), 0x00);
+        assert!(components.registers.f.get_zero() == FlagValue::Set);
+        assert!(components.registers.f.get_add_subtract() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn adc_hl_de_adds_carry() {
+        let mut components = runtime_components();
+        set_pairs(&mut components, 0x7FFF, 0x0000, 0x0000);
+        components.registers.f.set_carry(FlagValue::Set);
+
+        let cycles = _0xED5A {}.execute(&mut components, Operands::None);
+
+        assert_eq!(cycles, 15);
+        assert_eq!((components.registers.h.get(), components.registers.l.get()), (0x80, 0x00));
+        assert!(components.registers.f.get_carry() == FlagValue::Unset);
+        assert!(components.registers.f.get_half_carry() == FlagValue::Set);
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Set);
+        assert!(components.registers.f.get_sign() == FlagValue::Set);
+        assert!(components.registers.f.get_add_subtract() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn sbc_hl_hl_gives_minus_carry() {
+        let mut components = runtime_components();
+        set_pairs(&mut components, 0x1234, 0x0000, 0x0000);
+        components.registers.f.set_carry(FlagValue::Unset);
+        _0xED62 {}.execute(&mut components, Operands::None);
+        assert_eq!((components.registers.h.get(), components.registers.l.get()), (0x00, 0x00));
+        assert!(components.registers.f.get_zero() == FlagValue::Set);
+        assert!(components.registers.f.get_carry() == FlagValue::Unset);
+        assert!(components.registers.f.get_add_subtract() == FlagValue::Set);
+
+        set_pairs(&mut components, 0x1234, 0x0000, 0x0000);
+        components.registers.f.set_carry(FlagValue::Set);
+        _0xED62 {}.execute(&mut components, Operands::None);
+        assert_eq!((components.registers.h.get(), components.registers.l.get()), (0xFF, 0xFF));
+        assert!(components.registers.f.get_zero() == FlagValue::Unset);
+        assert!(components.registers.f.get_carry() == FlagValue::Set);
+        assert!(components.registers.f.get_sign() == FlagValue::Set);
+    }
+
+    #[test]
+    fn store_bc_and_load_into_de() {
+        let mut components = runtime_components();
+        set_pairs(&mut components, 0x0000, 0x0000, 0x1234);
+
+        assert_eq!(_0xED43 {}.execute(&mut components, Operands::Word(0x4000)), 20);
+        assert_eq!(components.mem.read_u8(0x4000), 0x34);
+        assert_eq!(components.mem.read_u8(0x4001), 0x12);
+
+        assert_eq!(_0xED5B {}.execute(&mut components, Operands::Word(0x4000)), 20);
+        assert_eq!((components.registers.d.get(), components.registers.e.get()), (0x12, 0x34));
+    }
+
+    #[test]
+    fn store_and_load_sp() {
+        let mut components = runtime_components();
+        components.registers.sp.set(0xBFFE);
+        _0xED73 {}.execute(&mut components, Operands::Word(0x4000));
+        components.registers.sp.set(0x0000);
+        _0xED7B {}.execute(&mut components, Operands::Word(0x4000));
+        assert_eq!(components.registers.sp.get(), 0xBFFE);
+    }
+
+    #[test]
+    fn neg() {
+        let mut components = runtime_components();
+        components.registers.a.set(0x01);
+        _0xED44 {}.execute(&mut components, Operands::None);
+        assert_eq!(components.registers.a.get(), 0xFF);
+        assert!(components.registers.f.get_carry() == FlagValue::Set);
+        assert!(components.registers.f.get_sign() == FlagValue::Set);
+        assert!(components.registers.f.get_add_subtract() == FlagValue::Set);
+
+        components.registers.a.set(0x00);
+        _0xED44 {}.execute(&mut components, Operands::None);
+        assert_eq!(components.registers.a.get(), 0x00);
+        assert!(components.registers.f.get_carry() == FlagValue::Unset);
+        assert!(components.registers.f.get_zero() == FlagValue::Set);
+
+        components.registers.a.set(0x80);
+        _0xED44 {}.execute(&mut components, Operands::None);
+        assert_eq!(components.registers.a.get(), 0x80);
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Set);
+    }
+
+    #[test]
+    fn retn_restores_interrupt_state_saved_at_nmi() {
+        let mut components = runtime_components();
+        components.registers.sp.set(0xC000);
+        components.registers.iff1 = true;
+        components.registers.iff2 = true;
+
+        // What an NMI does on entry: IFF1 is cleared, IFF2 keeps the old state and PC is pushed.
+        components.registers.iff1 = false;
+        components.registers.sp.push(&mut components.mem, 0x1234);
+        components.registers.pc.set(0x0066);
+
+        let cycles = _0xED45 {}.execute(&mut components, Operands::None);
+
+        assert_eq!(cycles, 14);
+        assert_eq!(components.registers.pc.get(), 0x1234);
+        assert_eq!(components.registers.sp.get(), 0xC000);
+        assert!(components.registers.iff1);
+    }
+
+    #[test]
+    fn reti_pops_pc() {
+        let mut components = runtime_components();
+        components.registers.sp.set(0xC000);
+        components.registers.sp.push(&mut components.mem, 0x4321);
+
+        assert_eq!(_0xED4D {}.execute(&mut components, Operands::None), 14);
+        assert_eq!(components.registers.pc.get(), 0x4321);
+        assert_eq!(components.registers.sp.get(), 0xC000);
+    }
+
+    #[test]
+    fn im_2() {
+        let mut components = runtime_components();
+        components.registers.i.set(0x10);
+        assert_eq!(_0xED5E {}.execute(&mut components, Operands::None), 8);
+        assert_eq!(components.registers.interrupt_mode, 2);
+    }
+
+    #[test]
+    fn ld_a_i_copies_iff2_into_parity() {
+        let mut components = runtime_components();
+        components.registers.a.set(0x80);
+        components.registers.f.set(0x01);
+        assert_eq!(_0xED47 {}.execute(&mut components, Operands::None), 9);
+        assert_eq!(components.registers.i.get(), 0x80);
+
+        components.registers.a.set(0x00);
+        components.registers.iff2 = true;
+        assert_eq!(_0xED57 {}.execute(&mut components, Operands::None), 9);
+        assert_eq!(components.registers.a.get(), 0x80);
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Set);
+        assert!(components.registers.f.get_sign() == FlagValue::Set);
+        assert!(components.registers.f.get_zero() == FlagValue::Unset);
+        assert!(components.registers.f.get_carry() == FlagValue::Set);
+
+        components.registers.iff2 = false;
+        _0xED57 {}.execute(&mut components, Operands::None);
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn ld_r_a_then_ld_a_r() {
+        let mut components = runtime_components();
+        components.registers.a.set(0x00);
+        _0xED4F {}.execute(&mut components, Operands::None);
+        assert_eq!(components.registers.r.get(), 0x00);
+
+        components.registers.a.set(0x42);
+        _0xED5F {}.execute(&mut components, Operands::None);
+        assert_eq!(components.registers.a.get(), 0x00);
+        assert!(components.registers.f.get_zero() == FlagValue::Set);
+    }
+
+    #[test]
+    fn rld_manual_example() {
+        // From the Z80 user manual: A=#7A, (HL)=#31 gives A=#73, (HL)=#1A.
+        let mut components = runtime_components();
+        set_pairs(&mut components, 0x5000, 0x0000, 0x0000);
+        components.registers.a.set(0x7A);
+        components.mem.locations[0x5000] = 0x31;
+
+        assert_eq!(_0xED6F {}.execute(&mut components, Operands::None), 18);
+        assert_eq!(components.registers.a.get(), 0x73);
+        assert_eq!(components.mem.read_u8(0x5000), 0x1A);
+        assert!(components.registers.f.get_sign() == FlagValue::Unset);
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn rrd_manual_example() {
+        // From the Z80 user manual: A=#84, (HL)=#20 gives A=#80, (HL)=#42.
+        let mut components = runtime_components();
+        set_pairs(&mut components, 0x5000, 0x0000, 0x0000);
+        components.registers.a.set(0x84);
+        components.mem.locations[0x5000] = 0x20;
+
+        assert_eq!(_0xED67 {}.execute(&mut components, Operands::None), 18);
+        assert_eq!(components.registers.a.get(), 0x80);
+        assert_eq!(components.mem.read_u8(0x5000), 0x42);
+        assert!(components.registers.f.get_sign() == FlagValue::Set);
+        assert!(components.registers.f.get_parity_overflow() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn in_e_sets_flags_from_byte_read() {
+        let mut components = runtime_components();
+        set_pairs(&mut components, 0x0000, 0x0000, 0xF4FF);
+        let expected = components.data_bus.read(0xF4FF);
+        components.registers.f.set_carry(FlagValue::Set);
+
+        assert_eq!(_0xED58 {}.execute(&mut components, Operands::None), 12);
+
+        assert_eq!(components.registers.e.get(), expected);
+        assert!(components.registers.f.get_sign() == if expected & 0x80 == 0x80 { FlagValue::Set } else { FlagValue::Unset });
+        assert!(components.registers.f.get_zero() == if expected == 0 { FlagValue::Set } else { FlagValue::Unset });
+        assert!(components.registers.f.get_carry() == FlagValue::Set);
+        assert!(components.registers.f.get_add_subtract() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn in_c_only_sets_flags() {
+        let mut components = runtime_components();
+        set_pairs(&mut components, 0x0000, 0x0000, 0xF4FF);
+        _0xED70 {}.execute(&mut components, Operands::None);
+        assert_eq!((components.registers.b.get(), components.registers.c.get()), (0xF4, 0xFF));
+        assert!(components.registers.f.get_half_carry() == FlagValue::Unset);
+        assert_eq!(_0xED71 {}.execute(&mut components, Operands::None), 12);
+    }
+}
@@ -0,0 +1,667 @@
+// Single-instruction correctness tests driven by vectors in the FUSE emulator's
+// tests.in / tests.expected format (see tests/fuse). Each vector gives a full machine
+// state, runs one instruction, and lists the registers, memory and T-states expected after.
+// The fixtures are NOT the upstream FUSE vectors: they were written for this repo in the same
+// format and only cover opcodes we implement, so they check the code against our own reading
+// of the Z80 rather than against FUSE. The upstream files can be dropped in as-is since tests
+// are looked up by name, with failures marked #[ignore]. Until then, point FUSE_TESTS_DIR at
+// a directory holding them to run the same tests without replacing the fixtures.
+//
+// Not yet compared: R, which isn't incremented on each opcode fetch, and MEMPTR, which isn't modelled.
+// The undocumented flag bits 3 and 5 are masked out of F.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::{memory::Register, runtime::Runtime, utils::{combine_to_double_byte, split_double_byte}};
+
+const TESTS_IN: &str = include_str!("../../tests/fuse/tests.in");
+const TESTS_EXPECTED: &str = include_str!("../../tests/fuse/tests.expected");
+
+const DOCUMENTED_FLAGS: u8 = 0xD7;
+
+struct MachineState {
+    // AF BC DE HL AF' BC' DE' HL' IX IY SP PC
+    registers: Vec<u16>,
+    i: u8,
+    r: u8,
+    iff1: bool,
+    iff2: bool,
+    interrupt_mode: u8,
+    halted: bool,
+    tstates: u32,
+    memory: Vec<(u16, Vec<u8>)>
+}
+
+fn parse_hex_u16(value: &str) -> u16 {
+    u16::from_str_radix(value, 16).unwrap()
+}
+
+// Parses the register, state and memory lines that follow a test name.
+// Event lines (indented, only present in tests.expected) are skipped.
+fn parse_states(text: &str) -> HashMap<String, MachineState> {
+    let mut states = HashMap::new();
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty() && !line.starts_with(' ')).peekable();
+    while let Some(name) = lines.next() {
+        let registers: Vec<u16> = lines.next().unwrap().split_whitespace().map(parse_hex_u16).collect();
+        let state: Vec<&str> = lines.next().unwrap().split_whitespace().collect();
+        let mut memory = Vec::new();
+        loop {
+            let tokens: Vec<&str> = match lines.peek() {
+                Some(line) if line.split_whitespace().count() > 1 || line.trim() == "-1" => lines.next().unwrap().split_whitespace().collect(),
+                _ => break
+            };
+            if tokens[0] == "-1" { break; }
+            let bytes = tokens[1..].iter().take_while(|b| **b != "-1").map(|b| u8::from_str_radix(b, 16).unwrap()).collect();
+            memory.push((parse_hex_u16(tokens[0]), bytes));
+        }
+        states.insert(name.trim().to_string(), MachineState {
+            registers,
+            i: u8::from_str_radix(state[0], 16).unwrap(),
+            r: u8::from_str_radix(state[1], 16).unwrap(),
+            iff1: state[2] == "1",
+            iff2: state[3] == "1",
+            interrupt_mode: state[4].parse().unwrap(),
+            halted: state[5] == "1",
+            tstates: state[6].parse().unwrap(),
+            memory
+        });
+    }
+    states
+}
+
+fn setup(runtime: &mut Runtime, state: &MachineState) {
+    let mem = &mut runtime.components.mem;
+    for (addr, location) in mem.locations.iter_mut().enumerate() {
+        *location = [0xDE, 0xAD, 0xBE, 0xEF][addr % 4];
+    }
+    for (start, bytes) in &state.memory {
+        for (offset, byte) in bytes.iter().enumerate() {
+            mem.locations[*start as usize + offset] = *byte;
+        }
+    }
+
+    let reg = &mut runtime.components.registers;
+    let r = &state.registers;
+    let (a, f) = split_double_byte(r[0]); reg.a.set(a); reg.f.set(f);
+    let (b, c) = split_double_byte(r[1]); reg.b.set(b); reg.c.set(c);
+    let (d, e) = split_double_byte(r[2]); reg.d.set(d); reg.e.set(e);
+    let (h, l) = split_double_byte(r[3]); reg.h.set(h); reg.l.set(l);
+    let (a, f) = split_double_byte(r[4]); reg.a_.set(a); reg.f_.set(f);
+    let (b, c) = split_double_byte(r[5]); reg.b_.set(b); reg.c_.set(c);
+    let (d, e) = split_double_byte(r[6]); reg.d_.set(d); reg.e_.set(e);
+    let (h, l) = split_double_byte(r[7]); reg.h_.set(h); reg.l_.set(l);
+    reg.ix = r[8];
+    reg.iy = r[9];
+    reg.sp.set(r[10] as usize);
+    reg.pc.set(r[11]);
+    reg.i.set(state.i);
+    reg.r.set(state.r);
+    reg.iff1 = state.iff1;
+    reg.iff2 = state.iff2;
+    reg.interrupt_mode = state.interrupt_mode;
+    reg.halted = state.halted;
+}
+
+fn compare(runtime: &Runtime, expected: &MachineState, tstates: u32) -> Vec<String> {
+    let reg = &runtime.components.registers;
+    let actual = [
+        combine_to_double_byte(reg.a.get(), reg.f.get() & DOCUMENTED_FLAGS),
+        combine_to_double_byte(reg.b.get(), reg.c.get()),
+        combine_to_double_byte(reg.d.get(), reg.e.get()),
+        combine_to_double_byte(reg.h.get(), reg.l.get()),
+        combine_to_double_byte(reg.a_.get(), reg.f_.get() & DOCUMENTED_FLAGS),
+        combine_to_double_byte(reg.b_.get(), reg.c_.get()),
+        combine_to_double_byte(reg.d_.get(), reg.e_.get()),
+        combine_to_double_byte(reg.h_.get(), reg.l_.get()),
+        reg.ix,
+        reg.iy,
+    ];
+    let names = ["AF", "BC", "DE", "HL", "AF'", "BC'", "DE'", "HL'", "IX", "IY"];
+
+    let mut failures = Vec::new();
+    for (i, name) in names.iter().enumerate() {
+        let mut wanted = expected.registers[i];
+        if i == 0 || i == 4 { wanted &= 0xFF00 | DOCUMENTED_FLAGS as u16; }
+        if actual[i] != wanted {
+            failures.push(format!("{}: expected {:04X}, got {:04X}", name, wanted, actual[i]));
+        }
+    }
+    if reg.sp.get() as u16 != expected.registers[10] {
+        failures.push(format!("SP: expected {:04X}, got {:04X}", expected.registers[10], reg.sp.get()));
+    }
+    if reg.pc.get() != expected.registers[11] {
+        failures.push(format!("PC: expected {:04X}, got {:04X}", expected.registers[11], reg.pc.get()));
+    }
+    if (reg.iff1, reg.iff2, reg.interrupt_mode) != (expected.iff1, expected.iff2, expected.interrupt_mode) {
+        failures.push(format!("IFF1/IFF2/IM: expected {}/{}/{}, got {}/{}/{}", expected.iff1, expected.iff2, expected.interrupt_mode, reg.iff1, reg.iff2, reg.interrupt_mode));
+    }
+    if reg.i.get() != expected.i {
+        failures.push(format!("I: expected {:02X}, got {:02X}", expected.i, reg.i.get()));
+    }
+    if reg.halted != expected.halted {
+        failures.push(format!("Halted: expected {}, got {}", expected.halted, reg.halted));
+    }
+    if tstates != expected.tstates {
+        failures.push(format!("T-states: expected {}, got {}", expected.tstates, tstates));
+    }
+    for (start, bytes) in &expected.memory {
+        for (offset, byte) in bytes.iter().enumerate() {
+            let addr = *start as usize + offset;
+            let actual = runtime.components.mem.locations[addr];
+            if actual != *byte {
+                failures.push(format!("({:04X}): expected {:02X}, got {:02X}", addr, byte, actual));
+            }
+        }
+    }
+    failures
+}
+
+// The tests.in and tests.expected text, from FUSE_TESTS_DIR if it's set.
+fn fuse_files() -> (Cow<'static, str>, Cow<'static, str>) {
+    match std::env::var_os("FUSE_TESTS_DIR") {
+        Some(dir) => {
+            let read = |file| std::fs::read_to_string(std::path::Path::new(&dir).join(file))
+                .unwrap_or_else(|e| panic!("Couldn't read {} from FUSE_TESTS_DIR: {}", file, e));
+            (Cow::Owned(read("tests.in")), Cow::Owned(read("tests.expected")))
+        }
+        None => (Cow::Borrowed(TESTS_IN), Cow::Borrowed(TESTS_EXPECTED))
+    }
+}
+
+fn run_fuse_test(name: &str) {
+    let (tests_in, tests_expected) = fuse_files();
+    let inputs = parse_states(&tests_in);
+    let outputs = parse_states(&tests_expected);
+    let input = inputs.get(name).unwrap_or_else(|| panic!("No FUSE input for {}", name));
+    let expected = outputs.get(name).unwrap_or_else(|| panic!("No FUSE expectation for {}", name));
+
+    let mut runtime = Runtime::default();
+    setup(&mut runtime, input);
+
+    // Like FUSE, keep executing whole instructions until the requested T-states have elapsed.
+    let mut tstates = 0u32;
+    while tstates < input.tstates {
+        tstates += runtime.step().unwrap() as u32;
+    }
+
+    let failures = compare(&runtime, expected, tstates);
+    assert!(failures.is_empty(), "FUSE test {} failed:\n  {}", name, failures.join("\n  "));
+}
+
+macro_rules! fuse_tests {
+    ($( $(#[$attr:meta])* $test_name:ident: $fuse_name:expr ),* $(,)?) => {
+        $(
+            #[test]
+            $(#[$attr])*
+            fn $test_name() {
+                run_fuse_test($fuse_name);
+            }
+        )*
+    }
+}
+
+fuse_tests! {
+    op_00: "00",
+    op_01: "01",
+    op_02: "02",
+    op_04: "04",
+    op_04_1: "04_1",
+    op_05: "05",
+    op_05_1: "05_1",
+    op_05_2: "05_2",
+    op_06: "06",
+    op_07: "07",
+    op_07_1: "07_1",
+    op_08: "08",
+    op_09: "09",
+    op_0a: "0a",
+    op_0c: "0c",
+    op_0d: "0d",
+    op_0e: "0e",
+    op_0f: "0f",
+    op_0f_1: "0f_1",
+    op_10: "10",
+    op_10_1: "10_1",
+    op_10_2: "10_2",
+    op_11: "11",
+    op_12: "12",
+    op_13: "13",
+    op_14: "14",
+    op_15: "15",
+    op_16: "16",
+    op_17: "17",
+    op_17_1: "17_1",
+    op_18: "18",
+    op_18_1: "18_1",
+    op_19: "19",
+    op_1a: "1a",
+    op_1c: "1c",
+    op_1d: "1d",
+    op_1e: "1e",
+    op_1f: "1f",
+    op_1f_1: "1f_1",
+    op_20: "20",
+    op_20_1: "20_1",
+    op_21: "21",
+    op_22: "22",
+    op_23: "23",
+    op_24: "24",
+    op_25: "25",
+    op_26: "26",
+    op_27: "27",
+    op_27_1: "27_1",
+    op_27_2: "27_2",
+    op_27_3: "27_3",
+    op_27_4: "27_4",
+    op_27_5: "27_5",
+    op_27_6: "27_6",
+    op_28: "28",
+    op_28_1: "28_1",
+    op_28_2: "28_2",
+    op_29: "29",
+    op_2a: "2a",
+    op_2b: "2b",
+    op_2c: "2c",
+    op_2d: "2d",
+    op_2e: "2e",
+    op_2f: "2f",
+    op_30: "30",
+    op_30_1: "30_1",
+    op_31: "31",
+    op_32: "32",
+    op_33: "33",
+    op_34: "34",
+    op_35: "35",
+    op_36: "36",
+    op_38: "38",
+    op_38_1: "38_1",
+    op_38_2: "38_2",
+    op_39: "39",
+    op_39_1: "39_1",
+    op_3a: "3a",
+    op_3b: "3b",
+    op_3c: "3c",
+    op_3c_1: "3c_1",
+    op_3d: "3d",
+    op_3e: "3e",
+    op_40: "40",
+    op_41: "41",
+    op_42: "42",
+    op_43: "43",
+    op_44: "44",
+    op_45: "45",
+    op_46: "46",
+    op_47: "47",
+    op_48: "48",
+    op_49: "49",
+    op_4a: "4a",
+    op_4b: "4b",
+    op_4c: "4c",
+    op_4d: "4d",
+    op_4e: "4e",
+    op_4f: "4f",
+    op_50: "50",
+    op_51: "51",
+    op_52: "52",
+    op_53: "53",
+    op_54: "54",
+    op_55: "55",
+    op_56: "56",
+    op_57: "57",
+    op_58: "58",
+    op_59: "59",
+    op_5a: "5a",
+    op_5b: "5b",
+    op_5c: "5c",
+    op_5d: "5d",
+    op_5e: "5e",
+    op_5f: "5f",
+    op_60: "60",
+    op_61: "61",
+    op_62: "62",
+    op_63: "63",
+    op_64: "64",
+    op_65: "65",
+    op_66: "66",
+    op_67: "67",
+    op_68: "68",
+    op_69: "69",
+    op_6a: "6a",
+    op_6b: "6b",
+    op_6c: "6c",
+    op_6d: "6d",
+    op_6e: "6e",
+    op_6f: "6f",
+    op_70: "70",
+    op_71: "71",
+    op_72: "72",
+    op_73: "73",
+    op_74: "74",
+    op_75: "75",
+    op_76: "76",
+    op_77: "77",
+    op_78: "78",
+    op_79: "79",
+    op_7a: "7a",
+    op_7b: "7b",
+    op_7c: "7c",
+    op_7d: "7d",
+    op_7e: "7e",
+    op_7f: "7f",
+    op_80: "80",
+    op_80_1: "80_1",
+    op_80_2: "80_2",
+    op_81: "81",
+    op_82: "82",
+    op_83: "83",
+    op_84: "84",
+    op_85: "85",
+    op_86: "86",
+    op_87: "87",
+    op_87_1: "87_1",
+    op_88: "88",
+    op_88_1: "88_1",
+    op_89: "89",
+    op_8a: "8a",
+    op_8b: "8b",
+    op_8c: "8c",
+    op_8d: "8d",
+    op_8e: "8e",
+    op_8f: "8f",
+    op_8f_1: "8f_1",
+    op_90: "90",
+    op_90_1: "90_1",
+    op_91: "91",
+    op_92: "92",
+    op_93: "93",
+    op_94: "94",
+    op_95: "95",
+    op_96: "96",
+    op_97: "97",
+    op_98: "98",
+    op_98_1: "98_1",
+    op_99: "99",
+    op_9a: "9a",
+    op_9b: "9b",
+    op_9c: "9c",
+    op_9d: "9d",
+    op_9e: "9e",
+    op_9f: "9f",
+    op_9f_1: "9f_1",
+    op_a0: "a0",
+    op_a1: "a1",
+    op_a2: "a2",
+    op_a3: "a3",
+    op_a4: "a4",
+    op_a5: "a5",
+    op_a6: "a6",
+    op_a7: "a7",
+    op_a8: "a8",
+    op_a9: "a9",
+    op_aa: "aa",
+    op_ab: "ab",
+    op_ac: "ac",
+    op_ad: "ad",
+    op_ae: "ae",
+    op_af: "af",
+    op_b0: "b0",
+    op_b1: "b1",
+    op_b2: "b2",
+    op_b3: "b3",
+    op_b4: "b4",
+    op_b5: "b5",
+    op_b6: "b6",
+    op_b7: "b7",
+    op_b7_1: "b7_1",
+    op_b8: "b8",
+    op_b9: "b9",
+    op_ba: "ba",
+    op_bb: "bb",
+    op_bb_1: "bb_1",
+    op_bc: "bc",
+    op_bd: "bd",
+    op_be: "be",
+    op_bf: "bf",
+    op_c0: "c0",
+    op_c0_1: "c0_1",
+    op_c1: "c1",
+    op_c3: "c3",
+    op_c4: "c4",
+    op_c4_1: "c4_1",
+    op_c4_2: "c4_2",
+    op_c5: "c5",
+    op_c6: "c6",
+    op_c7: "c7",
+    op_c8: "c8",
+    op_c8_1: "c8_1",
+    op_c9: "c9",
+    op_ca: "ca",
+    op_ca_1: "ca_1",
+    op_ca_2: "ca_2",
+    op_cc: "cc",
+    op_cc_1: "cc_1",
+    op_cc_2: "cc_2",
+    op_cd: "cd",
+    op_ce: "ce",
+    op_cf: "cf",
+    op_d0: "d0",
+    op_d0_1: "d0_1",
+    op_d0_2: "d0_2",
+    op_d1: "d1",
+    op_d2: "d2",
+    op_d2_1: "d2_1",
+    op_d2_2: "d2_2",
+    op_d4: "d4",
+    op_d4_1: "d4_1",
+    op_d4_2: "d4_2",
+    op_d5: "d5",
+    op_d6: "d6",
+    op_d6_1: "d6_1",
+    op_d7: "d7",
+    op_d8: "d8",
+    op_d8_1: "d8_1",
+    op_d9: "d9",
+    op_da: "da",
+    op_da_1: "da_1",
+    op_da_2: "da_2",
+    op_dc: "dc",
+    op_dc_1: "dc_1",
+    op_dc_2: "dc_2",
+    op_de: "de",
+    op_de_1: "de_1",
+    op_df: "df",
+    op_e0: "e0",
+    op_e0_1: "e0_1",
+    op_e0_2: "e0_2",
+    op_e1: "e1",
+    op_e2: "e2",
+    op_e2_1: "e2_1",
+    op_e2_2: "e2_2",
+    op_e3: "e3",
+    op_e4: "e4",
+    op_e4_1: "e4_1",
+    op_e4_2: "e4_2",
+    op_e5: "e5",
+    op_e6: "e6",
+    op_e7: "e7",
+    op_e8: "e8",
+    op_e8_1: "e8_1",
+    op_e8_2: "e8_2",
+    op_e9: "e9",
+    op_ea: "ea",
+    op_ea_1: "ea_1",
+    op_ea_2: "ea_2",
+    op_eb: "eb",
+    op_ec: "ec",
+    op_ec_1: "ec_1",
+    op_ec_2: "ec_2",
+    op_ee: "ee",
+    op_ef: "ef",
+    op_f0: "f0",
+    op_f0_1: "f0_1",
+    op_f0_2: "f0_2",
+    op_f1: "f1",
+    op_f2: "f2",
+    op_f2_1: "f2_1",
+    op_f3: "f3",
+    op_f4: "f4",
+    op_f4_1: "f4_1",
+    op_f4_2: "f4_2",
+    op_f5: "f5",
+    op_f6: "f6",
+    op_f7: "f7",
+    op_f8: "f8",
+    op_f8_1: "f8_1",
+    op_f9: "f9",
+    op_fa: "fa",
+    op_fa_1: "fa_1",
+    op_fa_2: "fa_2",
+    op_fb: "fb",
+    op_fc: "fc",
+    op_fc_1: "fc_1",
+    op_fc_2: "fc_2",
+    op_fe: "fe",
+    op_fe_1: "fe_1",
+    op_ff: "ff",
+    op_cb00: "cb00",
+    op_cb06: "cb06",
+    op_cb09: "cb09",
+    op_cb0e: "cb0e",
+    op_cb12: "cb12",
+    op_cb16: "cb16",
+    op_cb1b: "cb1b",
+    op_cb1e: "cb1e",
+    op_cb24: "cb24",
+    op_cb26: "cb26",
+    op_cb2d: "cb2d",
+    op_cb2e: "cb2e",
+    op_cb36: "cb36",
+    op_cb37: "cb37",
+    op_cb38: "cb38",
+    op_cb3e: "cb3e",
+    op_cb3f: "cb3f",
+    op_cb40: "cb40",
+    op_cb46: "cb46",
+    op_cb49: "cb49",
+    op_cb52: "cb52",
+    op_cb5b: "cb5b",
+    op_cb64: "cb64",
+    op_cb6d: "cb6d",
+    op_cb77: "cb77",
+    op_cb7e: "cb7e",
+    op_cb7f: "cb7f",
+    op_cb80: "cb80",
+    op_cb86: "cb86",
+    op_cb89: "cb89",
+    op_cb92: "cb92",
+    op_cb9b: "cb9b",
+    op_cba4: "cba4",
+    op_cbad: "cbad",
+    op_cbb7: "cbb7",
+    op_cbbe: "cbbe",
+    op_cbbf: "cbbf",
+    op_cbc0: "cbc0",
+    op_cbc6: "cbc6",
+    op_cbc9: "cbc9",
+    op_cbd2: "cbd2",
+    op_cbdb: "cbdb",
+    op_cbe4: "cbe4",
+    op_cbed: "cbed",
+    op_cbf7: "cbf7",
+    op_cbfe: "cbfe",
+    op_cbff: "cbff",
+    op_dd21: "dd21",
+    op_dd34: "dd34",
+    op_dd35: "dd35",
+    op_dd36: "dd36",
+    op_dd46: "dd46",
+    op_dd4e: "dd4e",
+    op_dd5e: "dd5e",
+    op_dd66: "dd66",
+    op_dd6e: "dd6e",
+    op_dd70: "dd70",
+    op_dd75: "dd75",
+    op_dd77: "dd77",
+    op_dd7e: "dd7e",
+    op_dd86: "dd86",
+    op_dd8e: "dd8e",
+    op_dd96: "dd96",
+    op_dd9e: "dd9e",
+    op_dda6: "dda6",
+    op_ddae: "ddae",
+    op_ddb6: "ddb6",
+    op_ddbe: "ddbe",
+    op_ddcb06: "ddcb06",
+    op_ddcb1e: "ddcb1e",
+    op_ddcb3e: "ddcb3e",
+    op_ddcb46: "ddcb46",
+    op_ddcb7e: "ddcb7e",
+    op_ddcb86: "ddcb86",
+    op_ddcbfe: "ddcbfe",
+    op_dde1: "dde1",
+    op_dde5: "dde5",
+    op_ed41: "ed41",
+    op_ed42: "ed42",
+    op_ed43: "ed43",
+    op_ed44: "ed44",
+    op_ed44_1: "ed44_1",
+    op_ed44_2: "ed44_2",
+    op_ed44_3: "ed44_3",
+    op_ed45: "ed45",
+    op_ed46: "ed46",
+    op_ed47: "ed47",
+    op_ed49: "ed49",
+    op_ed4a: "ed4a",
+    op_ed4b: "ed4b",
+    op_ed4d: "ed4d",
+    op_ed4f: "ed4f",
+    op_ed51: "ed51",
+    op_ed52: "ed52",
+    op_ed53: "ed53",
+    op_ed56: "ed56",
+    op_ed57: "ed57",
+    op_ed57_1: "ed57_1",
+    op_ed59: "ed59",
+    op_ed5a: "ed5a",
+    op_ed5b: "ed5b",
+    op_ed5e: "ed5e",
+    op_ed61: "ed61",
+    op_ed62: "ed62",
+    op_ed63: "ed63",
+    op_ed67: "ed67",
+    op_ed69: "ed69",
+    op_ed6a: "ed6a",
+    op_ed6b: "ed6b",
+    op_ed6f: "ed6f",
+    op_ed71: "ed71",
+    op_ed72: "ed72",
+    op_ed73: "ed73",
+    #[ignore]
+    op_ed78: "ed78",
+    op_ed79: "ed79",
+    op_ed7a: "ed7a",
+    op_ed7b: "ed7b",
+    op_eda0: "eda0",
+    op_eda1: "eda1",
+    op_eda3: "eda3",
+    op_eda8: "eda8",
+    op_eda9: "eda9",
+    op_edab: "edab",
+    op_edb0: "edb0",
+    op_edb0_1: "edb0_1",
+    op_edb1: "edb1",
+    op_edb3: "edb3",
+    op_edb8: "edb8",
+    op_edb9: "edb9",
+    op_edbb: "edbb",
+    op_fd21: "fd21",
+    op_fd36: "fd36",
+    op_fd7e: "fd7e",
+    op_fd86: "fd86",
+    op_fdcb26: "fdcb26",
+    op_fdcb56: "fdcb56",
+    op_fdcbce: "fdcbce",
+    op_fde1: "fde1",
+    op_fde5: "fde5",
+}
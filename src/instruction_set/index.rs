@@ -1,28 +1,1381 @@
 use log::error;
 
-use crate::{memory::{Memory, Registers, AddressBus, DataBus, Register, RegisterOperations}, utils::{self, combine_to_double_byte, split_double_byte}, runtime::{Runtime, RuntimeComponents}, inst_metadata};
-use super::{Instruction, Operands};
+use crate::{memory::{Memory, Registers, AddressBus, DataBus, Register, RegisterOperations}, utils::{self, combine_to_double_byte, split_double_byte}, runtime::{Runtime, RuntimeComponents}, inst_metadata, indexed_inst_metadata};
+use super::{Instruction, Operands, OperandKind};
 
-pub struct _0xDDE1 {}
+// Selects which index register an instruction works on, so one struct can serve both the
+// DD (IX) and FD (IY) prefixed forms of an opcode. The structs are named after the DD form.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IndexRegister {
+    IX,
+    IY
+}
+
+impl IndexRegister {
+    pub fn get(&self, registers: &Registers) -> u16 {
+        match self {
+            IndexRegister::IX => registers.ix,
+            IndexRegister::IY => registers.iy
+        }
+    }
+
+    pub fn set(&self, registers: &mut Registers, value: u16) {
+        match self {
+            IndexRegister::IX => registers.ix = value,
+            IndexRegister::IY => registers.iy = value
+        }
+    }
+}
+
+// Like inst_metadata, but picks the DD or FD prefix and the IX or IY name from self.index.
+// The assembly is given as the text either side of the register name.
+#[macro_export]
+macro_rules! indexed_inst_metadata {
+    ( $op_count:expr, $op_code:expr, $assem_before:expr, $assem_after:expr) => {
+        fn operand_count(&self) -> u8 {
+            $op_count
+        }
+
+        fn machine_code(&self) -> &str {
+            match self.index {
+                IndexRegister::IX => concat!("DD ", $op_code),
+                IndexRegister::IY => concat!("FD ", $op_code)
+            }
+        }
+
+        fn assembly(&self) -> &str {
+            match self.index {
+                IndexRegister::IX => concat!($assem_before, "IX", $assem_after),
+                IndexRegister::IY => concat!($assem_before, "IY", $assem_after)
+            }
+        }
+    };
+}
+
+pub struct _0xDD21 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD21 {
+    // load nn into the index register
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Word(value) => self.index.set(&mut components.registers, value),
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        14
+    }
+
+    indexed_inst_metadata!(2, "21 *1 *2", "LD ", ",*2*1");
+}
+
+pub struct _0xDD34 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD34 {
+    // Adds one to the value pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                let value = RegisterOperations::inc_value(components.mem.read_u8(addr), &mut components.registers.f);
+                components.mem.write_u8(addr, value);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "34 *1", "INC (", "+*1)");
+}
+
+pub struct _0xDD35 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD35 {
+    // Subtracts one from the value pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                let value = RegisterOperations::dec_value(components.mem.read_u8(addr), &mut components.registers.f);
+                components.mem.write_u8(addr, value);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "35 *1", "DEC (", "+*1)");
+}
+
+pub struct _0xDD36 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD36 {
+    // Stores n into the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::IndexedImmediate(offset, value) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, value);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::IndexedImmediate8
+    }
+
+    indexed_inst_metadata!(2, "36 *1 *2", "LD (", "+*1),*2");
+}
+
+pub struct _0xDD46 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD46 {
+    // Loads the value pointed to by the index register plus d into B.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.registers.b.set(components.mem.read_u8(addr));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "46 *1", "LD B,(", "+*1)");
+}
+
+pub struct _0xDD4E {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD4E {
+    // Loads the value pointed to by the index register plus d into C.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.registers.c.set(components.mem.read_u8(addr));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "4E *1", "LD C,(", "+*1)");
+}
+
+pub struct _0xDD56 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD56 {
+    // Loads the value pointed to by the index register plus d into D.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.registers.d.set(components.mem.read_u8(addr));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "56 *1", "LD D,(", "+*1)");
+}
+
+pub struct _0xDD5E {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD5E {
+    // Loads the value pointed to by the index register plus d into E.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.registers.e.set(components.mem.read_u8(addr));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "5E *1", "LD E,(", "+*1)");
+}
+
+pub struct _0xDD66 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD66 {
+    // Loads the value pointed to by the index register plus d into H.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.registers.h.set(components.mem.read_u8(addr));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "66 *1", "LD H,(", "+*1)");
+}
+
+pub struct _0xDD6E {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD6E {
+    // Loads the value pointed to by the index register plus d into L.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.registers.l.set(components.mem.read_u8(addr));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "6E *1", "LD L,(", "+*1)");
+}
+
+pub struct _0xDD70 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD70 {
+    // Stores B into the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, components.registers.b.get());
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "70 *1", "LD (", "+*1),B");
+}
+
+pub struct _0xDD71 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD71 {
+    // Stores C into the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, components.registers.c.get());
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "71 *1", "LD (", "+*1),C");
+}
+
+pub struct _0xDD72 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD72 {
+    // Stores D into the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, components.registers.d.get());
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "72 *1", "LD (", "+*1),D");
+}
+
+pub struct _0xDD73 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD73 {
+    // Stores E into the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, components.registers.e.get());
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "73 *1", "LD (", "+*1),E");
+}
+
+pub struct _0xDD74 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD74 {
+    // Stores H into the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, components.registers.h.get());
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "74 *1", "LD (", "+*1),H");
+}
+
+pub struct _0xDD75 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD75 {
+    // Stores L into the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, components.registers.l.get());
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "75 *1", "LD (", "+*1),L");
+}
+
+pub struct _0xDD77 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD77 {
+    // Stores A into the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, components.registers.a.get());
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "77 *1", "LD (", "+*1),A");
+}
+
+pub struct _0xDD7E {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD7E {
+    // Loads the value pointed to by the index register plus d into A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.registers.a.set(components.mem.read_u8(addr));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "7E *1", "LD A,(", "+*1)");
+}
+
+pub struct _0xDD86 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD86 {
+    // Adds the value pointed to by the index register plus d to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.registers.a.add_value(components.mem.read_u8(addr), &mut components.registers.f);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "86 *1", "ADD A,(", "+*1)");
+}
+
+pub struct _0xDD8E {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD8E {
+    // Adds the value pointed to by the index register plus d and the carry flag to A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.registers.a.adc_value(components.mem.read_u8(addr), &mut components.registers.f);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "8E *1", "ADC A,(", "+*1)");
+}
+
+pub struct _0xDD96 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD96 {
+    // Subtracts the value pointed to by the index register plus d from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.registers.a.sub_value(components.mem.read_u8(addr), &mut components.registers.f);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "96 *1", "SUB (", "+*1)");
+}
+
+pub struct _0xDD9E {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDD9E {
+    // Subtracts the value pointed to by the index register plus d and the carry flag from A.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.registers.a.sub_value_and_carry(components.mem.read_u8(addr), &mut components.registers.f);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "9E *1", "SBC A,(", "+*1)");
+}
+
+pub struct _0xDDA6 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDA6 {
+    // Bitwise AND on A with the value pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.registers.a.and(components.mem.read_u8(addr), &mut components.registers.f);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "A6 *1", "AND (", "+*1)");
+}
+
+pub struct _0xDDAE {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDAE {
+    // Bitwise XOR on A with the value pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.registers.a.xor_value(components.mem.read_u8(addr), &mut components.registers.f);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "AE *1", "XOR (", "+*1)");
+}
+
+pub struct _0xDDB6 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDB6 {
+    // Bitwise OR on A with the value pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.registers.a.or_value(components.mem.read_u8(addr), &mut components.registers.f);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "B6 *1", "OR (", "+*1)");
+}
+
+pub struct _0xDDBE {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDBE {
+    // Subtracts the value pointed to by the index register plus d from A and affects flags according to the result. A is not modified.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.registers.a.compare_val(components.mem.read_u8(addr), &mut components.registers.f);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        19
+    }
+
+    fn operand_kind(&self) -> OperandKind {
+        OperandKind::Relative8
+    }
+
+    indexed_inst_metadata!(1, "BE *1", "CP (", "+*1)");
+}
+
+pub struct _0xDDE1 {
+    pub index: IndexRegister
+}
 impl Instruction for _0xDDE1 {
-    // Set interrupt mode 0
+    // The top stack entry is popped into the index register.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        let reg = &mut components.registers;
-        RegisterOperations::pop_register_pair((&mut reg.i, &mut reg.x), &mut reg.sp, &mut components.mem);
+        let value = components.registers.sp.pop(&components.mem);
+        self.index.set(&mut components.registers, value);
         14
     }
 
-    inst_metadata!(0, "DD E1", "POP IX");
+    indexed_inst_metadata!(0, "E1", "POP ", "");
+}
+
+pub struct _0xDDE5 {
+    pub index: IndexRegister
 }
-pub struct _0xDDE5 {}
 impl Instruction for _0xDDE5 {
-    // Set interrupt mode 0
+    // The index register is pushed onto the stack.
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
-        let reg = &mut components.registers;
-        RegisterOperations::push_register_pair((&reg.i, &reg.x), &mut reg.sp, &mut components.mem);
+        let value = self.index.get(&components.registers);
+        components.registers.sp.push(&mut components.mem, value);
         15
     }
 
-    inst_metadata!(0, "DD E5", "PUSH IX");
+    indexed_inst_metadata!(0, "E5", "PUSH ", "");
 }
 
+
+// Bit instructions on (IX+d) and (IY+d): DD CB d op / FD CB d op. The displacement comes before
+// the opcode, so the runtime fetches it and passes it in as a Relative operand.
+
+pub struct _0xDDCB06 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB06 {
+    // The contents of the memory location pointed to by the index register plus d are rotated left one bit position.
+    // The contents of bit 7 are copied to the carry flag and bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                RegisterOperations::read_modify_write_at(&mut components.mem, addr, &mut components.registers.f, RegisterOperations::rlc_value);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 06", "RLC (", "+*1)");
+}
+
+pub struct _0xDDCB0E {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB0E {
+    // The contents of the memory location pointed to by the index register plus d are rotated right one bit position.
+    // The contents of bit 0 are copied to the carry flag and bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                RegisterOperations::read_modify_write_at(&mut components.mem, addr, &mut components.registers.f, RegisterOperations::rrc_value);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 0E", "RRC (", "+*1)");
+}
+
+pub struct _0xDDCB16 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB16 {
+    // The contents of the memory location pointed to by the index register plus d are rotated left one bit position through the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                RegisterOperations::read_modify_write_at(&mut components.mem, addr, &mut components.registers.f, RegisterOperations::rl_value);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 16", "RL (", "+*1)");
+}
+
+pub struct _0xDDCB1E {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB1E {
+    // The contents of the memory location pointed to by the index register plus d are rotated right one bit position through the carry flag.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                RegisterOperations::read_modify_write_at(&mut components.mem, addr, &mut components.registers.f, RegisterOperations::rr_value);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 1E", "RR (", "+*1)");
+}
+
+pub struct _0xDDCB26 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB26 {
+    // The contents of the memory location pointed to by the index register plus d are shifted left one bit position.
+    // A zero is put into bit 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                RegisterOperations::read_modify_write_at(&mut components.mem, addr, &mut components.registers.f, RegisterOperations::sla_value);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 26", "SLA (", "+*1)");
+}
+
+pub struct _0xDDCB2E {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB2E {
+    // The contents of the memory location pointed to by the index register plus d are shifted right one bit position.
+    // Bit 7 is unchanged.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                RegisterOperations::read_modify_write_at(&mut components.mem, addr, &mut components.registers.f, RegisterOperations::sra_value);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 2E", "SRA (", "+*1)");
+}
+
+pub struct _0xDDCB36 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB36 {
+    // The contents of the memory location pointed to by the index register plus d are shifted left one bit position.
+    // A one is put into bit 0.
+    // Undocumented.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                RegisterOperations::read_modify_write_at(&mut components.mem, addr, &mut components.registers.f, RegisterOperations::sll_value);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 36", "SLL (", "+*1)");
+}
+
+pub struct _0xDDCB3E {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB3E {
+    // The contents of the memory location pointed to by the index register plus d are shifted right one bit position.
+    // A zero is put into bit 7.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                RegisterOperations::read_modify_write_at(&mut components.mem, addr, &mut components.registers.f, RegisterOperations::srl_value);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 3E", "SRL (", "+*1)");
+}
+
+pub struct _0xDDCB46 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB46 {
+    // Tests bit 0 of the memory location pointed to by the index register plus d, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                RegisterOperations::bit(0, components.mem.read_u8(addr), &mut components.registers.f);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        20
+    }
+
+    indexed_inst_metadata!(0, "CB *1 46", "BIT 0,(", "+*1)");
+}
+
+pub struct _0xDDCB4E {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB4E {
+    // Tests bit 1 of the memory location pointed to by the index register plus d, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                RegisterOperations::bit(1, components.mem.read_u8(addr), &mut components.registers.f);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        20
+    }
+
+    indexed_inst_metadata!(0, "CB *1 4E", "BIT 1,(", "+*1)");
+}
+
+pub struct _0xDDCB56 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB56 {
+    // Tests bit 2 of the memory location pointed to by the index register plus d, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                RegisterOperations::bit(2, components.mem.read_u8(addr), &mut components.registers.f);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        20
+    }
+
+    indexed_inst_metadata!(0, "CB *1 56", "BIT 2,(", "+*1)");
+}
+
+pub struct _0xDDCB5E {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB5E {
+    // Tests bit 3 of the memory location pointed to by the index register plus d, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                RegisterOperations::bit(3, components.mem.read_u8(addr), &mut components.registers.f);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        20
+    }
+
+    indexed_inst_metadata!(0, "CB *1 5E", "BIT 3,(", "+*1)");
+}
+
+pub struct _0xDDCB66 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB66 {
+    // Tests bit 4 of the memory location pointed to by the index register plus d, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                RegisterOperations::bit(4, components.mem.read_u8(addr), &mut components.registers.f);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        20
+    }
+
+    indexed_inst_metadata!(0, "CB *1 66", "BIT 4,(", "+*1)");
+}
+
+pub struct _0xDDCB6E {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB6E {
+    // Tests bit 5 of the memory location pointed to by the index register plus d, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                RegisterOperations::bit(5, components.mem.read_u8(addr), &mut components.registers.f);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        20
+    }
+
+    indexed_inst_metadata!(0, "CB *1 6E", "BIT 5,(", "+*1)");
+}
+
+pub struct _0xDDCB76 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB76 {
+    // Tests bit 6 of the memory location pointed to by the index register plus d, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                RegisterOperations::bit(6, components.mem.read_u8(addr), &mut components.registers.f);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        20
+    }
+
+    indexed_inst_metadata!(0, "CB *1 76", "BIT 6,(", "+*1)");
+}
+
+pub struct _0xDDCB7E {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB7E {
+    // Tests bit 7 of the memory location pointed to by the index register plus d, setting the zero flag if it is 0.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                RegisterOperations::bit(7, components.mem.read_u8(addr), &mut components.registers.f);
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        20
+    }
+
+    indexed_inst_metadata!(0, "CB *1 7E", "BIT 7,(", "+*1)");
+}
+
+pub struct _0xDDCB86 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB86 {
+    // Resets bit 0 of the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, RegisterOperations::change_bit(0, components.mem.read_u8(addr), false));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 86", "RES 0,(", "+*1)");
+}
+
+pub struct _0xDDCB8E {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB8E {
+    // Resets bit 1 of the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, RegisterOperations::change_bit(1, components.mem.read_u8(addr), false));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 8E", "RES 1,(", "+*1)");
+}
+
+pub struct _0xDDCB96 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB96 {
+    // Resets bit 2 of the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, RegisterOperations::change_bit(2, components.mem.read_u8(addr), false));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 96", "RES 2,(", "+*1)");
+}
+
+pub struct _0xDDCB9E {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCB9E {
+    // Resets bit 3 of the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, RegisterOperations::change_bit(3, components.mem.read_u8(addr), false));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 9E", "RES 3,(", "+*1)");
+}
+
+pub struct _0xDDCBA6 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCBA6 {
+    // Resets bit 4 of the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, RegisterOperations::change_bit(4, components.mem.read_u8(addr), false));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 A6", "RES 4,(", "+*1)");
+}
+
+pub struct _0xDDCBAE {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCBAE {
+    // Resets bit 5 of the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, RegisterOperations::change_bit(5, components.mem.read_u8(addr), false));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 AE", "RES 5,(", "+*1)");
+}
+
+pub struct _0xDDCBB6 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCBB6 {
+    // Resets bit 6 of the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, RegisterOperations::change_bit(6, components.mem.read_u8(addr), false));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 B6", "RES 6,(", "+*1)");
+}
+
+pub struct _0xDDCBBE {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCBBE {
+    // Resets bit 7 of the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, RegisterOperations::change_bit(7, components.mem.read_u8(addr), false));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 BE", "RES 7,(", "+*1)");
+}
+
+pub struct _0xDDCBC6 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCBC6 {
+    // Sets bit 0 of the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, RegisterOperations::change_bit(0, components.mem.read_u8(addr), true));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 C6", "SET 0,(", "+*1)");
+}
+
+pub struct _0xDDCBCE {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCBCE {
+    // Sets bit 1 of the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, RegisterOperations::change_bit(1, components.mem.read_u8(addr), true));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 CE", "SET 1,(", "+*1)");
+}
+
+pub struct _0xDDCBD6 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCBD6 {
+    // Sets bit 2 of the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, RegisterOperations::change_bit(2, components.mem.read_u8(addr), true));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 D6", "SET 2,(", "+*1)");
+}
+
+pub struct _0xDDCBDE {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCBDE {
+    // Sets bit 3 of the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, RegisterOperations::change_bit(3, components.mem.read_u8(addr), true));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 DE", "SET 3,(", "+*1)");
+}
+
+pub struct _0xDDCBE6 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCBE6 {
+    // Sets bit 4 of the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, RegisterOperations::change_bit(4, components.mem.read_u8(addr), true));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 E6", "SET 4,(", "+*1)");
+}
+
+pub struct _0xDDCBEE {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCBEE {
+    // Sets bit 5 of the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, RegisterOperations::change_bit(5, components.mem.read_u8(addr), true));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 EE", "SET 5,(", "+*1)");
+}
+
+pub struct _0xDDCBF6 {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCBF6 {
+    // Sets bit 6 of the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, RegisterOperations::change_bit(6, components.mem.read_u8(addr), true));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 F6", "SET 6,(", "+*1)");
+}
+
+pub struct _0xDDCBFE {
+    pub index: IndexRegister
+}
+impl Instruction for _0xDDCBFE {
+    // Sets bit 7 of the memory location pointed to by the index register plus d.
+    fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16 {
+        match operands {
+            Operands::Relative(offset) => {
+                let addr = RegisterOperations::indexed_address(self.index.get(&components.registers), offset);
+                components.mem.write_u8(addr, RegisterOperations::change_bit(7, components.mem.read_u8(addr), true));
+            }
+            _ => error!("Wrong operands used for {}", self.assembly()),
+        }
+        23
+    }
+
+    indexed_inst_metadata!(0, "CB *1 FE", "SET 7,(", "+*1)");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::instruction_set::{Instruction, Operands};
+    use crate::memory::{Memory, Registers, AddressBus, DataBus, Register, FlagValue};
+    use crate::runtime::RuntimeComponents;
+    use super::{_0xDD21, _0xDD7E, _0xDD86, _0xDD36, _0xDD35, _0xDDCB86, _0xDDCB46, IndexRegister};
+
+    fn runtime_components() -> RuntimeComponents {
+        RuntimeComponents { mem: Memory::default(), registers: Registers::default(), address_bus: AddressBus { value: 0 }, data_bus: DataBus::default() }
+    }
+
+    #[test]
+    fn ld_ix_nn() {
+        let mut components = runtime_components();
+
+        let cycles = _0xDD21 { index: IndexRegister::IX }.execute(&mut components, Operands::Word(0xC000));
+
+        assert_eq!(cycles, 14);
+        assert_eq!(components.registers.ix, 0xC000);
+        assert_eq!(components.registers.iy, 0x0000);
+    }
+
+    #[test]
+    fn ld_a_ix_plus_d() {
+        let mut components = runtime_components();
+        components.registers.ix = 0x4000;
+        components.mem.locations[0x4002] = 0x5A;
+
+        let cycles = _0xDD7E { index: IndexRegister::IX }.execute(&mut components, Operands::Relative(2));
+
+        assert_eq!(cycles, 19);
+        assert_eq!(components.registers.a.get(), 0x5A);
+    }
+
+    #[test]
+    fn add_a_ix_minus_d() {
+        let mut components = runtime_components();
+        components.registers.ix = 0x4000;
+        components.mem.locations[0x3FFF] = 0x0F;
+        components.registers.a.set(0x01);
+
+        _0xDD86 { index: IndexRegister::IX }.execute(&mut components, Operands::Relative(-1));
+
+        assert_eq!(components.registers.a.get(), 0x10);
+        assert!(components.registers.f.get_half_carry() == FlagValue::Set);
+        assert!(components.registers.f.get_carry() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn ld_ix_plus_d_n_then_dec() {
+        let mut components = runtime_components();
+        components.registers.ix = 0x4080;
+
+        _0xDD36 { index: IndexRegister::IX }.execute(&mut components, Operands::IndexedImmediate(-0x80, 0x01));
+        assert_eq!(components.mem.read_u8(0x4000), 0x01);
+
+        let cycles = _0xDD35 { index: IndexRegister::IX }.execute(&mut components, Operands::Relative(-0x80));
+        assert_eq!(cycles, 23);
+        assert_eq!(components.mem.read_u8(0x4000), 0x00);
+        assert!(components.registers.f.get_zero() == FlagValue::Set);
+    }
+
+    #[test]
+    fn res_0_ix_plus_d() {
+        let mut components = runtime_components();
+        components.registers.ix = 0x4000;
+        components.mem.locations[0x4001] = 0xFF;
+
+        let cycles = _0xDDCB86 { index: IndexRegister::IX }.execute(&mut components, Operands::Relative(1));
+
+        assert_eq!(cycles, 23);
+        assert_eq!(components.mem.read_u8(0x4001), 0xFE);
+    }
+
+    #[test]
+    fn bit_0_iy_plus_d() {
+        let mut components = runtime_components();
+        components.registers.iy = 0x4000;
+        components.mem.locations[0x3FFE] = 0xFE;
+
+        let instruction = _0xDDCB46 { index: IndexRegister::IY };
+        assert_eq!(instruction.execute(&mut components, Operands::Relative(-2)), 20);
+        assert!(components.registers.f.get_zero() == FlagValue::Set);
+        assert_eq!(instruction.assembly(), "BIT 0,(IY+*1)");
+        assert_eq!(instruction.machine_code(), "FD CB *1 46");
+    }
+}
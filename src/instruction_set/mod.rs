@@ -3,24 +3,97 @@ pub mod extended;
 pub mod index;
 pub mod bit;
 
+#[cfg(test)]
+mod fuse_tests;
+
 use crate::{memory::{Memory, Registers, DataBus, AddressBus}, runtime::{Runtime, RuntimeComponents}};
+use crate::utils::split_double_byte;
 
 use std::collections::HashMap;
+use std::fmt;
 use log::{debug, error, log_enabled, info, Level};
 
 use self::{extended::*, basic::*, index::*, bit::*};
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum Operands {
     None,
-    One(u8),
-    Two(u8, u8)
+    // An unsigned 8-bit value, e.g. the n in LD B,n.
+    Immediate(u8),
+    // A signed displacement, e.g. the d in JR d. Already converted from two's complement.
+    Relative(i8),
+    // A 16-bit value or address, already assembled from its little-endian bytes.
+    Word(u16),
+    // An index displacement followed by an immediate byte, e.g. the d and n in LD (IX+d),n.
+    IndexedImmediate(i8, u8)
+}
+
+// Renders an instruction fetched from address as its machine code and assembly. The machine code
+// is the bytes as they sit in memory, since not every template has placeholders for its operands.
+// In the assembly, *1 and *2 become the operands in hex: a Word's low and high bytes, an index
+// displacement with its sign, e.g. (IX-02), and a relative jump's absolute target.
+pub fn render(instruction: &dyn Instruction, operands: &Operands, address: u16, bytes: &[u8]) -> (String, String) {
+    let machine_code = bytes.iter().map(|byte| format!("{:0>2X}", byte)).collect::<Vec<_>>().join(" ");
+    let displacement = |offset: i8| format!("{}{:0>2X}", if offset < 0 { '-' } else { '+' }, offset.unsigned_abs());
+    let assembly = instruction.assembly();
+    let assembly = match *operands {
+        Operands::None => assembly.to_string(),
+        Operands::Immediate(value) => assembly.replace("*1", &format!("{:0>2X}", value)),
+        Operands::Relative(offset) if assembly.contains("+*1") => assembly.replace("+*1", &displacement(offset)),
+        Operands::Relative(offset) => {
+            let next_address = address.wrapping_add(bytes.len() as u16);
+            assembly.replace("*1", &format!("{:0>4X}", next_address.wrapping_add(offset as u16)))
+        }
+        Operands::Word(value) => {
+            let (high, low) = split_double_byte(value);
+            assembly.replace("*1", &format!("{:0>2X}", low)).replace("*2", &format!("{:0>2X}", high))
+        }
+        Operands::IndexedImmediate(offset, value) => assembly.replace("+*1", &displacement(offset)).replace("*2", &format!("{:0>2X}", value))
+    };
+    (machine_code, assembly)
+}
+
+// An opcode with no implementation, along with the prefix bytes it followed
+// (none for the basic set, e.g. DD CB for an IX bit instruction).
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct UnknownOpcodeError {
+    pub prefix: &'static [u8],
+    pub opcode: u8
+}
+
+impl fmt::Display for UnknownOpcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unimplemented instruction:")?;
+        for byte in self.prefix {
+            write!(f, " #{:02X}", byte)?;
+        }
+        write!(f, " #{:02X}", self.opcode)
+    }
+}
+
+// Describes how the bytes following an opcode should be packaged into Operands.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum OperandKind {
+    None,
+    Immediate8,
+    Relative8,
+    Word16,
+    IndexedImmediate8
 }
 
 
 pub trait Instruction {
     fn execute(&self, components: &mut RuntimeComponents, operands: Operands) -> u16;
     fn operand_count(&self) -> u8;
+    // Instructions whose operand isn't a plain immediate byte or word (e.g. JR's signed
+    // displacement) override this; everything else is derived from operand_count.
+    fn operand_kind(&self) -> OperandKind {
+        match self.operand_count() {
+            1 => OperandKind::Immediate8,
+            2 => OperandKind::Word16,
+            _ => OperandKind::None
+        }
+    }
     fn machine_code(&self) -> &str;
     fn assembly(&self) -> &str;
 }
@@ -28,8 +101,13 @@ pub trait Instruction {
 pub struct InstructionSet {
     basic_instructions: HashMap<u8, Box<dyn Instruction>>,
     extended_instructions: HashMap<u8, Box<dyn Instruction>>,
+    // DD and FD instructions, which share implementations but work on IX and IY respectively.
     index_instructions: HashMap<u8, Box<dyn Instruction>>,
-    bit_instructions: HashMap<u8, Box<dyn Instruction>>
+    iy_instructions: HashMap<u8, Box<dyn Instruction>>,
+    bit_instructions: HashMap<u8, Box<dyn Instruction>>,
+    // DD CB and FD CB instructions, keyed by the opcode byte that follows the displacement.
+    ix_bit_instructions: HashMap<u8, Box<dyn Instruction>>,
+    iy_bit_instructions: HashMap<u8, Box<dyn Instruction>>
 }
 
 macro_rules! instruction_set_map {
@@ -120,7 +198,174 @@ impl InstructionSet {
             0x29 => _0x29{},
             0xFE => _0xFE{},
             0x41 => _0x41{},
-            0xF8 => _0xF8{}
+            0xF8 => _0xF8{},
+            0x40 => _0x40{},
+            0x42 => _0x42{},
+            0x43 => _0x43{},
+            0x44 => _0x44{},
+            0x45 => _0x45{},
+            0x46 => _0x46{},
+            0x48 => _0x48{},
+            0x49 => _0x49{},
+            0x4A => _0x4A{},
+            0x4B => _0x4B{},
+            0x4D => _0x4D{},
+            0x4F => _0x4F{},
+            0x50 => _0x50{},
+            0x51 => _0x51{},
+            0x52 => _0x52{},
+            0x53 => _0x53{},
+            0x54 => _0x54{},
+            0x55 => _0x55{},
+            0x57 => _0x57{},
+            0x58 => _0x58{},
+            0x59 => _0x59{},
+            0x5A => _0x5A{},
+            0x5B => _0x5B{},
+            0x5C => _0x5C{},
+            0x5D => _0x5D{},
+            0x5F => _0x5F{},
+            0x60 => _0x60{},
+            0x61 => _0x61{},
+            0x62 => _0x62{},
+            0x63 => _0x63{},
+            0x64 => _0x64{},
+            0x65 => _0x65{},
+            0x66 => _0x66{},
+            0x68 => _0x68{},
+            0x69 => _0x69{},
+            0x6A => _0x6A{},
+            0x6B => _0x6B{},
+            0x6C => _0x6C{},
+            0x6D => _0x6D{},
+            0x6E => _0x6E{},
+            0x74 => _0x74{},
+            0x75 => _0x75{},
+            0x7A => _0x7A{},
+            0x7B => _0x7B{},
+            0x7F => _0x7F{},
+            0x80 => _0x80{},
+            0x81 => _0x81{},
+            0x82 => _0x82{},
+            0x83 => _0x83{},
+            0x84 => _0x84{},
+            0x85 => _0x85{},
+            0x86 => _0x86{},
+            0x87 => _0x87{},
+            0x88 => _0x88{},
+            0x89 => _0x89{},
+            0x8A => _0x8A{},
+            0x8B => _0x8B{},
+            0x8C => _0x8C{},
+            0x8D => _0x8D{},
+            0x8E => _0x8E{},
+            0x8F => _0x8F{},
+            0x90 => _0x90{},
+            0x91 => _0x91{},
+            0x92 => _0x92{},
+            0x93 => _0x93{},
+            0x94 => _0x94{},
+            0x95 => _0x95{},
+            0x96 => _0x96{},
+            0x97 => _0x97{},
+            0x98 => _0x98{},
+            0x99 => _0x99{},
+            0x9A => _0x9A{},
+            0x9B => _0x9B{},
+            0x9C => _0x9C{},
+            0x9D => _0x9D{},
+            0x9E => _0x9E{},
+            0x9F => _0x9F{},
+            0xA0 => _0xA0{},
+            0xA1 => _0xA1{},
+            0xA2 => _0xA2{},
+            0xA3 => _0xA3{},
+            0xA4 => _0xA4{},
+            0xA5 => _0xA5{},
+            0xA6 => _0xA6{},
+            0xA7 => _0xA7{},
+            0xA8 => _0xA8{},
+            0xAA => _0xAA{},
+            0xAB => _0xAB{},
+            0xAC => _0xAC{},
+            0xAD => _0xAD{},
+            0xAE => _0xAE{},
+            0xB0 => _0xB0{},
+            0xB1 => _0xB1{},
+            0xB2 => _0xB2{},
+            0xB3 => _0xB3{},
+            0xB4 => _0xB4{},
+            0xB5 => _0xB5{},
+            0xB8 => _0xB8{},
+            0xB9 => _0xB9{},
+            0xBA => _0xBA{},
+            0xBC => _0xBC{},
+            0xBD => _0xBD{},
+            0xBE => _0xBE{},
+            0xBF => _0xBF{},
+            0x14 => _0x14{},
+            0x1C => _0x1C{},
+            0x24 => _0x24{},
+            0x2C => _0x2C{},
+            0x34 => _0x34{},
+            0x15 => _0x15{},
+            0x1D => _0x1D{},
+            0x25 => _0x25{},
+            0x35 => _0x35{},
+            0x3D => _0x3D{},
+            0x16 => _0x16{},
+            0x1E => _0x1E{},
+            0x26 => _0x26{},
+            0x2E => _0x2E{},
+            0xC7 => _0xC7{},
+            0xCF => _0xCF{},
+            0xD7 => _0xD7{},
+            0xDF => _0xDF{},
+            0xE7 => _0xE7{},
+            0xEF => _0xEF{},
+            0xF7 => _0xF7{},
+            0xFF => _0xFF{},
+            0xC4 => _0xC4{},
+            0xCC => _0xCC{},
+            0xD4 => _0xD4{},
+            0xDC => _0xDC{},
+            0xE4 => _0xE4{},
+            0xEC => _0xEC{},
+            0xF4 => _0xF4{},
+            0xFC => _0xFC{},
+            0xCA => _0xCA{},
+            0xD2 => _0xD2{},
+            0xDA => _0xDA{},
+            0xE2 => _0xE2{},
+            0xEA => _0xEA{},
+            0xFA => _0xFA{},
+            0xD0 => _0xD0{},
+            0xE0 => _0xE0{},
+            0xE8 => _0xE8{},
+            0xF0 => _0xF0{},
+            0x28 => _0x28{},
+            0x38 => _0x38{},
+            0x33 => _0x33{},
+            0x3B => _0x3B{},
+            0x39 => _0x39{},
+            0xF9 => _0xF9{},
+            0x02 => _0x02{},
+            0x0A => _0x0A{},
+            0x12 => _0x12{},
+            0x27 => _0x27{},
+            0x0F => _0x0F{},
+            0x17 => _0x17{},
+            0x1F => _0x1F{},
+            0xE3 => _0xE3{},
+            0xE9 => _0xE9{},
+            0xF1 => _0xF1{},
+            0xE1 => _0xE1{},
+            0xC6 => _0xC6{},
+            0xCE => _0xCE{},
+            0xEE => _0xEE{},
+            0xF6 => _0xF6{},
+            0x76 => _0x76{},
+            0x2A => _0x2A{}
         ];
 
         let mut extended_instruction_set = instruction_set_map![
@@ -130,58 +375,492 @@ impl InstructionSet {
             0x56 => _0xED56{},
             0x46 => _0xED46{},
             0xB0 => _0xEDB0{},
-            0x5B => _0xED5B{}
+            0x5B => _0xED5B{},
+            0xA0 => _0xEDA0{},
+            0xA8 => _0xEDA8{},
+            0xB8 => _0xEDB8{},
+            0xA1 => _0xEDA1{},
+            0xA9 => _0xEDA9{},
+            0xB1 => _0xEDB1{},
+            0xB9 => _0xEDB9{},
+            0xA2 => _0xEDA2{},
+            0xA3 => _0xEDA3{},
+            0xAA => _0xEDAA{},
+            0xAB => _0xEDAB{},
+            0xB2 => _0xEDB2{},
+            0xB3 => _0xEDB3{},
+            0xBA => _0xEDBA{},
+            0xBB => _0xEDBB{},
+            0x42 => _0xED42{},
+            0x4A => _0xED4A{},
+            0x52 => _0xED52{},
+            0x5A => _0xED5A{},
+            0x62 => _0xED62{},
+            0x6A => _0xED6A{},
+            0x72 => _0xED72{},
+            0x7A => _0xED7A{},
+            0x43 => _0xED43{},
+            0x4B => _0xED4B{},
+            0x53 => _0xED53{},
+            0x63 => _0xED63{},
+            0x6B => _0xED6B{},
+            0x73 => _0xED73{},
+            0x7B => _0xED7B{},
+            0x44 => _0xED44{},
+            0x45 => _0xED45{},
+            0x4D => _0xED4D{},
+            0x5E => _0xED5E{},
+            0x67 => _0xED67{},
+            0x6F => _0xED6F{},
+            0x40 => _0xED40{},
+            0x41 => _0xED41{},
+            0x48 => _0xED48{},
+            0x50 => _0xED50{},
+            0x51 => _0xED51{},
+            0x58 => _0xED58{},
+            0x59 => _0xED59{},
+            0x60 => _0xED60{},
+            0x61 => _0xED61{},
+            0x68 => _0xED68{},
+            0x69 => _0xED69{},
+            0x70 => _0xED70{},
+            0x71 => _0xED71{},
+            0x47 => _0xED47{},
+            0x4F => _0xED4F{},
+            0x57 => _0xED57{},
+            0x5F => _0xED5F{}
         ];
 
         let mut index_instruction_set = instruction_set_map![
-            0xE5 => _0xDDE5{},
-            0xE1 => _0xDDE1{}
+            0xE5 => _0xDDE5{ index: IndexRegister::IX },
+            0xE1 => _0xDDE1{ index: IndexRegister::IX },
+            0x21 => _0xDD21{ index: IndexRegister::IX },
+            0x34 => _0xDD34{ index: IndexRegister::IX },
+            0x35 => _0xDD35{ index: IndexRegister::IX },
+            0x36 => _0xDD36{ index: IndexRegister::IX },
+            0x46 => _0xDD46{ index: IndexRegister::IX },
+            0x4E => _0xDD4E{ index: IndexRegister::IX },
+            0x56 => _0xDD56{ index: IndexRegister::IX },
+            0x5E => _0xDD5E{ index: IndexRegister::IX },
+            0x66 => _0xDD66{ index: IndexRegister::IX },
+            0x6E => _0xDD6E{ index: IndexRegister::IX },
+            0x70 => _0xDD70{ index: IndexRegister::IX },
+            0x71 => _0xDD71{ index: IndexRegister::IX },
+            0x72 => _0xDD72{ index: IndexRegister::IX },
+            0x73 => _0xDD73{ index: IndexRegister::IX },
+            0x74 => _0xDD74{ index: IndexRegister::IX },
+            0x75 => _0xDD75{ index: IndexRegister::IX },
+            0x77 => _0xDD77{ index: IndexRegister::IX },
+            0x7E => _0xDD7E{ index: IndexRegister::IX },
+            0x86 => _0xDD86{ index: IndexRegister::IX },
+            0x8E => _0xDD8E{ index: IndexRegister::IX },
+            0x96 => _0xDD96{ index: IndexRegister::IX },
+            0x9E => _0xDD9E{ index: IndexRegister::IX },
+            0xA6 => _0xDDA6{ index: IndexRegister::IX },
+            0xAE => _0xDDAE{ index: IndexRegister::IX },
+            0xB6 => _0xDDB6{ index: IndexRegister::IX },
+            0xBE => _0xDDBE{ index: IndexRegister::IX }
+        ];
+
+        let mut iy_instruction_set = instruction_set_map![
+            0xE5 => _0xDDE5{ index: IndexRegister::IY },
+            0xE1 => _0xDDE1{ index: IndexRegister::IY },
+            0x21 => _0xDD21{ index: IndexRegister::IY },
+            0x34 => _0xDD34{ index: IndexRegister::IY },
+            0x35 => _0xDD35{ index: IndexRegister::IY },
+            0x36 => _0xDD36{ index: IndexRegister::IY },
+            0x46 => _0xDD46{ index: IndexRegister::IY },
+            0x4E => _0xDD4E{ index: IndexRegister::IY },
+            0x56 => _0xDD56{ index: IndexRegister::IY },
+            0x5E => _0xDD5E{ index: IndexRegister::IY },
+            0x66 => _0xDD66{ index: IndexRegister::IY },
+            0x6E => _0xDD6E{ index: IndexRegister::IY },
+            0x70 => _0xDD70{ index: IndexRegister::IY },
+            0x71 => _0xDD71{ index: IndexRegister::IY },
+            0x72 => _0xDD72{ index: IndexRegister::IY },
+            0x73 => _0xDD73{ index: IndexRegister::IY },
+            0x74 => _0xDD74{ index: IndexRegister::IY },
+            0x75 => _0xDD75{ index: IndexRegister::IY },
+            0x77 => _0xDD77{ index: IndexRegister::IY },
+            0x7E => _0xDD7E{ index: IndexRegister::IY },
+            0x86 => _0xDD86{ index: IndexRegister::IY },
+            0x8E => _0xDD8E{ index: IndexRegister::IY },
+            0x96 => _0xDD96{ index: IndexRegister::IY },
+            0x9E => _0xDD9E{ index: IndexRegister::IY },
+            0xA6 => _0xDDA6{ index: IndexRegister::IY },
+            0xAE => _0xDDAE{ index: IndexRegister::IY },
+            0xB6 => _0xDDB6{ index: IndexRegister::IY },
+            0xBE => _0xDDBE{ index: IndexRegister::IY }
         ];
 
         let mut bit_instruction_set = instruction_set_map![
-            0x38 => _0xCB38{}
+            0x00 => _0xCB00{},
+            0x01 => _0xCB01{},
+            0x02 => _0xCB02{},
+            0x03 => _0xCB03{},
+            0x04 => _0xCB04{},
+            0x05 => _0xCB05{},
+            0x06 => _0xCB06{},
+            0x07 => _0xCB07{},
+            0x08 => _0xCB08{},
+            0x09 => _0xCB09{},
+            0x0A => _0xCB0A{},
+            0x0B => _0xCB0B{},
+            0x0C => _0xCB0C{},
+            0x0D => _0xCB0D{},
+            0x0E => _0xCB0E{},
+            0x0F => _0xCB0F{},
+            0x10 => _0xCB10{},
+            0x11 => _0xCB11{},
+            0x12 => _0xCB12{},
+            0x13 => _0xCB13{},
+            0x14 => _0xCB14{},
+            0x15 => _0xCB15{},
+            0x16 => _0xCB16{},
+            0x17 => _0xCB17{},
+            0x18 => _0xCB18{},
+            0x19 => _0xCB19{},
+            0x1A => _0xCB1A{},
+            0x1B => _0xCB1B{},
+            0x1C => _0xCB1C{},
+            0x1D => _0xCB1D{},
+            0x1E => _0xCB1E{},
+            0x1F => _0xCB1F{},
+            0x20 => _0xCB20{},
+            0x21 => _0xCB21{},
+            0x22 => _0xCB22{},
+            0x23 => _0xCB23{},
+            0x24 => _0xCB24{},
+            0x25 => _0xCB25{},
+            0x26 => _0xCB26{},
+            0x27 => _0xCB27{},
+            0x28 => _0xCB28{},
+            0x29 => _0xCB29{},
+            0x2A => _0xCB2A{},
+            0x2B => _0xCB2B{},
+            0x2C => _0xCB2C{},
+            0x2D => _0xCB2D{},
+            0x2E => _0xCB2E{},
+            0x2F => _0xCB2F{},
+            0x30 => _0xCB30{},
+            0x31 => _0xCB31{},
+            0x32 => _0xCB32{},
+            0x33 => _0xCB33{},
+            0x34 => _0xCB34{},
+            0x35 => _0xCB35{},
+            0x36 => _0xCB36{},
+            0x37 => _0xCB37{},
+            0x38 => _0xCB38{},
+            0x39 => _0xCB39{},
+            0x3A => _0xCB3A{},
+            0x3B => _0xCB3B{},
+            0x3C => _0xCB3C{},
+            0x3D => _0xCB3D{},
+            0x3E => _0xCB3E{},
+            0x3F => _0xCB3F{},
+            0x40 => _0xCB40{},
+            0x41 => _0xCB41{},
+            0x42 => _0xCB42{},
+            0x43 => _0xCB43{},
+            0x44 => _0xCB44{},
+            0x45 => _0xCB45{},
+            0x46 => _0xCB46{},
+            0x47 => _0xCB47{},
+            0x48 => _0xCB48{},
+            0x49 => _0xCB49{},
+            0x4A => _0xCB4A{},
+            0x4B => _0xCB4B{},
+            0x4C => _0xCB4C{},
+            0x4D => _0xCB4D{},
+            0x4E => _0xCB4E{},
+            0x4F => _0xCB4F{},
+            0x50 => _0xCB50{},
+            0x51 => _0xCB51{},
+            0x52 => _0xCB52{},
+            0x53 => _0xCB53{},
+            0x54 => _0xCB54{},
+            0x55 => _0xCB55{},
+            0x56 => _0xCB56{},
+            0x57 => _0xCB57{},
+            0x58 => _0xCB58{},
+            0x59 => _0xCB59{},
+            0x5A => _0xCB5A{},
+            0x5B => _0xCB5B{},
+            0x5C => _0xCB5C{},
+            0x5D => _0xCB5D{},
+            0x5E => _0xCB5E{},
+            0x5F => _0xCB5F{},
+            0x60 => _0xCB60{},
+            0x61 => _0xCB61{},
+            0x62 => _0xCB62{},
+            0x63 => _0xCB63{},
+            0x64 => _0xCB64{},
+            0x65 => _0xCB65{},
+            0x66 => _0xCB66{},
+            0x67 => _0xCB67{},
+            0x68 => _0xCB68{},
+            0x69 => _0xCB69{},
+            0x6A => _0xCB6A{},
+            0x6B => _0xCB6B{},
+            0x6C => _0xCB6C{},
+            0x6D => _0xCB6D{},
+            0x6E => _0xCB6E{},
+            0x6F => _0xCB6F{},
+            0x70 => _0xCB70{},
+            0x71 => _0xCB71{},
+            0x72 => _0xCB72{},
+            0x73 => _0xCB73{},
+            0x74 => _0xCB74{},
+            0x75 => _0xCB75{},
+            0x76 => _0xCB76{},
+            0x77 => _0xCB77{},
+            0x78 => _0xCB78{},
+            0x79 => _0xCB79{},
+            0x7A => _0xCB7A{},
+            0x7B => _0xCB7B{},
+            0x7C => _0xCB7C{},
+            0x7D => _0xCB7D{},
+            0x7E => _0xCB7E{},
+            0x7F => _0xCB7F{},
+            0x80 => _0xCB80{},
+            0x81 => _0xCB81{},
+            0x82 => _0xCB82{},
+            0x83 => _0xCB83{},
+            0x84 => _0xCB84{},
+            0x85 => _0xCB85{},
+            0x86 => _0xCB86{},
+            0x87 => _0xCB87{},
+            0x88 => _0xCB88{},
+            0x89 => _0xCB89{},
+            0x8A => _0xCB8A{},
+            0x8B => _0xCB8B{},
+            0x8C => _0xCB8C{},
+            0x8D => _0xCB8D{},
+            0x8E => _0xCB8E{},
+            0x8F => _0xCB8F{},
+            0x90 => _0xCB90{},
+            0x91 => _0xCB91{},
+            0x92 => _0xCB92{},
+            0x93 => _0xCB93{},
+            0x94 => _0xCB94{},
+            0x95 => _0xCB95{},
+            0x96 => _0xCB96{},
+            0x97 => _0xCB97{},
+            0x98 => _0xCB98{},
+            0x99 => _0xCB99{},
+            0x9A => _0xCB9A{},
+            0x9B => _0xCB9B{},
+            0x9C => _0xCB9C{},
+            0x9D => _0xCB9D{},
+            0x9E => _0xCB9E{},
+            0x9F => _0xCB9F{},
+            0xA0 => _0xCBA0{},
+            0xA1 => _0xCBA1{},
+            0xA2 => _0xCBA2{},
+            0xA3 => _0xCBA3{},
+            0xA4 => _0xCBA4{},
+            0xA5 => _0xCBA5{},
+            0xA6 => _0xCBA6{},
+            0xA7 => _0xCBA7{},
+            0xA8 => _0xCBA8{},
+            0xA9 => _0xCBA9{},
+            0xAA => _0xCBAA{},
+            0xAB => _0xCBAB{},
+            0xAC => _0xCBAC{},
+            0xAD => _0xCBAD{},
+            0xAE => _0xCBAE{},
+            0xAF => _0xCBAF{},
+            0xB0 => _0xCBB0{},
+            0xB1 => _0xCBB1{},
+            0xB2 => _0xCBB2{},
+            0xB3 => _0xCBB3{},
+            0xB4 => _0xCBB4{},
+            0xB5 => _0xCBB5{},
+            0xB6 => _0xCBB6{},
+            0xB7 => _0xCBB7{},
+            0xB8 => _0xCBB8{},
+            0xB9 => _0xCBB9{},
+            0xBA => _0xCBBA{},
+            0xBB => _0xCBBB{},
+            0xBC => _0xCBBC{},
+            0xBD => _0xCBBD{},
+            0xBE => _0xCBBE{},
+            0xBF => _0xCBBF{},
+            0xC0 => _0xCBC0{},
+            0xC1 => _0xCBC1{},
+            0xC2 => _0xCBC2{},
+            0xC3 => _0xCBC3{},
+            0xC4 => _0xCBC4{},
+            0xC5 => _0xCBC5{},
+            0xC6 => _0xCBC6{},
+            0xC7 => _0xCBC7{},
+            0xC8 => _0xCBC8{},
+            0xC9 => _0xCBC9{},
+            0xCA => _0xCBCA{},
+            0xCB => _0xCBCB{},
+            0xCC => _0xCBCC{},
+            0xCD => _0xCBCD{},
+            0xCE => _0xCBCE{},
+            0xCF => _0xCBCF{},
+            0xD0 => _0xCBD0{},
+            0xD1 => _0xCBD1{},
+            0xD2 => _0xCBD2{},
+            0xD3 => _0xCBD3{},
+            0xD4 => _0xCBD4{},
+            0xD5 => _0xCBD5{},
+            0xD6 => _0xCBD6{},
+            0xD7 => _0xCBD7{},
+            0xD8 => _0xCBD8{},
+            0xD9 => _0xCBD9{},
+            0xDA => _0xCBDA{},
+            0xDB => _0xCBDB{},
+            0xDC => _0xCBDC{},
+            0xDD => _0xCBDD{},
+            0xDE => _0xCBDE{},
+            0xDF => _0xCBDF{},
+            0xE0 => _0xCBE0{},
+            0xE1 => _0xCBE1{},
+            0xE2 => _0xCBE2{},
+            0xE3 => _0xCBE3{},
+            0xE4 => _0xCBE4{},
+            0xE5 => _0xCBE5{},
+            0xE6 => _0xCBE6{},
+            0xE7 => _0xCBE7{},
+            0xE8 => _0xCBE8{},
+            0xE9 => _0xCBE9{},
+            0xEA => _0xCBEA{},
+            0xEB => _0xCBEB{},
+            0xEC => _0xCBEC{},
+            0xED => _0xCBED{},
+            0xEE => _0xCBEE{},
+            0xEF => _0xCBEF{},
+            0xF0 => _0xCBF0{},
+            0xF1 => _0xCBF1{},
+            0xF2 => _0xCBF2{},
+            0xF3 => _0xCBF3{},
+            0xF4 => _0xCBF4{},
+            0xF5 => _0xCBF5{},
+            0xF6 => _0xCBF6{},
+            0xF7 => _0xCBF7{},
+            0xF8 => _0xCBF8{},
+            0xF9 => _0xCBF9{},
+            0xFA => _0xCBFA{},
+            0xFB => _0xCBFB{},
+            0xFC => _0xCBFC{},
+            0xFD => _0xCBFD{},
+            0xFE => _0xCBFE{},
+            0xFF => _0xCBFF{}
+        ];
+
+        let mut ix_bit_instruction_set = instruction_set_map![
+            0x06 => _0xDDCB06{ index: IndexRegister::IX },
+            0x0E => _0xDDCB0E{ index: IndexRegister::IX },
+            0x16 => _0xDDCB16{ index: IndexRegister::IX },
+            0x1E => _0xDDCB1E{ index: IndexRegister::IX },
+            0x26 => _0xDDCB26{ index: IndexRegister::IX },
+            0x2E => _0xDDCB2E{ index: IndexRegister::IX },
+            0x36 => _0xDDCB36{ index: IndexRegister::IX },
+            0x3E => _0xDDCB3E{ index: IndexRegister::IX },
+            0x46 => _0xDDCB46{ index: IndexRegister::IX },
+            0x4E => _0xDDCB4E{ index: IndexRegister::IX },
+            0x56 => _0xDDCB56{ index: IndexRegister::IX },
+            0x5E => _0xDDCB5E{ index: IndexRegister::IX },
+            0x66 => _0xDDCB66{ index: IndexRegister::IX },
+            0x6E => _0xDDCB6E{ index: IndexRegister::IX },
+            0x76 => _0xDDCB76{ index: IndexRegister::IX },
+            0x7E => _0xDDCB7E{ index: IndexRegister::IX },
+            0x86 => _0xDDCB86{ index: IndexRegister::IX },
+            0x8E => _0xDDCB8E{ index: IndexRegister::IX },
+            0x96 => _0xDDCB96{ index: IndexRegister::IX },
+            0x9E => _0xDDCB9E{ index: IndexRegister::IX },
+            0xA6 => _0xDDCBA6{ index: IndexRegister::IX },
+            0xAE => _0xDDCBAE{ index: IndexRegister::IX },
+            0xB6 => _0xDDCBB6{ index: IndexRegister::IX },
+            0xBE => _0xDDCBBE{ index: IndexRegister::IX },
+            0xC6 => _0xDDCBC6{ index: IndexRegister::IX },
+            0xCE => _0xDDCBCE{ index: IndexRegister::IX },
+            0xD6 => _0xDDCBD6{ index: IndexRegister::IX },
+            0xDE => _0xDDCBDE{ index: IndexRegister::IX },
+            0xE6 => _0xDDCBE6{ index: IndexRegister::IX },
+            0xEE => _0xDDCBEE{ index: IndexRegister::IX },
+            0xF6 => _0xDDCBF6{ index: IndexRegister::IX },
+            0xFE => _0xDDCBFE{ index: IndexRegister::IX }
+        ];
+
+        let mut iy_bit_instruction_set = instruction_set_map![
+            0x06 => _0xDDCB06{ index: IndexRegister::IY },
+            0x0E => _0xDDCB0E{ index: IndexRegister::IY },
+            0x16 => _0xDDCB16{ index: IndexRegister::IY },
+            0x1E => _0xDDCB1E{ index: IndexRegister::IY },
+            0x26 => _0xDDCB26{ index: IndexRegister::IY },
+            0x2E => _0xDDCB2E{ index: IndexRegister::IY },
+            0x36 => _0xDDCB36{ index: IndexRegister::IY },
+            0x3E => _0xDDCB3E{ index: IndexRegister::IY },
+            0x46 => _0xDDCB46{ index: IndexRegister::IY },
+            0x4E => _0xDDCB4E{ index: IndexRegister::IY },
+            0x56 => _0xDDCB56{ index: IndexRegister::IY },
+            0x5E => _0xDDCB5E{ index: IndexRegister::IY },
+            0x66 => _0xDDCB66{ index: IndexRegister::IY },
+            0x6E => _0xDDCB6E{ index: IndexRegister::IY },
+            0x76 => _0xDDCB76{ index: IndexRegister::IY },
+            0x7E => _0xDDCB7E{ index: IndexRegister::IY },
+            0x86 => _0xDDCB86{ index: IndexRegister::IY },
+            0x8E => _0xDDCB8E{ index: IndexRegister::IY },
+            0x96 => _0xDDCB96{ index: IndexRegister::IY },
+            0x9E => _0xDDCB9E{ index: IndexRegister::IY },
+            0xA6 => _0xDDCBA6{ index: IndexRegister::IY },
+            0xAE => _0xDDCBAE{ index: IndexRegister::IY },
+            0xB6 => _0xDDCBB6{ index: IndexRegister::IY },
+            0xBE => _0xDDCBBE{ index: IndexRegister::IY },
+            0xC6 => _0xDDCBC6{ index: IndexRegister::IY },
+            0xCE => _0xDDCBCE{ index: IndexRegister::IY },
+            0xD6 => _0xDDCBD6{ index: IndexRegister::IY },
+            0xDE => _0xDDCBDE{ index: IndexRegister::IY },
+            0xE6 => _0xDDCBE6{ index: IndexRegister::IY },
+            0xEE => _0xDDCBEE{ index: IndexRegister::IY },
+            0xF6 => _0xDDCBF6{ index: IndexRegister::IY },
+            0xFE => _0xDDCBFE{ index: IndexRegister::IY }
         ];
 
         InstructionSet { 
             basic_instructions: basic_instruction_set,
             extended_instructions: extended_instruction_set,
             index_instructions: index_instruction_set,
-            bit_instructions: bit_instruction_set
+            iy_instructions: iy_instruction_set,
+            bit_instructions: bit_instruction_set,
+            ix_bit_instructions: ix_bit_instruction_set,
+            iy_bit_instructions: iy_bit_instruction_set
         }
 
     }
 
-    pub fn instruction_for(&self, byte: u8) -> &Box<dyn Instruction> {
-        return &*self.basic_instructions.get(&byte).unwrap_or_else( || {
-            // Stop immediately so that the instruction can be identified and implemented.
-            error!("Unimplemented basic instruction: #{:02X?}", byte);
-            std::process::exit(1);
-        });
+    pub fn instruction_for(&self, byte: u8) -> Result<&dyn Instruction, UnknownOpcodeError> {
+        InstructionSet::lookup(&self.basic_instructions, &[], byte)
     }
 
-    pub fn extended_instruction_for(&self, byte: u8) -> &Box<dyn Instruction> {
-        return self.extended_instructions.get(&byte).unwrap_or_else(|| {
-            // Stop immediately so that the instruction can be identified and implemented.
-            error!("Unimplemented extended instruction: #{:02X?}", byte);
-            std::process::exit(1);
-        });
+    pub fn extended_instruction_for(&self, byte: u8) -> Result<&dyn Instruction, UnknownOpcodeError> {
+        InstructionSet::lookup(&self.extended_instructions, &[0xED], byte)
     }
 
-    pub fn index_instruction_for(&self, byte: u8) -> &Box<dyn Instruction> {
-        return self.index_instructions.get(&byte).unwrap_or_else(|| {
-            // Stop immediately so that the instruction can be identified and implemented.
-            error!("Unimplemented index instruction: #{:02X?}", byte);
-            std::process::exit(1);
-        });
+    pub fn index_instruction_for(&self, index: IndexRegister, byte: u8) -> Result<&dyn Instruction, UnknownOpcodeError> {
+        match index {
+            IndexRegister::IX => InstructionSet::lookup(&self.index_instructions, &[0xDD], byte),
+            IndexRegister::IY => InstructionSet::lookup(&self.iy_instructions, &[0xFD], byte)
+        }
     }
 
-    pub fn bit_instruction_for(&self, byte: u8) -> &Box<dyn Instruction> {
-        return self.bit_instructions.get(&byte).unwrap_or_else(|| {
-            // Stop immediately so that the instruction can be identified and implemented.
-            error!("Unimplemented bit instruction: #{:02X?}", byte);
-            std::process::exit(1);
-        });
+    pub fn indexed_bit_instruction_for(&self, index: IndexRegister, byte: u8) -> Result<&dyn Instruction, UnknownOpcodeError> {
+        match index {
+            IndexRegister::IX => InstructionSet::lookup(&self.ix_bit_instructions, &[0xDD, 0xCB], byte),
+            IndexRegister::IY => InstructionSet::lookup(&self.iy_bit_instructions, &[0xFD, 0xCB], byte)
+        }
     }
 
+    pub fn bit_instruction_for(&self, byte: u8) -> Result<&dyn Instruction, UnknownOpcodeError> {
+        InstructionSet::lookup(&self.bit_instructions, &[0xCB], byte)
+    }
 
-}
\ No newline at end of file
+    fn lookup<'a>(instructions: &'a HashMap<u8, Box<dyn Instruction>>, prefix: &'static [u8], byte: u8) -> Result<&'a dyn Instruction, UnknownOpcodeError> {
+        instructions.get(&byte).map(|instruction| instruction.as_ref()).ok_or(UnknownOpcodeError { prefix, opcode: byte })
+    }
+}
@@ -0,0 +1,287 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use crate::ppi::Ppi;
+
+///////////////////////
+//
+// Keyboard - host keys to the CPC key matrix
+//
+///////////////////////
+
+// Every key on the CPC keyboard, in matrix order: eight to a row, from bit 0. The numeric keypad
+// is the function keys F0-F9, with its own full stop and ENTER. Both SHIFT keys share one matrix
+// position. Row 9 is the joystick apart from DEL in bit 7.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CpcKey {
+    CursorUp, CursorRight, CursorDown, F9, F6, F3, Enter, FDot,
+    CursorLeft, Copy, F7, F8, F5, F1, F2, F0,
+    Clr, LeftBracket, Return, RightBracket, F4, Shift, Backslash, Control,
+    Caret, Minus, At, P, Semicolon, Colon, Slash, Period,
+    Num0, Num9, O, I, L, K, M, Comma,
+    Num8, Num7, U, Y, H, J, N, Space,
+    Num6, Num5, R, T, G, F, B, V,
+    Num4, Num3, E, W, S, D, C, X,
+    Num1, Num2, Esc, Q, Tab, A, CapsLock, Z,
+    Del
+}
+
+impl CpcKey {
+    // The key's (row, column) in the matrix the PPI scans.
+    pub fn position(self) -> (usize, usize) {
+        if self == CpcKey::Del {
+            return (9, 7);
+        }
+        (self as usize / 8, self as usize % 8)
+    }
+}
+
+// A key as a front end sees it: either the character it types, or a key that doesn't type one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HostKey {
+    Char(char),
+    Up, Down, Left, Right,
+    Return, Escape, Tab, CapsLock, Backspace, Delete,
+    Shift, Control,
+    // COPY has no host equivalent, so it goes on Alt.
+    Alt,
+    Home,
+    // Keypad digits, full stop and enter map to the CPC's keypad (the function keys).
+    Keypad(u8), KeypadPeriod, KeypadEnter
+}
+
+// The CPC keys to hold down for a host key. Letters ignore case, as the host's SHIFT arrives
+// separately, but symbols add SHIFT where the CPC needs it: '"' is SHIFT 2 on a CPC but not on
+// most host layouts.
+pub fn cpc_keys(key: HostKey) -> Vec<CpcKey> {
+    use CpcKey::*;
+    let single = match key {
+        HostKey::Up => CursorUp,
+        HostKey::Down => CursorDown,
+        HostKey::Left => CursorLeft,
+        HostKey::Right => CursorRight,
+        HostKey::Return => Return,
+        HostKey::Escape => Esc,
+        HostKey::Tab => Tab,
+        HostKey::CapsLock => CapsLock,
+        HostKey::Backspace => Del,
+        HostKey::Delete | HostKey::Home => Clr,
+        HostKey::Shift => Shift,
+        HostKey::Control => Control,
+        HostKey::Alt => Copy,
+        HostKey::Keypad(digit) => match digit {
+            0 => F0, 1 => F1, 2 => F2, 3 => F3, 4 => F4, 5 => F5, 6 => F6, 7 => F7, 8 => F8, 9 => F9,
+            _ => return vec![]
+        },
+        HostKey::KeypadPeriod => FDot,
+        HostKey::KeypadEnter => Enter,
+        HostKey::Char(c) => return char_keys(c)
+    };
+    vec![single]
+}
+
+fn char_keys(c: char) -> Vec<CpcKey> {
+    use CpcKey::*;
+    let letter = match c.to_ascii_lowercase() {
+        'a' => Some(A), 'b' => Some(B), 'c' => Some(C), 'd' => Some(D), 'e' => Some(E), 'f' => Some(F),
+        'g' => Some(G), 'h' => Some(H), 'i' => Some(I), 'j' => Some(J), 'k' => Some(K), 'l' => Some(L),
+        'm' => Some(M), 'n' => Some(N), 'o' => Some(O), 'p' => Some(P), 'q' => Some(Q), 'r' => Some(R),
+        's' => Some(S), 't' => Some(T), 'u' => Some(U), 'v' => Some(V), 'w' => Some(W), 'x' => Some(X),
+        'y' => Some(Y), 'z' => Some(Z),
+        _ => None
+    };
+    if let Some(key) = letter {
+        return vec![key];
+    }
+    let (key, shifted) = match c {
+        '0' => (Num0, false), '_' => (Num0, true),
+        '1' => (Num1, false), '!' => (Num1, true),
+        '2' => (Num2, false), '"' => (Num2, true),
+        '3' => (Num3, false), '#' => (Num3, true),
+        '4' => (Num4, false), '$' => (Num4, true),
+        '5' => (Num5, false), '%' => (Num5, true),
+        '6' => (Num6, false), '&' => (Num6, true),
+        '7' => (Num7, false), '\'' => (Num7, true),
+        '8' => (Num8, false), '(' => (Num8, true),
+        '9' => (Num9, false), ')' => (Num9, true),
+        '-' => (Minus, false), '=' => (Minus, true),
+        '^' => (Caret, false), '£' => (Caret, true),
+        '@' => (At, false), '|' => (At, true),
+        '[' => (LeftBracket, false), '{' => (LeftBracket, true),
+        ']' => (RightBracket, false), '}' => (RightBracket, true),
+        ';' => (Semicolon, false), '+' => (Semicolon, true),
+        ':' => (Colon, false), '*' => (Colon, true),
+        ',' => (Comma, false), '<' => (Comma, true),
+        '.' => (Period, false), '>' => (Period, true),
+        '/' => (Slash, false), '?' => (Slash, true),
+        '\\' => (Backslash, false), '`' => (Backslash, true),
+        ' ' => (Space, false),
+        '\n' | '\r' => (Return, false),
+        '\t' => (Tab, false),
+        _ => return vec![]
+    };
+    if shifted { vec![Shift, key] } else { vec![key] }
+}
+
+// Presses and releases keys on the PPI's matrix, e.g. from a window's key events.
+pub struct Keyboard {
+    ppi: Rc<RefCell<Ppi>>
+}
+
+impl Keyboard {
+    pub fn new(ppi: Rc<RefCell<Ppi>>) -> Keyboard {
+        Keyboard { ppi }
+    }
+
+    // Returns false for keys with no CPC equivalent.
+    pub fn press(&mut self, key: HostKey) -> bool {
+        self.set(key, true)
+    }
+
+    pub fn release(&mut self, key: HostKey) -> bool {
+        self.set(key, false)
+    }
+
+    pub fn set_cpc_key(&mut self, key: CpcKey, pressed: bool) {
+        let (row, column) = key.position();
+        self.ppi.borrow_mut().set_key(row, column, pressed);
+    }
+
+    fn set(&mut self, key: HostKey, pressed: bool) -> bool {
+        let keys = cpc_keys(key);
+        for cpc_key in &keys {
+            self.set_cpc_key(*cpc_key, pressed);
+        }
+        !keys.is_empty()
+    }
+}
+
+// Joystick 0 is wired into row 9 of the key matrix, so it's read exactly like keys.
+pub const JOYSTICK_ROW: usize = 9;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FireButton {
+    Fire1,
+    Fire2
+}
+
+// Each switch closes its own matrix contact, so nothing stops opposite directions being held
+// at once: up and down together read as both pressed, as on a real joystick port.
+pub struct Joystick {
+    ppi: Rc<RefCell<Ppi>>
+}
+
+impl Joystick {
+    pub fn new(ppi: Rc<RefCell<Ppi>>) -> Joystick {
+        Joystick { ppi }
+    }
+
+    pub fn set_direction(&mut self, direction: Direction, pressed: bool) {
+        let column = match direction {
+            Direction::Up => 0,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Right => 3
+        };
+        self.ppi.borrow_mut().set_key(JOYSTICK_ROW, column, pressed);
+    }
+
+    pub fn set_fire(&mut self, button: FireButton, pressed: bool) {
+        let column = match button {
+            FireButton::Fire2 => 4,
+            FireButton::Fire1 => 5
+        };
+        self.ppi.borrow_mut().set_key(JOYSTICK_ROW, column, pressed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use std::cell::RefCell;
+
+    use crate::ppi::Ppi;
+    use super::{Keyboard, HostKey, CpcKey, cpc_keys, Joystick, Direction, FireButton, JOYSTICK_ROW};
+
+    fn keyboard() -> (Keyboard, Rc<RefCell<Ppi>>) {
+        let ppi = Rc::new(RefCell::new(Ppi::default()));
+        (Keyboard::new(ppi.clone()), ppi)
+    }
+
+    #[test]
+    fn pressing_a_sets_its_matrix_bit() {
+        let (mut keyboard, ppi) = keyboard();
+        assert!(keyboard.press(HostKey::Char('A')));
+        assert_eq!(ppi.borrow().keyboard_row(8), !0b0010_0000);
+        assert_eq!(ppi.borrow().keyboard_row(2), 0xFF); // no SHIFT
+
+        keyboard.release(HostKey::Char('A'));
+        assert_eq!(ppi.borrow().keyboard_row(8), 0xFF);
+    }
+
+    #[test]
+    fn shifted_symbols_press_shift() {
+        let (mut keyboard, ppi) = keyboard();
+        keyboard.press(HostKey::Char('"'));
+        assert_eq!(ppi.borrow().keyboard_row(8), !0b0000_0010); // 2
+        assert_eq!(ppi.borrow().keyboard_row(2), !0b0010_0000); // SHIFT
+
+        keyboard.release(HostKey::Char('"'));
+        assert_eq!(ppi.borrow().keyboard_row(2), 0xFF);
+    }
+
+    #[test]
+    fn matrix_positions() {
+        assert_eq!(CpcKey::CursorUp.position(), (0, 0));
+        assert_eq!(CpcKey::Copy.position(), (1, 1));
+        assert_eq!(CpcKey::Control.position(), (2, 7));
+        assert_eq!(CpcKey::Space.position(), (5, 7));
+        assert_eq!(CpcKey::Z.position(), (8, 7));
+        assert_eq!(CpcKey::Del.position(), (9, 7));
+    }
+
+    #[test]
+    fn keypad_and_special_keys() {
+        assert_eq!(cpc_keys(HostKey::Keypad(7)), vec![CpcKey::F7]);
+        assert_eq!(cpc_keys(HostKey::KeypadEnter), vec![CpcKey::Enter]);
+        assert_eq!(cpc_keys(HostKey::Return), vec![CpcKey::Return]);
+        assert_eq!(cpc_keys(HostKey::Alt), vec![CpcKey::Copy]);
+        assert_eq!(cpc_keys(HostKey::Backspace), vec![CpcKey::Del]);
+        assert_eq!(cpc_keys(HostKey::Char('€')), vec![]);
+        assert!(!keyboard().0.press(HostKey::Keypad(10)));
+    }
+
+    #[test]
+    fn fire1_is_row_9_bit_5() {
+        let ppi = Rc::new(RefCell::new(Ppi::default()));
+        let mut joystick = Joystick::new(ppi.clone());
+        joystick.set_fire(FireButton::Fire1, true);
+        assert_eq!(ppi.borrow().keyboard_row(JOYSTICK_ROW), !0b0010_0000);
+        joystick.set_fire(FireButton::Fire2, true);
+        assert_eq!(ppi.borrow().keyboard_row(JOYSTICK_ROW), !0b0011_0000);
+        joystick.set_fire(FireButton::Fire1, false);
+        joystick.set_fire(FireButton::Fire2, false);
+        assert_eq!(ppi.borrow().keyboard_row(JOYSTICK_ROW), 0xFF);
+    }
+
+    #[test]
+    fn opposite_directions_both_read() {
+        let ppi = Rc::new(RefCell::new(Ppi::default()));
+        let mut joystick = Joystick::new(ppi.clone());
+        joystick.set_direction(Direction::Up, true);
+        joystick.set_direction(Direction::Down, true);
+        joystick.set_direction(Direction::Right, true);
+        assert_eq!(ppi.borrow().keyboard_row(JOYSTICK_ROW), !0b0000_1011);
+
+        joystick.set_direction(Direction::Up, false);
+        assert_eq!(ppi.borrow().keyboard_row(JOYSTICK_ROW), !0b0000_1010);
+    }
+}
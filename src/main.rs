@@ -4,18 +4,32 @@ mod memory;
 mod screen;
 mod instruction_set;
 mod runtime;
+mod pacing;
+mod gate_array;
+mod crtc;
+mod psg;
+mod ppi;
+mod keyboard;
 mod dsk;
+mod amsdos;
+mod fdc;
+mod sna;
+mod disassembler;
+#[cfg(feature = "serde")]
+mod state;
 mod utils;
 
 use dsk::Dsk;
+use amsdos::{AmsdosHeader, FileType};
 use runtime::*;
 
 use std::io;
 use std::io::Read;
-use std::io::BufReader;
+use std::io::{BufReader, BufWriter};
 use std::fs::File;
+use std::path::Path;
 
-use clap::{Parser, Arg, App};
+use clap::{Parser, Arg, App, ArgMatches};
 use log::{debug, error, log_enabled, info, Level};
 
 
@@ -26,7 +40,69 @@ struct Arguments {
 
 fn main() -> io::Result<()> {
     env_logger::init();
-    let matches = App::new("CPC Emu")
+    let matches = cli().get_matches();
+
+    if matches.is_present("cat") {
+        let dsk = read_dsk(matches.get_one::<String>("dsk").unwrap().trim())?;
+        print!("{}", catalog_listing(&dsk));
+        return Ok(());
+    }
+
+    let (mut runtime, start_address) = runtime_from_args(&matches)?;
+
+    if let Some(path) = matches.get_one::<String>("trace") {
+        runtime.set_trace(Box::new(BufWriter::new(File::create(path)?)));
+    }
+
+    debug!("Running from #{:04X}...", start_address);
+    let result = run_until_stopped(&mut runtime, start_address, &matches);
+    save_on_exit(&runtime, &matches);
+
+    if let Err(e) = result {
+        // Stop so that the instruction can be identified and implemented.
+        error!("{}", e);
+        // exit skips destructors, so drop the runtime first to flush the trace.
+        drop(runtime);
+        std::process::exit(1);
+    }
+
+    Ok(())
+
+}
+
+// Runs for --cycles T-states if given, otherwise until an instruction can't be executed.
+fn run_until_stopped(runtime: &mut Runtime, start_address: u16, matches: &ArgMatches) -> Result<(), RuntimeError> {
+    match matches.get_one::<String>("cycles") {
+        Some(cycles) => {
+            let cycles = cycles.parse().expect("validated by clap");
+            let executed = runtime.run_for(start_address, cycles)?;
+            info!("Stopped after {} cycles", executed);
+            Ok(())
+        }
+        None => runtime.run(start_address)
+    }
+}
+
+// Saves whatever --screenshot-on-exit and --save-snapshot-on-exit ask for. Failures are only
+// logged so that one doesn't stop the other.
+fn save_on_exit(runtime: &Runtime, matches: &ArgMatches) {
+    if let Some(path) = matches.get_one::<String>("screenshot-on-exit") {
+        match runtime.screenshot(Path::new(path)) {
+            Ok(()) => info!("Saved screenshot to {}", path),
+            Err(e) => error!("Couldn't save screenshot to {}: {}", path, e)
+        }
+    }
+
+    if let Some(path) = matches.get_one::<String>("save-snapshot-on-exit") {
+        match std::fs::write(path, runtime.save_snapshot()) {
+            Ok(()) => info!("Saved snapshot to {}", path),
+            Err(e) => error!("Couldn't save snapshot to {}: {}", path, e)
+        }
+    }
+}
+
+fn cli() -> App<'static> {
+    App::new("CPC Emu")
         .version("0.1.0")
         .author("aidano")
         .about("Amstrad CPC Emulator")
@@ -34,61 +110,283 @@ fn main() -> io::Result<()> {
                  .short('d')
                  .long("dsk")
                  .takes_value(true)
-                 .required(true)
                  .help("DSK file to launch"))
         .arg(Arg::with_name("rom")
         .short('r')
         .long("rom")
         .takes_value(true)
-        .required(true)
-        .help("ROM file to use"))
-        .get_matches();
-    
-    let file_name: &str = matches.get_one::<String>("dsk").unwrap().trim();
+        .help("ROM file to use. Without one, RAM shows through where the ROMs would be"))
+        .arg(Arg::with_name("sna")
+        .long("sna")
+        .takes_value(true)
+        .conflicts_with("bin")
+        .help("SNA snapshot to resume from"))
+        .arg(Arg::with_name("bin")
+        .long("bin")
+        .takes_value(true)
+        .help("Binary to load and run. An AMSDOS header gives its load and entry addresses, otherwise both are #0000"))
+        .arg(Arg::with_name("start")
+        .long("start")
+        .takes_value(true)
+        .value_name("ADDRESS")
+        .validator(parse_address)
+        .help("Address to start running from, in hex with an optional 0x, & or # prefix. Overrides a snapshot or binary's own"))
+        .arg(Arg::with_name("cat")
+        .long("cat")
+        .requires("dsk")
+        .help("List the files on the DSK instead of running the emulator"))
+        .arg(Arg::with_name("unthrottled")
+        .long("unthrottled")
+        .help("Run as fast as possible instead of at the CPC's real speed"))
+        .arg(Arg::with_name("cycles")
+        .long("cycles")
+        .takes_value(true)
+        .value_name("N")
+        .validator(|text| text.parse::<u64>())
+        .help("Stop after running N T-states"))
+        .arg(Arg::with_name("screenshot-on-exit")
+        .long("screenshot-on-exit")
+        .takes_value(true)
+        .value_name("PNG")
+        .help("Save the final screen to a PNG file when emulation stops, after --cycles or on an error"))
+        .arg(Arg::with_name("save-snapshot-on-exit")
+        .long("save-snapshot-on-exit")
+        .takes_value(true)
+        .value_name("SNA")
+        .help("Save the machine's state to an SNA snapshot when emulation stops, after --cycles or on an error"))
+        .arg(Arg::with_name("trace")
+        .long("trace")
+        .takes_value(true)
+        .value_name("FILE")
+        .help("Write every instruction executed, with the registers after it, to a file"))
+}
+
+// Builds the machine the arguments describe, returning it along with the address to run from:
+// --start if given, otherwise the snapshot's PC, the binary's entry address, or #0000 to boot the ROM.
+fn runtime_from_args(matches: &ArgMatches) -> io::Result<(Runtime, u16)> {
+    let mut runtime = Runtime::default();
+    runtime.throttle = !matches.is_present("unthrottled");
+
+    if let Some(file_name) = matches.get_one::<String>("dsk") {
+        runtime.fdc.borrow_mut().insert_disc(read_dsk(file_name.trim())?);
+    }
+
+    if let Some(rom_file_name) = matches.get_one::<String>("rom") {
+        let rom_file_name = rom_file_name.trim();
+        debug!("loading rom: {} ...", rom_file_name);
+
+        let buffer = read_file(rom_file_name)?;
+        debug!("Read {} bytes\n", buffer.len());
+        validate_rom_bytes(&buffer).map_err(invalid_data)?;
+        runtime.load_rom_from_bytes(buffer.as_slice()).map_err(invalid_data)?;
+    }
+
+    let mut start_address = 0x0000;
+    if let Some(file_name) = matches.get_one::<String>("sna") {
+        let buffer = read_file(file_name.trim())?;
+        runtime.load_snapshot(&buffer).map_err(|msg| invalid_data(msg.to_string()))?;
+        start_address = runtime.components.registers.pc.get();
+    }
+    if let Some(file_name) = matches.get_one::<String>("bin") {
+        start_address = load_bin(&mut runtime, &read_file(file_name.trim())?);
+    }
+    if let Some(address) = matches.get_one::<String>("start") {
+        start_address = parse_address(address).map_err(invalid_data)?;
+    }
+    Ok((runtime, start_address))
+}
+
+// Parses a hex address written any of the usual ways: C000, 0xC000, &C000 or #C000.
+fn parse_address(text: &str) -> Result<u16, String> {
+    let text = text.trim();
+    let digits = ["0x", "0X", "&", "#"].iter()
+        .find_map(|prefix| text.strip_prefix(prefix))
+        .unwrap_or(text);
+    u16::from_str_radix(digits, 16).map_err(|_| format!("Invalid address: {}", text))
+}
 
+fn read_dsk(file_name: &str) -> io::Result<Dsk> {
     debug!("loading file: {} ...", file_name);
 
+    let buffer = read_file(file_name)?;
+    debug!("File: read {} bytes\n", buffer.len());
+    validate_dsk_bytes(&buffer).map_err(invalid_data)?;
+    Dsk::init_from_bytes(buffer.as_slice()).map_err(|msg| invalid_data(msg.to_string()))
+}
+
+// Pokes a binary into memory, returning its entry address. A file saved from AMSDOS carries a
+// header saying where it goes; anything else is taken to be a raw image starting at #0000.
+fn load_bin(runtime: &mut Runtime, bytes: &[u8]) -> u16 {
+    let (load_address, entry_address, data) = match AmsdosHeader::from_bytes(bytes) {
+        Some(header) => {
+            let data = &bytes[amsdos::HEADER_SIZE..];
+            (header.load_address, header.entry_address, &data[..header.length.min(data.len())])
+        }
+        None => (0x0000, 0x0000, bytes)
+    };
+    debug!("loading {} byte binary at #{:04X}", data.len(), load_address);
+    for (offset, byte) in data.iter().enumerate() {
+        runtime.poke(load_address.wrapping_add(offset as u16), *byte);
+    }
+    entry_address
+}
+
+// Lists user 0's files as CAT does: alphabetically, leaving out system files, with the space
+// each takes rounded up to whole kilobytes.
+fn catalog_listing(dsk: &Dsk) -> String {
+    let mut entries: Vec<_> = dsk.catalog().into_iter().filter(|entry| entry.user == 0 && !entry.system).collect();
+    entries.sort_by(|a, b| (&a.name, &a.extension).cmp(&(&b.name, &b.extension)));
+
+    let mut listing = String::from("Drive A: user  0\n\n");
+    for entry in &entries {
+        let file_type = match dsk.file_header(&entry.file_name()).map(|header| header.file_type) {
+            Some(FileType::Basic) => "BASIC".to_string(),
+            Some(FileType::ProtectedBasic) => "PROTECTED".to_string(),
+            Some(FileType::Binary) => "BINARY".to_string(),
+            Some(FileType::Other(code)) => format!("TYPE {}", code),
+            None => "ASCII".to_string()
+        };
+        let kilobytes = entry.size().div_ceil(1024);
+        listing.push_str(&format!("{:8}.{:3}  {:9} {:>3}K\n", entry.name, entry.extension, file_type, kilobytes));
+    }
+    listing
+}
+
+fn read_file(file_name: &str) -> io::Result<Vec<u8>> {
     let f = File::open(file_name)?;
     let mut reader = BufReader::new(f);
     let mut buffer = Vec::new();
-    
-    match (reader.read_to_end(&mut buffer)) {
-        Ok(bytes) => {
-            debug!("File: read {} bytes\n", bytes);
-            let _ = Dsk::init_from_bytes(buffer.as_slice());
-        },
-        Err(code) =>  {
-            error!("Error reading dsk: {:?}", code);
-        }
+    reader.read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    error!("{}", msg);
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+// A DSK image must at least contain its &100 byte Disc Information Block.
+const DSK_INFO_BLOCK_SIZE: usize = 0x100;
+// ROM images are either a 16K OS ROM or a 32K OS ROM followed by BASIC.
+const ROM_SIZES: [usize; 2] = [0x4000, 0x8000];
+
+fn validate_dsk_bytes(bytes: &[u8]) -> Result<(), String> {
+    if bytes.len() < DSK_INFO_BLOCK_SIZE {
+        return Err(format!("DSK file is too small: {} bytes, expected at least {}", bytes.len(), DSK_INFO_BLOCK_SIZE));
     }
+    Ok(())
+}
 
+fn validate_rom_bytes(bytes: &[u8]) -> Result<(), String> {
+    if !ROM_SIZES.contains(&bytes.len()) {
+        return Err(format!("ROM file has unexpected size: {} bytes, expected 16384 or 32768", bytes.len()));
+    }
+    Ok(())
+}
 
-    // Try out the runtime
+#[cfg(test)]
+mod tests {
+    use super::{validate_dsk_bytes, validate_rom_bytes, catalog_listing, cli, runtime_from_args, parse_address, run_until_stopped, save_on_exit};
+    use crate::amsdos::tests::{sample_disc, header};
+    use crate::memory::Register;
 
-    let mut runtime = Runtime::default();
+    #[test]
+    fn short_dsk_is_rejected() {
+        let msg = validate_dsk_bytes(&[0; 5]).unwrap_err();
+        assert_eq!(msg, "DSK file is too small: 5 bytes, expected at least 256");
+        assert!(validate_dsk_bytes(&[0; 0x100]).is_ok());
+    }
 
-    // Load the rom
-    let rom_file_name: &str = matches.get_one::<String>("rom").unwrap().trim();
+    #[test]
+    fn short_rom_is_rejected() {
+        let msg = validate_rom_bytes(&[0; 5]).unwrap_err();
+        assert_eq!(msg, "ROM file has unexpected size: 5 bytes, expected 16384 or 32768");
+        assert!(validate_rom_bytes(&[0; 0x4000]).is_ok());
+        assert!(validate_rom_bytes(&[0; 0x8000]).is_ok());
+    }
 
-    debug!("loading rom: {} ...", rom_file_name);
+    #[test]
+    fn cat_lists_files_alphabetically() {
+        let listing = catalog_listing(&sample_disc());
+        let lines: Vec<&str> = listing.lines().collect();
+        assert_eq!(lines, [
+            "Drive A: user  0",
+            "",
+            "BIG     .DAT  ASCII      17K",
+            "HELLO   .BIN  BINARY      2K",
+            "README  .TXT  ASCII       1K"
+        ]);
+    }
 
-    let f = File::open(rom_file_name)?;
-    let mut reader = BufReader::new(f);
-    let mut buffer = Vec::new();
-    
-    match (reader.read_to_end(&mut buffer)) {
-        Ok(bytes) => {
-            debug!("Read {} bytes\n", bytes);
-            let _ = runtime.load_rom_from_bytes(buffer.as_slice());
-        },
-        Err(code) =>  {
-            error!("Error reading dsk: {:?}", code);
+    #[test]
+    fn bin_alone_is_enough_to_run() {
+        let program = [0x3E, 0x42, 0xC9];
+        let mut bytes = header(2, 0x4000, 0x4001, program.len());
+        bytes.extend(program);
+        let path = std::env::temp_dir().join(format!("cpc-emu-bin-test-{}.bin", std::process::id()));
+        std::fs::write(&path, &bytes).unwrap();
+
+        let matches = cli().try_get_matches_from(["cpc-emu", "--bin", path.to_str().unwrap()]).unwrap();
+        let (runtime, start_address) = runtime_from_args(&matches).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(start_address, 0x4001);
+        assert_eq!(runtime.peek_range(0x4000, 3), program);
+    }
+
+    #[test]
+    fn conflicting_inputs_are_rejected() {
+        assert!(cli().try_get_matches_from(["cpc-emu", "--sna", "a.sna", "--bin", "a.bin"]).is_err());
+        // --cat has nothing to list without a disc.
+        assert!(cli().try_get_matches_from(["cpc-emu", "--cat"]).is_err());
+    }
+
+    #[test]
+    fn addresses_parse_with_any_prefix() {
+        for text in ["C000", "0xC000", "&C000", "#c000"] {
+            assert_eq!(parse_address(text), Ok(0xC000));
         }
+        assert_eq!(parse_address("&10000"), Err("Invalid address: &10000".to_string()));
+        assert_eq!(parse_address("&"), Err("Invalid address: &".to_string()));
     }
 
-    debug!("Running from #0000...");
-    runtime.run(0x0);
+    #[test]
+    fn start_chooses_where_to_run() {
+        let matches = cli().try_get_matches_from(["cpc-emu", "--start", "&C000"]).unwrap();
+        let (_, start_address) = runtime_from_args(&matches).unwrap();
+        assert_eq!(start_address, 0xC000);
 
-    Ok(())
+        let matches = cli().try_get_matches_from(["cpc-emu"]).unwrap();
+        let (_, start_address) = runtime_from_args(&matches).unwrap();
+        assert_eq!(start_address, 0x0000);
+
+        assert!(cli().try_get_matches_from(["cpc-emu", "--start", "&XYZ"]).is_err());
+    }
+
+    #[test]
+    fn cycles_stops_the_run_and_saves_on_exit() {
+        // LD A,#42 then JR back to it forever.
+        let program = [0x3E, 0x42, 0x18, 0xFC];
+        let mut bytes = header(2, 0x4000, 0x4000, program.len());
+        bytes.extend(program);
+        let dir = std::env::temp_dir();
+        let bin = dir.join(format!("cpc-emu-cycles-test-{}.bin", std::process::id()));
+        let sna = dir.join(format!("cpc-emu-cycles-test-{}.sna", std::process::id()));
+        std::fs::write(&bin, &bytes).unwrap();
+
+        let matches = cli().try_get_matches_from([
+            "cpc-emu", "--bin", bin.to_str().unwrap(), "--unthrottled", "--cycles", "1000",
+            "--save-snapshot-on-exit", sna.to_str().unwrap()
+        ]).unwrap();
+        let (mut runtime, start_address) = runtime_from_args(&matches).unwrap();
+        run_until_stopped(&mut runtime, start_address, &matches).unwrap();
+        save_on_exit(&runtime, &matches);
+        let snapshot = std::fs::read(&sna).unwrap();
+        std::fs::remove_file(&bin).unwrap();
+        std::fs::remove_file(&sna).unwrap();
 
-}
\ No newline at end of file
+        assert_eq!(runtime.components.registers.a.get(), 0x42);
+        assert_eq!(&snapshot[..8], b"MV - SNA");
+        assert!(cli().try_get_matches_from(["cpc-emu", "--cycles", "lots"]).is_err());
+    }
+}
@@ -1,14 +1,155 @@
-use std::{fmt, ops::Add};
-
-use crate::{utils::{split_double_byte, combine_to_double_byte}, instruction_set::Instruction};
-
+use std::{fmt, ops::Add, rc::Rc, cell::RefCell, collections::BTreeMap};
+
+use crate::{utils::{split_double_byte, combine_to_double_byte, parity}, instruction_set::Instruction};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+// The CPU sees 64K as four 16K blocks. A 6128 has eight blocks of RAM: the base 64K (0-3) and
+// a second 64K (4-7) that can be paged in through the expansion register at #7Fxx (data #C0-#FF,
+// of which bits 0-2 pick a configuration). Each configuration lists the RAM block that appears
+// at #0000, #4000, #8000 and #C000.
+const RAM_CONFIGS: [[usize; 4]; 8] = [
+    [0, 1, 2, 3],
+    [0, 1, 2, 7],
+    [4, 5, 6, 7],
+    [0, 3, 2, 7],
+    [0, 4, 2, 3],
+    [0, 5, 2, 3],
+    [0, 6, 2, 3],
+    [0, 7, 2, 3]
+];
+
+const BLOCK_SIZE: usize = 0x4000;
+
+// ROM sits over RAM rather than replacing it: the lower ROM at #0000-#3FFF and the upper ROM at
+// #C000-#FFFF are seen by reads only while the Gate Array has them enabled. Writes always go to
+// the RAM underneath.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Memory {
-    pub locations: [u8; 0xFFFF]
+    // The base 64K of RAM, which is also what the Gate Array displays. It's boxed to keep
+    // Memory small enough to move around by value.
+    #[cfg_attr(feature = "serde", serde(with = "crate::state::byte_array"))]
+    pub locations: Box<[u8; 0x10000]>,
+    // The 6128's second 64K.
+    pub expansion: Vec<u8>,
+    ram_config: usize,
+    lower_rom: Option<Vec<u8>>,
+    pub upper_roms: RomBank,
+    lower_rom_enabled: bool,
+    upper_rom_enabled: bool
 }
 
 impl Memory {
     pub fn default() -> Memory {
-        Memory { locations: [0x01; 0xFFFF] }
+        Memory {
+            locations: Box::new([0x01; 0x10000]), expansion: vec![0x01; 0x10000], ram_config: 0,
+            lower_rom: None, upper_roms: RomBank::default(), lower_rom_enabled: true, upper_rom_enabled: true
+        }
+    }
+
+    // ROM images are 16K; anything shorter is padded with #FF.
+    pub fn load_lower_rom(&mut self, bytes: &[u8]) {
+        self.lower_rom = Some(Memory::rom_image(bytes));
+    }
+
+    pub fn load_upper_rom(&mut self, slot: u8, bytes: &[u8]) {
+        self.upper_roms.insert(slot, Memory::rom_image(bytes));
+    }
+
+    pub fn set_rom_enables(&mut self, lower: bool, upper: bool) {
+        self.lower_rom_enabled = lower;
+        self.upper_rom_enabled = upper;
+    }
+
+    pub fn ram_config(&self) -> u8 {
+        self.ram_config as u8
+    }
+
+    // Selects one of the eight RAM configurations. Only the low 3 bits are used.
+    pub fn set_ram_config(&mut self, config: u8) {
+        self.ram_config = (config & 0x07) as usize;
+    }
+
+    // Reads the byte currently visible to the CPU at the given address.
+    pub fn read_u8(&self, addr: u16) -> u8 {
+        if let Some(rom) = self.rom_at(addr) {
+            return rom[addr as usize % BLOCK_SIZE];
+        }
+        let (block, offset) = self.ram_location(addr);
+        match block {
+            0..=3 => self.locations[block * BLOCK_SIZE + offset],
+            _ => self.expansion[(block - 4) * BLOCK_SIZE + offset]
+        }
+    }
+
+    // Writes to whichever RAM block is paged in at the given address, even where ROM is showing.
+    pub fn write_u8(&mut self, addr: u16, value: u8) {
+        let (block, offset) = self.ram_location(addr);
+        match block {
+            0..=3 => self.locations[block * BLOCK_SIZE + offset] = value,
+            _ => self.expansion[(block - 4) * BLOCK_SIZE + offset] = value
+        }
+    }
+
+    // Reads the little-endian word at the given address.
+    pub fn read_u16(&self, addr: u16) -> u16 {
+        combine_to_double_byte(self.read_u8(addr.wrapping_add(1)), self.read_u8(addr))
+    }
+
+    // The Gate Array fetches the screen from the base 64K whatever the CPU has paged in.
+    pub fn read_video(&self, addr: u16) -> u8 {
+        self.locations[addr as usize]
+    }
+
+    fn rom_at(&self, addr: u16) -> Option<&Vec<u8>> {
+        match addr {
+            0x0000..=0x3FFF if self.lower_rom_enabled => self.lower_rom.as_ref(),
+            0xC000..=0xFFFF if self.upper_rom_enabled => self.upper_roms.active(),
+            _ => None
+        }
+    }
+
+    fn rom_image(bytes: &[u8]) -> Vec<u8> {
+        let mut rom = vec![0xFF; BLOCK_SIZE];
+        let length = bytes.len().min(BLOCK_SIZE);
+        rom[..length].copy_from_slice(&bytes[..length]);
+        rom
+    }
+
+    fn ram_location(&self, addr: u16) -> (usize, usize) {
+        let addr = addr as usize;
+        (RAM_CONFIGS[self.ram_config][addr / BLOCK_SIZE], addr % BLOCK_SIZE)
+    }
+}
+
+// The upper ROMs, by slot number. Only one at a time overlays #C000, chosen by writing its number
+// to #DFxx. Slot 0 holds BASIC, and selecting a slot with nothing in it shows slot 0 instead, as
+// the firmware relies on when it probes for expansion ROMs.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RomBank {
+    roms: BTreeMap<u8, Vec<u8>>,
+    selected: u8
+}
+
+impl RomBank {
+    pub fn default() -> RomBank {
+        RomBank { roms: BTreeMap::new(), selected: 0 }
+    }
+
+    pub fn insert(&mut self, slot: u8, rom: Vec<u8>) {
+        self.roms.insert(slot, rom);
+    }
+
+    pub fn select(&mut self, slot: u8) {
+        self.selected = slot;
+    }
+
+    pub fn selected(&self) -> u8 {
+        self.selected
+    }
+
+    pub fn active(&self) -> Option<&Vec<u8>> {
+        self.roms.get(&self.selected).or_else(|| self.roms.get(&0))
     }
 }
 
@@ -17,6 +158,7 @@ pub trait Register {
     fn get(&self) -> u8;
     fn name(&self) -> &str;
 }
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DefaultRegister {
     name: String,
     value: u8
@@ -36,6 +178,7 @@ impl Register for DefaultRegister {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Accumulator {
     name: String,
     value: u8
@@ -57,93 +200,253 @@ impl Register for Accumulator {
 
 impl Accumulator {
     pub fn sub_reg<R : Register>(&mut self, reg: &R, flags: &mut FlagsRegister) {
-        self.set(self.get() - reg.get());
-        flags.set_parity_overflow( if reg.get() & 128 == 128 { FlagValue::Set } else { FlagValue::Unset });
+        self.sub_value(reg.get(), flags);
     }
 
+    // Subtract value from a, setting every flag from the result.
     pub fn sub_value(&mut self, value: u8, flags: &mut FlagsRegister) {
-        let carry = if (self.value as u32 + value as u32) > u16::MAX as u32 {
-            FlagValue::Set 
-           } else {
-                FlagValue::Unset 
-           };
-        self.set(self.get() - value);
-        flags.set_parity_overflow( if value & 128 == 128 { FlagValue::Set } else { FlagValue::Unset });
-        flags.set_carry(carry);
+        self.sub_with_borrow_in(value, 0, flags);
+    }
+
+    // Subtract the passed register and the carry flag from a
+    pub fn sbc_a<R : Register>(&mut self, reg: &R, flags: &mut FlagsRegister) {
+        self.sub_value_and_carry(reg.get(), flags);
     }
 
+    // Subtract value and the carry flag from a, setting every flag from the result.
     pub fn sub_value_and_carry(&mut self, value: u8, flags: &mut FlagsRegister) {
-        let value = value + if flags.get_carry() == FlagValue::Set { 1 } else { 0 };
-        self.sub_value(value, flags);
+        let borrow_in = 0 + flags.get_carry();
+        self.sub_with_borrow_in(value, borrow_in, flags);
+    }
+
+    fn sub_with_borrow_in(&mut self, value: u8, borrow_in: u8, flags: &mut FlagsRegister) {
+        let result = Accumulator::subtract(self.get(), value, borrow_in, flags);
+        self.set(result);
+    }
+
+    // Computes a - value - borrow_in and sets every flag from it, without storing the result.
+    fn subtract(a: u8, value: u8, borrow_in: u8, flags: &mut FlagsRegister) -> u8 {
+        let difference = a as i16 - value as i16 - borrow_in as i16;
+        let result = difference as u8;
+
+        flags.set_carry(if difference < 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_add_subtract(FlagValue::Set);
+        // Overflow when the operands have different signs and the result's sign differs from a.
+        flags.set_parity_overflow(if (a ^ value) & (a ^ result) & 0x80 != 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_half_carry(if ((a & 0x0F) as i16 - (value & 0x0F) as i16 - borrow_in as i16) < 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_zero(if result == 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_sign(if result & 0x80 == 0x80 { FlagValue::Set } else { FlagValue::Unset });
+        result
+    }
+
+    // Negates a by subtracting it from zero. Carry ends up set unless a was 0, and P/V only when a was #80.
+    pub fn neg(&mut self, flags: &mut FlagsRegister) {
+        let result = Accumulator::subtract(0, self.get(), 0, flags);
+        self.set(result);
     }
 
     pub fn and(&mut self, value: u8, flags: &mut FlagsRegister) {
         self.set(self.get() & value);
-        // todo: set flags
+        Accumulator::set_logic_flags(self.get(), FlagValue::Set, flags);
+    }
+
+    // AND, OR and XOR clear N and C, set zero and sign from the result and P/V from its parity.
+    // Only AND sets the half-carry.
+    fn set_logic_flags(result: u8, half_carry: FlagValue, flags: &mut FlagsRegister) {
         flags.set_carry(FlagValue::Unset);
         flags.set_add_subtract(FlagValue::Unset);
-        flags.set_half_carry(FlagValue::Set);
-
-        let overflow = if self.get() & 128 > 1 {
-            FlagValue::Set
-        } else {
-            FlagValue::Unset
-        };
-        flags.set_parity_overflow(overflow);
+        flags.set_half_carry(half_carry);
+        flags.set_parity_overflow(if parity(result) { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_zero(if result == 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_sign(if result & 0x80 == 0x80 { FlagValue::Set } else { FlagValue::Unset });
     }
 
     pub fn or<R : Register>(&mut self, reg: &R, flags: &mut FlagsRegister) {
-        self.set(self.get() | reg.get());
-        flags.set_parity_overflow( if reg.get() & 128 == 128 { FlagValue::Set } else { FlagValue::Unset });
+        self.or_value(reg.get(), flags);
+    }
+
+    pub fn or_value(&mut self, value: u8, flags: &mut FlagsRegister) {
+        self.set(self.get() | value);
+        Accumulator::set_logic_flags(self.get(), FlagValue::Unset, flags);
+    }
+
+    pub fn or_address_from_reg_pair<R : Register>(&mut self, mem: &Memory, reg_pair: (&R, &R), flags: &mut FlagsRegister) {
+        let location = combine_to_double_byte(reg_pair.0.get(), reg_pair.1.get());
+        let val = mem.read_u8(location);
+        self.or_value(val, flags);
     }
 
     pub fn or_a(&mut self, flags: &mut FlagsRegister) {
-        self.set(self.get() | self.get());
-        flags.set_parity_overflow( if self.get() & 128 == 128 { FlagValue::Set } else { FlagValue::Unset });
+        let value = self.get();
+        self.or_value(value, flags);
     }
 
 
     pub fn compare_reg<R: Register>(&self, reg: &R, flags: &mut FlagsRegister) {
-        flags.set_parity_overflow(if self.get() as i16 - (reg.get() as i16) < -128 { FlagValue::Set } else { FlagValue::Unset });        
+        self.compare_val(reg.get(), flags);
     }
 
+    // Subtracts val from a to set the flags, leaving a unchanged.
     pub fn compare_val(&self, val: u8, flags: &mut FlagsRegister) {
-        flags.set_parity_overflow(if self.get() as i16 - (val as i16) < -128 { FlagValue::Set } else { FlagValue::Unset });        
+        Accumulator::subtract(self.get(), val, 0, flags);
     }
 
     pub fn xor<R : Register>(&mut self, reg: &R, flags: &mut FlagsRegister) {
-        self.set(self.get() ^ reg.get());
-        flags.set_parity_overflow( if reg.get() & 128 == 128 { FlagValue::Set } else { FlagValue::Unset });
-        flags.set_zero(if self.value == 0 { FlagValue::Set } else { FlagValue::Unset });
-        flags.set_sign(if self.value & 128 == 128 { FlagValue::Set } else { FlagValue::Unset });
+        self.xor_value(reg.get(), flags);
+    }
+
+    pub fn xor_value(&mut self, value: u8, flags: &mut FlagsRegister) {
+        self.set(self.get() ^ value);
+        Accumulator::set_logic_flags(self.get(), FlagValue::Unset, flags);
     }
 
     pub fn xor_address_from_reg_pair<R : Register>(&mut self, mem: &Memory, reg_pair: (&R, &R), flags: &mut FlagsRegister) {
         let location = combine_to_double_byte(reg_pair.0.get(), reg_pair.1.get());
-        let val = mem.locations[location as usize];
-        self.set(self.get() ^ val);
-        flags.set_parity_overflow( if val & 128 == 128 { FlagValue::Set } else { FlagValue::Unset });
-        flags.set_zero(if self.value == 0 { FlagValue::Set } else { FlagValue::Unset });
-        flags.set_sign(if self.value & 128 == 128 { FlagValue::Set } else { FlagValue::Unset });
+        let val = mem.read_u8(location);
+        self.xor_value(val, flags);
     }
 
     pub fn xor_a(&mut self, flags: &mut FlagsRegister) {
-        self.set(self.get() ^ self.get());
-        flags.set_parity_overflow( if self.get() & 128 == 128 { FlagValue::Set } else { FlagValue::Unset });
+        let value = self.get();
+        self.xor_value(value, flags);
+    }
+
+    // Decimal adjusts a after a BCD addition or subtraction, using N, H and C to tell which
+    // correction the previous instruction needs.
+    pub fn daa(&mut self, flags: &mut FlagsRegister) {
+        let a = self.get();
+        let subtract = flags.get_add_subtract() == FlagValue::Set;
+        let half_carry = flags.get_half_carry() == FlagValue::Set;
+        let mut carry = flags.get_carry() == FlagValue::Set;
+
+        let mut correction = 0;
+        if half_carry || (a & 0x0F) > 0x09 {
+            correction |= 0x06;
+        }
+        if carry || a > 0x99 {
+            correction |= 0x60;
+            carry = true;
+        }
+
+        let result = if subtract { a.wrapping_sub(correction) } else { a.wrapping_add(correction) };
+        let new_half_carry = if subtract { half_carry && (a & 0x0F) < 0x06 } else { (a & 0x0F) > 0x09 };
+        self.set(result);
+
+        flags.set_carry(if carry { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_half_carry(if new_half_carry { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_parity_overflow(if parity(result) { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_zero(if result == 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_sign(if result & 0x80 == 0x80 { FlagValue::Set } else { FlagValue::Unset });
+    }
+
+    // Rotates a left one bit, copying bit 7 into both bit 0 and the carry flag.
+    pub fn rlca(&mut self, flags: &mut FlagsRegister) {
+        let a = self.get();
+        self.set(a.rotate_left(1));
+        Accumulator::set_rotate_flags(a & 0x80 == 0x80, flags);
+    }
+
+    // Rotates a right one bit, copying bit 0 into both bit 7 and the carry flag.
+    pub fn rrca(&mut self, flags: &mut FlagsRegister) {
+        let a = self.get();
+        self.set(a.rotate_right(1));
+        Accumulator::set_rotate_flags(a & 0x01 == 0x01, flags);
+    }
+
+    // Rotates a left through the carry flag: the old carry moves into bit 0 and bit 7 into carry.
+    pub fn rla(&mut self, flags: &mut FlagsRegister) {
+        let a = self.get();
+        self.set((a << 1) | (0 + flags.get_carry()));
+        Accumulator::set_rotate_flags(a & 0x80 == 0x80, flags);
+    }
+
+    // Rotates a right through the carry flag: the old carry moves into bit 7 and bit 0 into carry.
+    pub fn rra(&mut self, flags: &mut FlagsRegister) {
+        let a = self.get();
+        self.set((a >> 1) | ((0 + flags.get_carry()) << 7));
+        Accumulator::set_rotate_flags(a & 0x01 == 0x01, flags);
+    }
+
+    // The accumulator rotates only touch carry, H and N; sign, zero and parity are left alone.
+    fn set_rotate_flags(carry_out: bool, flags: &mut FlagsRegister) {
+        flags.set_carry(if carry_out { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_half_carry(FlagValue::Unset);
+        flags.set_add_subtract(FlagValue::Unset);
+    }
+
+    // Rotates the three nibbles formed by the low nibble of a and the byte at addr one nibble left:
+    // (addr) low -> (addr) high -> a low -> (addr) low.
+    pub fn rld(&mut self, mem: &mut Memory, addr: u16, flags: &mut FlagsRegister) {
+        let a = self.get();
+        let value = mem.read_u8(addr);
+        mem.write_u8(addr, (value << 4) | (a & 0x0F));
+        self.set((a & 0xF0) | (value >> 4));
+        Accumulator::set_nibble_rotate_flags(self.get(), flags);
+    }
+
+    // Rotates the same three nibbles one nibble right: a low -> (addr) high -> (addr) low -> a low.
+    pub fn rrd(&mut self, mem: &mut Memory, addr: u16, flags: &mut FlagsRegister) {
+        let a = self.get();
+        let value = mem.read_u8(addr);
+        mem.write_u8(addr, (a << 4) | (value >> 4));
+        self.set((a & 0xF0) | (value & 0x0F));
+        Accumulator::set_nibble_rotate_flags(self.get(), flags);
+    }
+
+    // LD A,I and LD A,R: loads value into a, setting sign and zero from it and copying IFF2 into P/V.
+    // H and N are cleared and carry is left alone.
+    pub fn ld_interrupt_state(&mut self, value: u8, iff2: bool, flags: &mut FlagsRegister) {
+        self.set(value);
+        flags.set_half_carry(FlagValue::Unset);
+        flags.set_add_subtract(FlagValue::Unset);
+        flags.set_parity_overflow(if iff2 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_zero(if value == 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_sign(if value & 0x80 == 0x80 { FlagValue::Set } else { FlagValue::Unset });
+    }
+
+    // RLD and RRD set sign, zero and parity from a, clear H and N and leave carry alone.
+    fn set_nibble_rotate_flags(result: u8, flags: &mut FlagsRegister) {
+        flags.set_half_carry(FlagValue::Unset);
+        flags.set_add_subtract(FlagValue::Unset);
+        flags.set_parity_overflow(if parity(result) { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_zero(if result == 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_sign(if result & 0x80 == 0x80 { FlagValue::Set } else { FlagValue::Unset });
     }
 
     // Add the passed register to a
     pub fn add_a<R : Register>(&mut self, reg: &R, flags: &mut FlagsRegister) {
-        let carry = flags.get_carry();
-        self.set(self.get() + reg.get()); // todo: read up on this.
-        flags.set_parity_overflow( if reg.get() & 128 == 128 { FlagValue::Set } else { FlagValue::Unset });
+        self.add_value(reg.get(), flags);
+    }
+
+    // Add value to a, setting every flag from the result.
+    pub fn add_value(&mut self, value: u8, flags: &mut FlagsRegister) {
+        self.add_with_carry_in(value, 0, flags);
+    }
+
+    // Add value and the carry flag to a, setting every flag from the result.
+    pub fn adc_value(&mut self, value: u8, flags: &mut FlagsRegister) {
+        let carry_in = 0 + flags.get_carry();
+        self.add_with_carry_in(value, carry_in, flags);
+    }
+
+    fn add_with_carry_in(&mut self, value: u8, carry_in: u8, flags: &mut FlagsRegister) {
+        let a = self.get();
+        let sum = a as u16 + value as u16 + carry_in as u16;
+        let result = sum as u8;
+        self.set(result);
+
+        flags.set_carry(if sum > 0xFF { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_add_subtract(FlagValue::Unset);
+        // Overflow when both operands have the same sign and the result's sign differs.
+        flags.set_parity_overflow(if (a ^ result) & (value ^ result) & 0x80 != 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_half_carry(if (a & 0x0F) + (value & 0x0F) + carry_in > 0x0F { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_zero(if result == 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_sign(if result & 0x80 == 0x80 { FlagValue::Set } else { FlagValue::Unset });
     }
 
     // Add the passed register and the carry flag to a
     pub fn adc_a<R : Register>(&mut self, reg: &R, flags: &mut FlagsRegister) {
-        let carry = flags.get_carry();
-        self.set(self.get() + reg.get() + carry); // todo: read up on this.
-        flags.set_parity_overflow( if reg.get() & 128 == 128 { FlagValue::Set } else { FlagValue::Unset });
+        self.adc_value(reg.get(), flags);
     }
 }
 
@@ -157,6 +460,7 @@ impl fmt::Debug for dyn Register {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FlagsRegister {
     value: u8
 }
@@ -183,6 +487,7 @@ impl FlagsRegister {
     // Bit	    7	6	5	4	3	2	1	0
     // Position	S	Z	/	H	/	P/V	N	C
     //
+    #[allow(clippy::assign_op_pattern)]
     pub fn set_carry(&mut self, value: FlagValue) {
         match value {
             FlagValue::Set => self.value = self.value | 1,
@@ -190,6 +495,7 @@ impl FlagsRegister {
         }
     }
 
+    #[allow(clippy::assign_op_pattern)]
     pub fn set_add_subtract(&mut self, value: FlagValue) {
         match value {
             FlagValue::Set => self.value = self.value | 2,
@@ -197,6 +503,7 @@ impl FlagsRegister {
         }
     }
 
+    #[allow(clippy::assign_op_pattern)]
     pub fn set_parity_overflow(&mut self, value: FlagValue) {
         match value {
             FlagValue::Set => self.value = self.value | 4,
@@ -204,6 +511,7 @@ impl FlagsRegister {
         }
     }
 
+    #[allow(clippy::assign_op_pattern)]
     pub fn set_half_carry(&mut self, value: FlagValue) {
         match value {
             FlagValue::Set => self.value = self.value | 16,
@@ -276,6 +584,7 @@ impl FlagsRegister {
 
 
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProgramCounter {
     value: u16
 }
@@ -290,14 +599,15 @@ impl ProgramCounter {
     }
 
     pub(crate) fn inc(&mut self) {
-        self.value = self.value + 1;
+        self.value = self.value.wrapping_add(1);
     }
 
     pub(crate) fn dec(&mut self) {
-        self.value = self.value - 1;
+        self.value = self.value.wrapping_sub(1);
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StackPointer {
     location: usize
 }
@@ -305,44 +615,142 @@ pub struct StackPointer {
 impl StackPointer {
     pub fn push(&mut self, memory: &mut Memory, value: u16) {
         let (high, low) = split_double_byte(value);
-        self.location -= 1;
-        memory.locations[self.location] = high;
-        self.location -= 1;
-        memory.locations[self.location] = low;
+        self.dec();
+        memory.write_u8(self.location as u16, high);
+        self.dec();
+        memory.write_u8(self.location as u16, low);
     }
 
     pub fn pop(&mut self, memory: &Memory) -> u16 {
-        let low = memory.locations[self.location];
-        self.location += 1;
-        let high = memory.locations[self.location];
-        self.location += 1;
+        let low = memory.read_u8(self.location as u16);
+        self.inc();
+        let high = memory.read_u8(self.location as u16);
+        self.inc();
         combine_to_double_byte(high, low)
     }
 
     pub fn set(&mut self, value: usize) {
         self.location = value;
     }
+
+    pub fn get(&self) -> usize {
+        self.location
+    }
+
+    // Adds one to SP, wrapping from #FFFF to #0000.
+    pub fn inc(&mut self) {
+        self.location = (self.location + 1) & 0xFFFF;
+    }
+
+    // Subtracts one from SP, wrapping from #0000 to #FFFF.
+    pub fn dec(&mut self) {
+        self.location = self.location.wrapping_sub(1) & 0xFFFF;
+    }
 }
 
 
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AddressBus {
     pub value: u16 // TODO: simple impl for now.
 }
 
-// TODO: This struct might actually represent both the address and the data bus, in which case the above struct can go away.
-pub struct DataBus {}
+// Anything attached to the CPC's I/O ports.
+pub trait PortDevice {
+    fn write(&mut self, port: u16, value: u8);
+    // Returns None when the device doesn't drive the data bus for this port.
+    fn read(&mut self, port: u16) -> Option<u8>;
+}
+
+// The CPC only partially decodes I/O addresses: each device is selected by one address line
+// being low (the Gate Array also needs A14 high), whatever the rest of the port number is.
+// A single OUT can therefore reach several devices at once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PortSelect {
+    GateArray,  // A15 low, A14 high, e.g. #7Fxx
+    Crtc,       // A14 low, e.g. #BCxx to #BFxx
+    RomSelect,  // A13 low, e.g. #DFxx
+    Printer,    // A12 low, e.g. #EFxx
+    Ppi,        // A11 low, e.g. #F4xx to #F7xx
+    Peripheral  // A10 low, e.g. the FDC at #FB7E / #FB7F
+}
+
+impl PortSelect {
+    pub fn selected_by(&self, port: u16) -> bool {
+        match self {
+            PortSelect::GateArray => port & 0xC000 == 0x4000,
+            PortSelect::Crtc => port & 0x4000 == 0,
+            PortSelect::RomSelect => port & 0x2000 == 0,
+            PortSelect::Printer => port & 0x1000 == 0,
+            PortSelect::Ppi => port & 0x0800 == 0,
+            PortSelect::Peripheral => port & 0x0400 == 0
+        }
+    }
+}
+
+// The write-only register at #DFxx that picks which upper ROM is paged in.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UpperRomSelect {
+    pub selected: u8
+}
+
+impl UpperRomSelect {
+    pub fn default() -> UpperRomSelect {
+        UpperRomSelect { selected: 0 }
+    }
+}
+
+impl PortDevice for UpperRomSelect {
+    fn write(&mut self, port: u16, value: u8) {
+        self.selected = value;
+    }
+
+    fn read(&mut self, port: u16) -> Option<u8> {
+        None
+    }
+}
+
+// What IN reads when no device drives the bus; the data lines are pulled high.
+pub const FLOATING_BUS: u8 = 0xFF;
+
+// Routes IN and OUT to the devices selected by the port's address lines.
+pub struct DataBus {
+    devices: Vec<(PortSelect, Rc<RefCell<dyn PortDevice>>)>
+}
+
 impl DataBus {
-    
-    pub fn write(&self, port: u16, value: u8) {
-        // stub for now
+    pub fn default() -> DataBus {
+        DataBus { devices: Vec::new() }
+    }
+
+    // Connects device to the bus. It sees every read and write whose port selects it.
+    pub fn attach(&mut self, select: PortSelect, device: Rc<RefCell<dyn PortDevice>>) {
+        self.devices.push((select, device));
+    }
+
+    pub fn write(&mut self, port: u16, value: u8) {
+        for (select, device) in &self.devices {
+            if select.selected_by(port) {
+                device.borrow_mut().write(port, value);
+            }
+        }
     }
 
-    pub fn read(&self, port: u16) -> u8 {
-        0xEF // dummy value for now
+    // Devices can only pull data lines low, so if more than one drives the bus the values are ANDed.
+    pub fn read(&mut self, port: u16) -> u8 {
+        let mut value = FLOATING_BUS;
+        for (select, device) in &self.devices {
+            if select.selected_by(port) {
+                if let Some(driven) = device.borrow_mut().read(port) {
+                    value &= driven;
+                }
+            }
+        }
+        value
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Registers {
     pub a: Accumulator,
     pub f: FlagsRegister,
@@ -362,13 +770,21 @@ pub struct Registers {
     pub l_: DefaultRegister,
 
     pub i: DefaultRegister,
-    pub x: DefaultRegister,
+    pub r: DefaultRegister,
+
+    pub ix: u16,
+    pub iy: u16,
 
     pub pc: ProgramCounter,
     pub sp: StackPointer,
     pub iff1: bool,
     pub iff2: bool,
-    pub interrupt_mode: u8
+    pub interrupt_mode: u8,
+    // Set by HALT. While halted the CPU executes NOPs without fetching until an interrupt.
+    pub halted: bool,
+    // Set by EI. Interrupts aren't accepted until the instruction after EI has run, so that
+    // EI; RET at the end of a handler returns before the next interrupt is taken.
+    pub ei_pending: bool
 }
 
 pub struct RegisterOperations {}
@@ -376,38 +792,49 @@ pub struct RegisterOperations {}
 impl RegisterOperations {
 
     pub fn dec<R: Register>(reg: &mut R, flags: &mut FlagsRegister) {
-        reg.set(reg.get() - 1);
-        flags.set_parity_overflow( if reg.get() & 128 == 128 { FlagValue::Set } else { FlagValue::Unset });
+        reg.set(RegisterOperations::dec_value(reg.get(), flags));
+    }
+
+    // Returns value - 1, setting every flag but carry, which DEC leaves alone.
+    pub fn dec_value(value: u8, flags: &mut FlagsRegister) -> u8 {
+        let result = value.wrapping_sub(1);
+        flags.set_parity_overflow(if value == 0x80 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_half_carry(if value & 0x0F == 0x00 { FlagValue::Set } else { FlagValue::Unset });
         flags.set_add_subtract(FlagValue::Set);
-        flags.set_zero(if reg.get() == 0 { FlagValue::Set } else { FlagValue::Unset});
-        flags.set_sign(if (reg.get() as i8) < 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_zero(if result == 0 { FlagValue::Set } else { FlagValue::Unset});
+        flags.set_sign(if result & 0x80 == 0x80 { FlagValue::Set } else { FlagValue::Unset });
+        result
     }
     
-    pub fn dec_register_pair<R: Register>(reg_pair: (&mut R, &mut R), flags: &mut FlagsRegister) {
-        let value = combine_to_double_byte(reg_pair.0.get(), reg_pair.1.get()) - 1;
+    // Like all 16-bit increments and decrements, this leaves the flags alone.
+    pub fn dec_register_pair<R: Register>(reg_pair: (&mut R, &mut R)) {
+        let value = combine_to_double_byte(reg_pair.0.get(), reg_pair.1.get()).wrapping_sub(1);
         let (high, low) = split_double_byte(value);
         reg_pair.0.set(high);
         reg_pair.1.set(low);
-        flags.set_add_subtract(FlagValue::Set);
     }
 
     pub fn inc<R: Register>(reg: &mut R, flags: &mut FlagsRegister) {
-        let half_carry = ((reg.get() & 0xf) + (1 & 0xf)) & 0x10 == 0x10;
-        reg.set(reg.get() + 1);
-        flags.set_parity_overflow( if reg.get() & 128 == 128 { FlagValue::Set } else { FlagValue::Unset });
-        flags.set_half_carry( if half_carry { FlagValue::Set } else { FlagValue::Unset });
+        reg.set(RegisterOperations::inc_value(reg.get(), flags));
+    }
+
+    // Returns value + 1, setting every flag but carry, which INC leaves alone.
+    pub fn inc_value(value: u8, flags: &mut FlagsRegister) -> u8 {
+        let result = value.wrapping_add(1);
+        flags.set_parity_overflow(if value == 0x7F { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_half_carry(if value & 0x0F == 0x0F { FlagValue::Set } else { FlagValue::Unset });
         flags.set_add_subtract(FlagValue::Unset);
+        flags.set_zero(if result == 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_sign(if result & 0x80 == 0x80 { FlagValue::Set } else { FlagValue::Unset });
+        result
     }
 
-    pub fn inc_register_pair<R: Register>(reg_pair: (&mut R, &mut R), flags: &mut FlagsRegister) {
-        let half_carry = ((reg_pair.0.get() & 0xf) + (1 & 0xf)) & 0x10 == 0x10;
-        let value = combine_to_double_byte(reg_pair.0.get(), reg_pair.1.get()) + 1;
+    // Like all 16-bit increments and decrements, this leaves the flags alone.
+    pub fn inc_register_pair<R: Register>(reg_pair: (&mut R, &mut R)) {
+        let value = combine_to_double_byte(reg_pair.0.get(), reg_pair.1.get()).wrapping_add(1);
         let (high, low) = split_double_byte(value);
         reg_pair.0.set(high);
         reg_pair.1.set(low);
-        // flags.set_add_subtract(FlagValue::Unset);
-        // flags.set_parity_overflow( if reg_pair.0.get() & 128 == 128 { FlagValue::Set } else { FlagValue::Unset });
-        // flags.set_half_carry( if half_carry { FlagValue::Set } else { FlagValue::Unset });
     }
 
 
@@ -420,12 +847,12 @@ impl RegisterOperations {
     }
 
     pub fn ld_register_from_addr<R: Register>(mem: &Memory, reg: &mut R, value: u16) {
-        reg.set(mem.locations[value as usize]);
+        reg.set(mem.read_u8(value));
     }
 
     pub fn ld_register_from_addr_with_register_pair<R : Register, P: Register>(mem: &Memory, reg: &mut R, reg_pair: (&P, &P)) {
         let addr = combine_to_double_byte(reg_pair.0.get(), reg_pair.1.get());
-        reg.set(mem.locations[addr as usize]);
+        reg.set(mem.read_u8(addr));
     }
 
     pub fn ld_register_pair_with_value<R: Register>(reg_pair: (&mut R, &mut R), value: u16) {
@@ -434,84 +861,105 @@ impl RegisterOperations {
         reg_pair.1.set(low);
     }
 
+    // Loads the little-endian word at addr into the register pair.
     pub fn ld_register_pair_from_addr<R: Register>(mem: &Memory, reg_pair: (&mut R, &mut R), addr: u16) {
-        let value = mem.locations[addr as usize];
-        RegisterOperations::ld_register_pair_with_value(reg_pair, combine_to_double_byte(0x0, value));
+        RegisterOperations::ld_register_pair_with_value(reg_pair, mem.read_u16(addr));
     }
 
     pub fn ld_addr_from_reg_pair_with_value<R : Register>(mem: &mut Memory, reg_pair: (&R, &R), value: u8) {
         let addr = combine_to_double_byte(reg_pair.0.get(), reg_pair.1.get());
-        mem.locations[addr as usize] = value;
+        mem.write_u8(addr, value);
     }
 
     pub fn ld_addr_from_value_with_register<R : Register>(mem: &mut Memory, value: u16, reg: &R) {
-        mem.locations[value as usize] = reg.get();
+        mem.write_u8(value, reg.get());
     }
 
-    pub fn ld_addr_from_value_with_register_pair<R : Register>(mem: &mut Memory, value: u16, reg_pair: (&R, &R)) {
-        mem.locations[value as usize] = reg_pair.1.get();
-        // seems like we just store the low byte and ignore the high byte.
-        //mem.locations[(value + 1) as usize] = reg_pair.1.get(); 
+    // Stores the register pair at addr, low byte first.
+    pub fn ld_addr_from_value_with_register_pair<R : Register>(mem: &mut Memory, addr: u16, reg_pair: (&R, &R)) {
+        RegisterOperations::ld_addr_with_word(mem, addr, combine_to_double_byte(reg_pair.0.get(), reg_pair.1.get()));
+    }
 
+    // Stores value at addr, low byte first.
+    pub fn ld_addr_with_word(mem: &mut Memory, addr: u16, value: u16) {
+        let (high, low) = split_double_byte(value);
+        mem.write_u8(addr, low);
+        mem.write_u8(addr.wrapping_add(1), high);
     }
 
     pub fn ld_addr_from_reg_pair_with_register<R : Register, P : Register>(mem: &mut Memory, reg_pair: (&R, &R), reg: (&P)) {
         let addr = combine_to_double_byte(reg_pair.0.get(), reg_pair.1.get());
-        mem.locations[addr as usize] = reg.get();
+        mem.write_u8(addr, reg.get());
     }
 
+    // ADD HL,HL: adds the pair to itself with the usual 16-bit ADD flags.
     pub fn dbl_register_pair<P: Register>(reg_pair: (&mut P, &mut P), flags: &mut FlagsRegister) {
         let val = combine_to_double_byte(reg_pair.0.get(), reg_pair.1.get());
-        let total_as_u32 = (val as u32 + val as u32);
-        let carry = if (val as u32 + val as u32) > u16::MAX as u32 {
-             FlagValue::Set 
-            } else {
-                 FlagValue::Unset 
-            };
-        let half_carry = if (val & 8 == 1) && (val & 8 == 1) {
-                FlagValue::Set
-            } else {
-                FlagValue::Unset
-            };
-        let total_as_u16 = (total_as_u32 & 0xFFFF) as u16;
-        let (h, l) = split_double_byte(total_as_u16);
-        reg_pair.0.set(h);
-        reg_pair.1.set(l);
-        flags.set_carry(carry);
-        flags.set_half_carry(half_carry);
-        flags.set_add_subtract(FlagValue::Set);
+        RegisterOperations::add_value_to_register_pair(reg_pair, val, flags);
     }
 
-
     pub fn add_register_pairs<P: Register>(target_reg_pair: (&mut P, &mut P), source_reg_pair: (&P, &P), flags: &mut FlagsRegister) {
-        let val1 = combine_to_double_byte(target_reg_pair.0.get(), target_reg_pair.1.get());
-        let val2 = combine_to_double_byte(source_reg_pair.0.get(), source_reg_pair.1.get());
-        let total_as_u32 = (val1 as u32 + val2 as u32);
-        let carry = if (val1 as u32 + val2 as u32) > u16::MAX as u32 {
-             FlagValue::Set 
-            } else {
-                 FlagValue::Unset 
-            };
-        let half_carry = if (val1 & 8 == 1) && (val2 & 8 == 1) {
-                FlagValue::Set
-            } else {
-                FlagValue::Unset
-            };
-        let total_as_u16 = (total_as_u32 & 0xFFFF) as u16;
-        let (h, l) = split_double_byte(total_as_u16);
+        let value = combine_to_double_byte(source_reg_pair.0.get(), source_reg_pair.1.get());
+        RegisterOperations::add_value_to_register_pair(target_reg_pair, value, flags);
+    }
+
+    pub fn add_sp_to_hl<P: Register>(hl: (&mut P, &mut P), sp: &StackPointer, flags: &mut FlagsRegister) {
+        RegisterOperations::add_value_to_register_pair(hl, sp.get() as u16, flags);
+    }
+
+    // 16-bit ADD: sets carry out of bit 15 and half-carry out of bit 11, clears N,
+    // and leaves zero, sign and P/V untouched.
+    pub fn add_value_to_register_pair<P: Register>(target_reg_pair: (&mut P, &mut P), value: u16, flags: &mut FlagsRegister) {
+        let target = combine_to_double_byte(target_reg_pair.0.get(), target_reg_pair.1.get());
+        let total = target as u32 + value as u32;
+        let (h, l) = split_double_byte(total as u16);
         target_reg_pair.0.set(h);
         target_reg_pair.1.set(l);
-        flags.set_carry(carry);
-        flags.set_half_carry(half_carry);
-        flags.set_add_subtract(FlagValue::Set);
+        flags.set_carry(if total > 0xFFFF { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_half_carry(if (target & 0x0FFF) + (value & 0x0FFF) > 0x0FFF { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_add_subtract(FlagValue::Unset);
     }
 
+    // 16-bit ADC: adds value and the carry flag, setting carry out of bit 15, half-carry out of bit 11,
+    // zero and sign from the result, P/V from signed overflow, and clearing N.
+    pub fn adc_value_to_register_pair<P: Register>(target_reg_pair: (&mut P, &mut P), value: u16, flags: &mut FlagsRegister) {
+        let target = combine_to_double_byte(target_reg_pair.0.get(), target_reg_pair.1.get());
+        let carry_in = (0 + flags.get_carry()) as u32;
+        let total = target as u32 + value as u32 + carry_in;
+        let result = total as u16;
+        RegisterOperations::ld_register_pair_with_value(target_reg_pair, result);
+        flags.set_carry(if total > 0xFFFF { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_half_carry(if (target & 0x0FFF) as u32 + (value & 0x0FFF) as u32 + carry_in > 0x0FFF { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_add_subtract(FlagValue::Unset);
+        // Overflow when both operands have the same sign and the result's sign differs.
+        flags.set_parity_overflow(if !(target ^ value) & (target ^ result) & 0x8000 != 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_zero(if result == 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_sign(if result & 0x8000 == 0x8000 { FlagValue::Set } else { FlagValue::Unset });
+    }
+
+    // 16-bit SBC: subtracts value and the carry flag, setting carry and half-carry from the borrows
+    // out of bits 15 and 11, zero and sign from the result, P/V from signed overflow, and setting N.
+    pub fn sbc_value_from_register_pair<P: Register>(target_reg_pair: (&mut P, &mut P), value: u16, flags: &mut FlagsRegister) {
+        let target = combine_to_double_byte(target_reg_pair.0.get(), target_reg_pair.1.get());
+        let borrow_in = (0 + flags.get_carry()) as i32;
+        let difference = target as i32 - value as i32 - borrow_in;
+        let result = difference as u16;
+        RegisterOperations::ld_register_pair_with_value(target_reg_pair, result);
+        flags.set_carry(if difference < 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_half_carry(if ((target & 0x0FFF) as i32 - (value & 0x0FFF) as i32 - borrow_in) < 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_add_subtract(FlagValue::Set);
+        // Overflow when the operands have different signs and the result's sign differs from the target.
+        flags.set_parity_overflow(if (target ^ value) & (target ^ result) & 0x8000 != 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_zero(if result == 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_sign(if result & 0x8000 == 0x8000 { FlagValue::Set } else { FlagValue::Unset });
+    }
 
     pub fn push_register_pair<R: Register, P: Register>(reg_pair: (&R, &P), sp: &mut StackPointer, mem: &mut Memory) {
         let val = combine_to_double_byte(reg_pair.0.get(), reg_pair.1.get());
         sp.push(mem, val);
     }
 
+    #[allow(clippy::needless_borrow)]
     pub fn pop_register_pair<R: Register, P: Register>(reg_pair: (&mut R, &mut P), sp: &mut StackPointer, mem: &mut Memory) {
         let (val1, val2) = split_double_byte(sp.pop(&mem));
         reg_pair.0.set(val1);
@@ -524,11 +972,196 @@ impl RegisterOperations {
         pc.set(value);
     }
 
-    // The contents of the passed register are shifted right one bit position. 
-    // The contents of bit 0 are copied to the carry flag and a zero is put into bit 7.
-    pub fn srl<R: Register>(reg: &mut R, flags: &mut FlagsRegister) {
-        flags.set_carry(if reg.get() & 1 == 1 { FlagValue::Set } else { FlagValue::Unset });
-        reg.set((reg.get()) >> 1 & 0x7F);
+    // Pops the top stack entry into PC if the condition holds for the current flags.
+    // Returns the cycles taken: 11 when the return happens, 5 when it doesn't.
+    pub fn ret_if<F: Fn(&mut FlagsRegister) -> bool>(condition: F, flags: &mut FlagsRegister, sp: &mut StackPointer, pc: &mut ProgramCounter, mem: &Memory) -> u16 {
+        if condition(flags) {
+            pc.set(sp.pop(mem));
+            return 11;
+        }
+        5
+    }
+
+    // Copies the byte at HL to DE, steps HL and DE up (LDI) or down (LDD) by one and decrements BC,
+    // returning the new BC. P/V is set while BC is non-zero, H and N are cleared.
+    pub fn block_load(mem: &mut Memory, registers: &mut Registers, increment: bool) -> u16 {
+        let hl = combine_to_double_byte(registers.h.get(), registers.l.get());
+        let de = combine_to_double_byte(registers.d.get(), registers.e.get());
+        let bc = combine_to_double_byte(registers.b.get(), registers.c.get()).wrapping_sub(1);
+        mem.write_u8(de, mem.read_u8(hl));
+
+        let (next_hl, next_de) = if increment { (hl.wrapping_add(1), de.wrapping_add(1)) } else { (hl.wrapping_sub(1), de.wrapping_sub(1)) };
+        RegisterOperations::ld_register_pair_with_value((&mut registers.h, &mut registers.l), next_hl);
+        RegisterOperations::ld_register_pair_with_value((&mut registers.d, &mut registers.e), next_de);
+        RegisterOperations::ld_register_pair_with_value((&mut registers.b, &mut registers.c), bc);
+
+        registers.f.set_parity_overflow(if bc != 0 { FlagValue::Set } else { FlagValue::Unset });
+        registers.f.set_half_carry(FlagValue::Unset);
+        registers.f.set_add_subtract(FlagValue::Unset);
+        bc
+    }
+
+    // Compares a with the byte at HL like CP, but leaves carry alone. HL is stepped up (CPI) or
+    // down (CPD) by one and BC decremented, with P/V set while BC is non-zero.
+    // Returns true if the repeating forms should carry on, i.e. no match was found and BC is non-zero.
+    pub fn block_compare(mem: &Memory, registers: &mut Registers, increment: bool) -> bool {
+        let hl = combine_to_double_byte(registers.h.get(), registers.l.get());
+        let bc = combine_to_double_byte(registers.b.get(), registers.c.get()).wrapping_sub(1);
+        let carry = registers.f.get_carry();
+        let result = Accumulator::subtract(registers.a.get(), mem.read_u8(hl), 0, &mut registers.f);
+
+        let next_hl = if increment { hl.wrapping_add(1) } else { hl.wrapping_sub(1) };
+        RegisterOperations::ld_register_pair_with_value((&mut registers.h, &mut registers.l), next_hl);
+        RegisterOperations::ld_register_pair_with_value((&mut registers.b, &mut registers.c), bc);
+
+        registers.f.set_carry(carry);
+        registers.f.set_parity_overflow(if bc != 0 { FlagValue::Set } else { FlagValue::Unset });
+        bc != 0 && result != 0
+    }
+
+    // Reads a byte from the given port for IN r,(C), setting sign, zero and parity from it,
+    // clearing H and N and leaving carry alone.
+    pub fn read_port(data_bus: &mut DataBus, port: u16, flags: &mut FlagsRegister) -> u8 {
+        let value = data_bus.read(port);
+        flags.set_half_carry(FlagValue::Unset);
+        flags.set_add_subtract(FlagValue::Unset);
+        flags.set_parity_overflow(if parity(value) { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_zero(if value == 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_sign(if value & 0x80 == 0x80 { FlagValue::Set } else { FlagValue::Unset });
+        value
+    }
+
+    // Reads a byte from port BC into the memory location pointed to by HL, then steps HL up (INI)
+    // or down (IND) by one and decrements B, returning the new B.
+    pub fn block_in(mem: &mut Memory, registers: &mut Registers, data_bus: &mut DataBus, increment: bool) -> u8 {
+        let hl = combine_to_double_byte(registers.h.get(), registers.l.get());
+        let value = data_bus.read(combine_to_double_byte(registers.b.get(), registers.c.get()));
+        mem.write_u8(hl, value);
+
+        let next_hl = if increment { hl.wrapping_add(1) } else { hl.wrapping_sub(1) };
+        RegisterOperations::ld_register_pair_with_value((&mut registers.h, &mut registers.l), next_hl);
+        let b = registers.b.get().wrapping_sub(1);
+        registers.b.set(b);
+
+        let c = if increment { registers.c.get().wrapping_add(1) } else { registers.c.get().wrapping_sub(1) };
+        RegisterOperations::set_block_io_flags(b, value, value as u16 + c as u16, &mut registers.f);
+        b
+    }
+
+    // Decrements B, then writes the memory location pointed to by HL to port BC and steps HL up (OUTI)
+    // or down (OUTD) by one, returning the new B.
+    pub fn block_out(mem: &Memory, registers: &mut Registers, data_bus: &mut DataBus, increment: bool) -> u8 {
+        let hl = combine_to_double_byte(registers.h.get(), registers.l.get());
+        let b = registers.b.get().wrapping_sub(1);
+        registers.b.set(b);
+        let value = mem.read_u8(hl);
+        data_bus.write(combine_to_double_byte(b, registers.c.get()), value);
+
+        let next_hl = if increment { hl.wrapping_add(1) } else { hl.wrapping_sub(1) };
+        RegisterOperations::ld_register_pair_with_value((&mut registers.h, &mut registers.l), next_hl);
+
+        RegisterOperations::set_block_io_flags(b, value, value as u16 + registers.l.get() as u16, &mut registers.f);
+        b
+    }
+
+    // Zero and sign come from B, N from bit 7 of the byte transferred. H, C and P/V depend on
+    // the byte plus the adjusted C (for input) or the new L (for output), as on a real Z80.
+    fn set_block_io_flags(b: u8, value: u8, k: u16, flags: &mut FlagsRegister) {
+        flags.set_zero(if b == 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_sign(if b & 0x80 == 0x80 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_add_subtract(if value & 0x80 == 0x80 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_half_carry(if k > 0xFF { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_carry(if k > 0xFF { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_parity_overflow(if parity((k as u8 & 0x07) ^ b) { FlagValue::Set } else { FlagValue::Unset });
+    }
+
+    // The address an (IX+d) or (IY+d) operand refers to: the index register plus the signed displacement.
+    pub fn indexed_address(index: u16, offset: i8) -> u16 {
+        index.wrapping_add(offset as u16)
+    }
+
+    // Reads the byte at the address held in the register pair, applies op to it and writes the
+    // result back. Shared by every CB-prefixed (HL) instruction.
+    pub fn read_modify_write<R: Register, F: Fn(u8, &mut FlagsRegister) -> u8>(mem: &mut Memory, reg_pair: (&R, &R), flags: &mut FlagsRegister, op: F) {
+        let location = combine_to_double_byte(reg_pair.0.get(), reg_pair.1.get());
+        RegisterOperations::read_modify_write_at(mem, location, flags, op);
+    }
+
+    // As read_modify_write, but for an address that has already been worked out, e.g. IX+d.
+    pub fn read_modify_write_at<F: Fn(u8, &mut FlagsRegister) -> u8>(mem: &mut Memory, location: u16, flags: &mut FlagsRegister, op: F) {
+        let value = op(mem.read_u8(location), flags);
+        mem.write_u8(location, value);
+    }
+
+    // Returns value rotated left one bit, with bit 7 copied to the carry flag and bit 0.
+    pub fn rlc_value(value: u8, flags: &mut FlagsRegister) -> u8 {
+        RegisterOperations::set_shift_flags(value.rotate_left(1), value & 0x80 == 0x80, flags)
+    }
+
+    // Returns value rotated right one bit, with bit 0 copied to the carry flag and bit 7.
+    pub fn rrc_value(value: u8, flags: &mut FlagsRegister) -> u8 {
+        RegisterOperations::set_shift_flags(value.rotate_right(1), value & 0x01 == 0x01, flags)
+    }
+
+    // Returns value rotated left through the carry flag: the old carry goes into bit 0 and bit 7 into carry.
+    pub fn rl_value(value: u8, flags: &mut FlagsRegister) -> u8 {
+        let result = (value << 1) | (0 + flags.get_carry());
+        RegisterOperations::set_shift_flags(result, value & 0x80 == 0x80, flags)
+    }
+
+    // Returns value rotated right through the carry flag: the old carry goes into bit 7 and bit 0 into carry.
+    pub fn rr_value(value: u8, flags: &mut FlagsRegister) -> u8 {
+        let result = (value >> 1) | ((0 + flags.get_carry()) << 7);
+        RegisterOperations::set_shift_flags(result, value & 0x01 == 0x01, flags)
+    }
+
+    // Returns value shifted left one bit, with bit 7 copied to the carry flag and a zero put into bit 0.
+    pub fn sla_value(value: u8, flags: &mut FlagsRegister) -> u8 {
+        RegisterOperations::set_shift_flags(value << 1, value & 0x80 == 0x80, flags)
+    }
+
+    // Returns value shifted right one bit, with bit 0 copied to the carry flag and bit 7 left unchanged.
+    pub fn sra_value(value: u8, flags: &mut FlagsRegister) -> u8 {
+        RegisterOperations::set_shift_flags((value >> 1) | (value & 0x80), value & 0x01 == 0x01, flags)
+    }
+
+    // Returns value shifted left one bit, with bit 7 copied to the carry flag and a one put into bit 0.
+    // Undocumented, but used by some games.
+    pub fn sll_value(value: u8, flags: &mut FlagsRegister) -> u8 {
+        RegisterOperations::set_shift_flags((value << 1) | 0x01, value & 0x80 == 0x80, flags)
+    }
+
+    // Returns value shifted right one bit, with bit 0 copied to the carry flag and a zero put into bit 7.
+    pub fn srl_value(value: u8, flags: &mut FlagsRegister) -> u8 {
+        RegisterOperations::set_shift_flags(value >> 1, value & 0x01 == 0x01, flags)
+    }
+
+    // Tests the given bit of value: zero (and P/V, which mirrors it) is set when the bit is 0.
+    // Sign is only set when testing bit 7 and it is 1. H is set, N cleared and carry unaffected.
+    pub fn bit(index: u8, value: u8, flags: &mut FlagsRegister) {
+        let bit_set = value & (1 << index) != 0;
+        flags.set_zero(if bit_set { FlagValue::Unset } else { FlagValue::Set });
+        flags.set_parity_overflow(if bit_set { FlagValue::Unset } else { FlagValue::Set });
+        flags.set_sign(if index == 7 && bit_set { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_half_carry(FlagValue::Set);
+        flags.set_add_subtract(FlagValue::Unset);
+    }
+
+    // Returns value with the given bit set (SET) or cleared (RES). No flags are affected.
+    pub fn change_bit(index: u8, value: u8, set: bool) -> u8 {
+        if set { value | (1 << index) } else { value & !(1 << index) }
+    }
+
+    // The CB rotates and shifts set carry from the bit shifted out, take sign, zero and parity
+    // from the result, and clear H and N. Returns the result for convenience.
+    fn set_shift_flags(result: u8, carry_out: bool, flags: &mut FlagsRegister) -> u8 {
+        flags.set_carry(if carry_out { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_half_carry(FlagValue::Unset);
+        flags.set_add_subtract(FlagValue::Unset);
+        flags.set_parity_overflow(if parity(result) { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_zero(if result == 0 { FlagValue::Set } else { FlagValue::Unset });
+        flags.set_sign(if result & 0x80 == 0x80 { FlagValue::Set } else { FlagValue::Unset });
+        result
     }
 
 }
@@ -536,6 +1169,7 @@ impl RegisterOperations {
 impl Add<FlagValue> for u8 {
     type Output = u8;
 
+    #[allow(clippy::needless_return)]
     fn add(self, rhs: FlagValue) -> Self::Output {
         return if rhs == FlagValue::Set { self + 1 } else { self }
     }
@@ -567,27 +1201,144 @@ impl Registers {
             h_: DefaultRegister {name: "h'".to_string(), value: 0},
             l_: DefaultRegister {name: "l'".to_string(), value: 0},
             i: DefaultRegister {name: "i".to_string(), value: 0},
-            x: DefaultRegister {name: "x".to_string(), value: 0},
+            r: DefaultRegister {name: "r".to_string(), value: 0},
+            ix: 0,
+            iy: 0,
             pc: ProgramCounter { value: 0 }, // PC normally begins at start of memory
             sp: StackPointer { location: 0xFFFF }, // SP normally begins at the end of memory and moves down.
             iff1: false,
             iff2: false,
-            interrupt_mode: 0
+            interrupt_mode: 0,
+            halted: false,
+            ei_pending: false
         }
     }
 }
 
+// Three lines: the main set with the index registers, SP and PC, then the alternate set, then
+// the interrupt state and the flags. Each flag shows as its letter in SZ-H-PNC when set or . when
+// clear, with - for the two undocumented bits.
+impl fmt::Display for Registers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pair = |high: &dyn Register, low: &dyn Register| combine_to_double_byte(high.get(), low.get());
+        writeln!(
+            f, "AF={:04X} BC={:04X} DE={:04X} HL={:04X} IX={:04X} IY={:04X} SP={:04X} PC={:04X}",
+            pair(&self.a, &self.f), pair(&self.b, &self.c), pair(&self.d, &self.e), pair(&self.h, &self.l),
+            self.ix, self.iy, self.sp.get(), self.pc.get()
+        )?;
+        writeln!(
+            f, "AF'={:04X} BC'={:04X} DE'={:04X} HL'={:04X}",
+            pair(&self.a_, &self.f_), pair(&self.b_, &self.c_), pair(&self.d_, &self.e_), pair(&self.h_, &self.l_)
+        )?;
+        let flags: String = "SZ-H-PNC".chars().enumerate().map(|(i, letter)| {
+            match letter {
+                '-' => '-',
+                _ if self.f.get() & (0x80 >> i) != 0 => letter,
+                _ => '.'
+            }
+        }).collect();
+        write!(
+            f, "I={:02X} R={:02X} IM={} IFF1={} IFF2={} F={}",
+            self.i.get(), self.r.get(), self.interrupt_mode, self.iff1 as u8, self.iff2 as u8, flags
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{instruction_set::{Instruction, InstructionSet}, runtime::RuntimeComponents};
 
-    use super::{Memory, Registers, AddressBus, DataBus, StackPointer};
+    use std::{rc::Rc, cell::RefCell};
+
+    use super::{Memory, Registers, AddressBus, DataBus, PortDevice, PortSelect, FLOATING_BUS, StackPointer, ProgramCounter, Register, FlagValue, RegisterOperations};
 
     fn runtime_components() -> RuntimeComponents {
-        RuntimeComponents { mem: Memory::default(), registers: Registers::default(), address_bus: AddressBus { value: 0 }, data_bus: DataBus { } }
+        RuntimeComponents { mem: Memory::default(), registers: Registers::default(), address_bus: AddressBus { value: 0 }, data_bus: DataBus::default() }
     }
     
     #[test]
+    fn registers_display_dumps_state_and_flags() {
+        let mut registers = Registers::default();
+        registers.a.set(0x12);
+        registers.f.set(0xC5); // S Z P C
+        registers.h.set(0xBE);
+        registers.l.set(0xEF);
+        registers.ix = 0x1234;
+        registers.pc.set(0x4000);
+        registers.b_.set(0xAB);
+        registers.interrupt_mode = 1;
+        registers.iff1 = true;
+
+        let dump = registers.to_string();
+        assert_eq!(dump, [
+            "AF=12C5 BC=0000 DE=0000 HL=BEEF IX=1234 IY=0000 SP=FFFF PC=4000",
+            "AF'=0000 BC'=AB00 DE'=0000 HL'=0000",
+            "I=00 R=00 IM=1 IFF1=1 IFF2=0 F=SZ-.-P.C"
+        ].join("\n"));
+    }
+
+    #[test]
+    fn selected_upper_rom_shows_at_c000() {
+        let mut mem = Memory::default();
+        mem.load_upper_rom(0, &[0xBA; 0x4000]);
+        mem.load_upper_rom(7, &[0xD0; 0x4000]);
+        assert_eq!(mem.read_u8(0xC000), 0xBA);
+
+        mem.upper_roms.select(7);
+        assert_eq!(mem.read_u8(0xC000), 0xD0);
+        assert_eq!(mem.read_u8(0xFFFF), 0xD0);
+
+        // Nothing in slot 3, so BASIC appears.
+        mem.upper_roms.select(3);
+        assert_eq!(mem.read_u8(0xC000), 0xBA);
+    }
+
+    #[test]
+    fn rom_overlays_ram_while_enabled() {
+        let mut mem = Memory::default();
+        mem.load_lower_rom(&[0xC3; 0x4000]);
+        mem.load_upper_rom(0, &[0xAB; 0x4000]);
+        assert_eq!(mem.read_u8(0x0000), 0xC3);
+
+        // The write goes to the RAM under the ROM...
+        mem.write_u8(0x0000, 0x42);
+        assert_eq!(mem.read_u8(0x0000), 0xC3);
+        // ...which shows once the ROM is disabled.
+        mem.set_rom_enables(false, true);
+        assert_eq!(mem.read_u8(0x0000), 0x42);
+        assert_eq!(mem.read_u8(0xC000), 0xAB);
+
+        mem.write_u8(0xC000, 0x24);
+        mem.set_rom_enables(true, false);
+        assert_eq!(mem.read_u8(0xC000), 0x24);
+        assert_eq!(mem.read_u8(0x4000), 0x01);
+    }
+
+    #[test]
+    fn ram_configs_map_blocks() {
+        let mut mem = Memory::default();
+        mem.set_ram_config(2);
+        mem.write_u8(0x0000, 0x44);
+        mem.write_u8(0x4000, 0x55);
+        assert_eq!(mem.expansion[0x0000], 0x44);
+        assert_eq!(mem.expansion[0x4000], 0x55);
+        assert_eq!(mem.locations[0x0000], 0x01);
+
+        // Configurations 4-7 page one expansion block in at #4000 and leave the rest alone.
+        mem.set_ram_config(6);
+        mem.write_u8(0x4001, 0x66);
+        mem.write_u8(0xC000, 0x77);
+        assert_eq!(mem.expansion[0x8001], 0x66);
+        assert_eq!(mem.locations[0xC000], 0x77);
+
+        // Config 3 shows base block 3 at #4000.
+        mem.set_ram_config(3);
+        assert_eq!(mem.read_u8(0x4000), 0x77);
+        assert_eq!(mem.read_video(0x4000), 0x01);
+    }
+
+    #[test]
+    #[allow(clippy::unnecessary_mut_passed)]
     fn test_stack_pointer() {
         let mut sp = StackPointer { location: 0x100 };
         let mut mem = Memory::default();
@@ -607,4 +1358,359 @@ mod tests {
         assert!(sp.location == 0x100);
     }
 
+    // Records what's written to it and answers reads with a fixed value.
+    struct RecordingDevice {
+        writes: Vec<(u16, u8)>,
+        value: Option<u8>
+    }
+
+    impl PortDevice for RecordingDevice {
+        fn write(&mut self, port: u16, value: u8) {
+            self.writes.push((port, value));
+        }
+
+        fn read(&mut self, port: u16) -> Option<u8> {
+            self.value
+        }
+    }
+
+    #[test]
+    fn data_bus_routes_by_address_line() {
+        let mut bus = DataBus::default();
+        let gate_array = Rc::new(RefCell::new(RecordingDevice { writes: Vec::new(), value: None }));
+        bus.attach(PortSelect::GateArray, gate_array.clone());
+
+        bus.write(0x7F00, 0x8D);
+        bus.write(0xBC0C, 0x30); // CRTC, not the Gate Array
+        bus.write(0x3F00, 0x01); // A14 low as well, so also the CRTC
+        assert_eq!(gate_array.borrow().writes, vec![(0x7F00, 0x8D)]);
+    }
+
+    #[test]
+    fn data_bus_reads() {
+        let mut bus = DataBus::default();
+        assert_eq!(bus.read(0xF5FF), FLOATING_BUS);
+
+        let ppi = Rc::new(RefCell::new(RecordingDevice { writes: Vec::new(), value: Some(0x5E) }));
+        bus.attach(PortSelect::Ppi, ppi);
+        assert_eq!(bus.read(0xF5FF), 0x5E);
+        // Not selected: A11 is high.
+        assert_eq!(bus.read(0xFFFF), FLOATING_BUS);
+    }
+
+    #[test]
+    fn top_of_memory_is_addressable() {
+        let mut mem = Memory::default();
+        mem.locations[0xFFFF] = 0xA5;
+        assert_eq!(mem.read_u8(0xFFFF), 0xA5);
+
+        mem.locations[0x0000] = 0x5A;
+        assert_eq!(mem.read_u16(0xFFFF), 0x5AA5);
+    }
+
+    #[test]
+    fn stack_wraps_around_top_of_memory() {
+        let mut sp = StackPointer { location: 0x0001 };
+        let mut mem = Memory::default();
+
+        sp.push(&mut mem, 0x1234);
+        assert_eq!(sp.get(), 0xFFFF);
+        assert_eq!(mem.locations[0x0000], 0x12);
+        assert_eq!(mem.locations[0xFFFF], 0x34);
+
+        assert_eq!(sp.pop(&mem), 0x1234);
+        assert_eq!(sp.get(), 0x0001);
+    }
+
+    #[test]
+    fn program_counter_inc_dec_wrap() {
+        let mut pc = ProgramCounter { value: 0xFFFF };
+        pc.inc();
+        assert_eq!(pc.get(), 0x0000);
+        pc.dec();
+        assert_eq!(pc.get(), 0xFFFF);
+    }
+
+    #[test]
+    fn stack_pointer_inc_dec_wrap() {
+        let mut sp = StackPointer { location: 0x0000 };
+        sp.dec();
+        assert_eq!(sp.get(), 0xFFFF);
+        sp.inc();
+        assert_eq!(sp.get(), 0x0000);
+        sp.inc();
+        assert_eq!(sp.get(), 0x0001);
+    }
+
+    #[test]
+    fn add_sp_to_hl_carry() {
+        let mut components = runtime_components();
+        let registers = &mut components.registers;
+        registers.h.set(0x80);
+        registers.l.set(0x00);
+        registers.sp.set(0x8000);
+        registers.f.set_zero(FlagValue::Set);
+        RegisterOperations::add_sp_to_hl((&mut registers.h, &mut registers.l), &registers.sp, &mut registers.f);
+        assert_eq!(registers.h.get(), 0x00);
+        assert_eq!(registers.l.get(), 0x00);
+        assert!(registers.f.get_carry() == FlagValue::Set);
+        assert!(registers.f.get_half_carry() == FlagValue::Unset);
+        assert!(registers.f.get_add_subtract() == FlagValue::Unset);
+        assert!(registers.f.get_zero() == FlagValue::Set);
+
+        registers.h.set(0x0F);
+        registers.l.set(0xFF);
+        registers.sp.set(0x0001);
+        RegisterOperations::add_sp_to_hl((&mut registers.h, &mut registers.l), &registers.sp, &mut registers.f);
+        assert_eq!(registers.h.get(), 0x10);
+        assert_eq!(registers.l.get(), 0x00);
+        assert!(registers.f.get_carry() == FlagValue::Unset);
+        assert!(registers.f.get_half_carry() == FlagValue::Set);
+    }
+
+    #[test]
+    fn daa_after_add() {
+        let mut components = runtime_components();
+        let registers = &mut components.registers;
+        registers.a.set(0x09);
+        registers.a.add_value(0x01, &mut registers.f);
+        assert_eq!(registers.a.get(), 0x0A);
+        registers.a.daa(&mut registers.f);
+        assert_eq!(registers.a.get(), 0x10);
+        assert!(registers.f.get_carry() == FlagValue::Unset);
+
+        registers.a.set(0x99);
+        registers.a.add_value(0x01, &mut registers.f);
+        registers.a.daa(&mut registers.f);
+        assert_eq!(registers.a.get(), 0x00);
+        assert!(registers.f.get_carry() == FlagValue::Set);
+        assert!(registers.f.get_zero() == FlagValue::Set);
+
+        registers.a.set(0x38);
+        registers.a.add_value(0x29, &mut registers.f);
+        registers.a.daa(&mut registers.f);
+        assert_eq!(registers.a.get(), 0x67);
+    }
+
+    #[test]
+    fn daa_after_sub() {
+        let mut components = runtime_components();
+        let registers = &mut components.registers;
+        registers.a.set(0x10);
+        registers.a.sub_value(0x01, &mut registers.f);
+        registers.a.daa(&mut registers.f);
+        assert_eq!(registers.a.get(), 0x09);
+        assert!(registers.f.get_carry() == FlagValue::Unset);
+        assert!(registers.f.get_add_subtract() == FlagValue::Set);
+
+        registers.a.set(0x00);
+        registers.a.sub_value(0x01, &mut registers.f);
+        registers.a.daa(&mut registers.f);
+        assert_eq!(registers.a.get(), 0x99);
+        assert!(registers.f.get_carry() == FlagValue::Set);
+    }
+
+    #[test]
+    fn add_a_half_carry() {
+        let mut components = runtime_components();
+        let registers = &mut components.registers;
+        registers.a.set(0x7F);
+        registers.b.set(0x01);
+        registers.a.add_a(&registers.b, &mut registers.f);
+        assert_eq!(registers.a.get(), 0x80);
+        assert!(registers.f.get_half_carry() == FlagValue::Set);
+        assert!(registers.f.get_zero() == FlagValue::Unset);
+        assert!(registers.f.get_sign() == FlagValue::Set);
+        assert!(registers.f.get_parity_overflow() == FlagValue::Set);
+        assert!(registers.f.get_carry() == FlagValue::Unset);
+        assert!(registers.f.get_add_subtract() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn adc_a_carry_in_and_out() {
+        let mut components = runtime_components();
+        let registers = &mut components.registers;
+        registers.a.set(0xFF);
+        registers.b.set(0x00);
+        registers.f.set_carry(FlagValue::Set);
+        registers.a.adc_a(&registers.b, &mut registers.f);
+        assert_eq!(registers.a.get(), 0x00);
+        assert!(registers.f.get_carry() == FlagValue::Set);
+        assert!(registers.f.get_half_carry() == FlagValue::Set);
+        assert!(registers.f.get_zero() == FlagValue::Set);
+        assert!(registers.f.get_parity_overflow() == FlagValue::Unset);
+
+        // Chaining into the next byte picks up the carry.
+        registers.a.set(0x12);
+        registers.a.adc_a(&registers.b, &mut registers.f);
+        assert_eq!(registers.a.get(), 0x13);
+        assert!(registers.f.get_carry() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn sub_borrow() {
+        let mut components = runtime_components();
+        let registers = &mut components.registers;
+        registers.a.set(0x00);
+        registers.b.set(0x01);
+        registers.a.sub_reg(&registers.b, &mut registers.f);
+        assert_eq!(registers.a.get(), 0xFF);
+        assert!(registers.f.get_carry() == FlagValue::Set);
+        assert!(registers.f.get_sign() == FlagValue::Set);
+        assert!(registers.f.get_half_carry() == FlagValue::Set);
+        assert!(registers.f.get_add_subtract() == FlagValue::Set);
+        assert!(registers.f.get_zero() == FlagValue::Unset);
+        assert!(registers.f.get_parity_overflow() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn sub_a_from_itself() {
+        let mut components = runtime_components();
+        let registers = &mut components.registers;
+        registers.a.set(0x5A);
+        let value = registers.a.get();
+        registers.a.sub_value(value, &mut registers.f);
+        assert_eq!(registers.a.get(), 0x00);
+        assert!(registers.f.get_zero() == FlagValue::Set);
+        assert!(registers.f.get_carry() == FlagValue::Unset);
+        assert!(registers.f.get_sign() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn sbc_a_honours_carry_in() {
+        let mut components = runtime_components();
+        let registers = &mut components.registers;
+        registers.a.set(0x00);
+        registers.b.set(0x00);
+        registers.f.set_carry(FlagValue::Set);
+        registers.a.sbc_a(&registers.b, &mut registers.f);
+        assert_eq!(registers.a.get(), 0xFF);
+        assert!(registers.f.get_carry() == FlagValue::Set);
+        assert!(registers.f.get_half_carry() == FlagValue::Set);
+        assert!(registers.f.get_add_subtract() == FlagValue::Set);
+
+        registers.a.set(0x10);
+        registers.f.set_carry(FlagValue::Unset);
+        registers.a.sbc_a(&registers.b, &mut registers.f);
+        assert_eq!(registers.a.get(), 0x10);
+        assert!(registers.f.get_carry() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn xor_a_clears_a() {
+        let mut components = runtime_components();
+        let registers = &mut components.registers;
+        for value in [0x00, 0x01, 0x80, 0xFF] {
+            registers.a.set(value);
+            registers.f.set(0xFF);
+            registers.a.xor_a(&mut registers.f);
+            assert_eq!(registers.a.get(), 0x00);
+            assert!(registers.f.get_zero() == FlagValue::Set);
+            assert!(registers.f.get_parity_overflow() == FlagValue::Set);
+            assert!(registers.f.get_sign() == FlagValue::Unset);
+            assert!(registers.f.get_carry() == FlagValue::Unset);
+            assert!(registers.f.get_half_carry() == FlagValue::Unset);
+            assert!(registers.f.get_add_subtract() == FlagValue::Unset);
+        }
+    }
+
+    #[test]
+    fn or_a_tests_for_zero() {
+        let mut components = runtime_components();
+        let registers = &mut components.registers;
+        registers.a.set(0x00);
+        registers.f.set(0xFF);
+        registers.a.or_a(&mut registers.f);
+        assert_eq!(registers.a.get(), 0x00);
+        assert!(registers.f.get_zero() == FlagValue::Set);
+        assert!(registers.f.get_carry() == FlagValue::Unset);
+        assert!(registers.f.get_half_carry() == FlagValue::Unset);
+
+        registers.a.set(0x81);
+        registers.a.or_a(&mut registers.f);
+        assert_eq!(registers.a.get(), 0x81);
+        assert!(registers.f.get_zero() == FlagValue::Unset);
+        assert!(registers.f.get_sign() == FlagValue::Set);
+        assert!(registers.f.get_parity_overflow() == FlagValue::Set);
+    }
+
+    #[test]
+    fn compare_sets_zero_and_carry() {
+        let mut components = runtime_components();
+        let registers = &mut components.registers;
+        registers.a.set(0x42);
+        registers.e.set(0x42);
+        registers.a.compare_reg(&registers.e, &mut registers.f);
+        assert_eq!(registers.a.get(), 0x42);
+        assert!(registers.f.get_zero() == FlagValue::Set);
+        assert!(registers.f.get_carry() == FlagValue::Unset);
+        assert!(registers.f.get_add_subtract() == FlagValue::Set);
+
+        registers.a.compare_val(0x43, &mut registers.f);
+        assert_eq!(registers.a.get(), 0x42);
+        assert!(registers.f.get_zero() == FlagValue::Unset);
+        assert!(registers.f.get_carry() == FlagValue::Set);
+        assert!(registers.f.get_sign() == FlagValue::Set);
+    }
+
+    #[test]
+    fn inc_wraps_to_zero() {
+        let mut components = runtime_components();
+        let registers = &mut components.registers;
+        registers.d.set(0xFF);
+        registers.f.set_carry(FlagValue::Set);
+        RegisterOperations::inc(&mut registers.d, &mut registers.f);
+        assert_eq!(registers.d.get(), 0x00);
+        assert!(registers.f.get_zero() == FlagValue::Set);
+        assert!(registers.f.get_half_carry() == FlagValue::Set);
+        assert!(registers.f.get_parity_overflow() == FlagValue::Unset);
+        assert!(registers.f.get_carry() == FlagValue::Set);
+    }
+
+    #[test]
+    fn inc_signed_overflow() {
+        let mut components = runtime_components();
+        let registers = &mut components.registers;
+        registers.e.set(0x7F);
+        RegisterOperations::inc(&mut registers.e, &mut registers.f);
+        assert_eq!(registers.e.get(), 0x80);
+        assert!(registers.f.get_parity_overflow() == FlagValue::Set);
+        assert!(registers.f.get_sign() == FlagValue::Set);
+        assert!(registers.f.get_zero() == FlagValue::Unset);
+    }
+
+    #[test]
+    fn dec_wraps_to_0xff() {
+        let mut components = runtime_components();
+        let registers = &mut components.registers;
+        registers.h.set(0x00);
+        RegisterOperations::dec(&mut registers.h, &mut registers.f);
+        assert_eq!(registers.h.get(), 0xFF);
+        assert!(registers.f.get_sign() == FlagValue::Set);
+        assert!(registers.f.get_half_carry() == FlagValue::Set);
+        assert!(registers.f.get_add_subtract() == FlagValue::Set);
+        assert!(registers.f.get_zero() == FlagValue::Unset);
+        assert!(registers.f.get_parity_overflow() == FlagValue::Unset);
+
+        registers.h.set(0x80);
+        RegisterOperations::dec(&mut registers.h, &mut registers.f);
+        assert_eq!(registers.h.get(), 0x7F);
+        assert!(registers.f.get_parity_overflow() == FlagValue::Set);
+    }
+
+    #[test]
+    fn add_a_signed_overflow() {
+        let mut components = runtime_components();
+        let registers = &mut components.registers;
+        registers.a.set(0x80);
+        registers.b.set(0x80);
+        registers.a.add_a(&registers.b, &mut registers.f);
+        assert_eq!(registers.a.get(), 0x00);
+        assert!(registers.f.get_parity_overflow() == FlagValue::Set);
+        assert!(registers.f.get_carry() == FlagValue::Set);
+        assert!(registers.f.get_zero() == FlagValue::Set);
+        assert!(registers.f.get_half_carry() == FlagValue::Unset);
+        assert!(registers.f.get_sign() == FlagValue::Unset);
+    }
+
 }
\ No newline at end of file
@@ -0,0 +1,174 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+///////////////////////
+//
+// Pacing - keeps emulated time in step with wall-clock time
+//
+///////////////////////
+
+// The CPC's Z80 runs at 4Mhz.
+pub const CPC_CLOCK_HZ: u64 = 4_000_000;
+
+// The CPC's video runs at 50Hz. The pacer checks the clock once per frame.
+pub const FRAMES_PER_SECOND: u64 = 50;
+
+// Cycles in one video frame at the CPC's clock speed.
+pub const FRAME_CYCLES: u64 = CPC_CLOCK_HZ / FRAMES_PER_SECOND;
+
+pub trait Clock {
+    // Time passed since the clock was created.
+    fn elapsed(&self) -> Duration;
+    fn sleep(&mut self, duration: Duration);
+}
+
+pub struct SystemClock {
+    start: Instant
+}
+
+impl SystemClock {
+    pub fn new() -> SystemClock {
+        SystemClock { start: Instant::now() }
+    }
+}
+
+impl Clock for SystemClock {
+    fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+// Counts emulated cycles against a wall-clock anchor. Rather than waiting after every
+// instruction, it sleeps once per batch for however far emulation has run ahead of real
+// time. If emulation falls behind, the shortfall carries forward and is made up by
+// sleeping less (or not at all) in later batches.
+pub struct Pacer {
+    // The emulated CPU's clock speed. Each cycle represents 1/clock_hz seconds.
+    pub clock_hz: u64,
+    clock: Box<dyn Clock>,
+    anchor: Duration,
+    cycles: u64,
+    cycles_since_sync: u64
+}
+
+impl Pacer {
+    pub fn default() -> Pacer {
+        Pacer::new(CPC_CLOCK_HZ, Box::new(SystemClock::new()))
+    }
+
+    pub fn new(clock_hz: u64, clock: Box<dyn Clock>) -> Pacer {
+        let anchor = clock.elapsed();
+        Pacer { clock_hz, clock, anchor, cycles: 0, cycles_since_sync: 0 }
+    }
+
+    // Starts counting again from now, e.g. when a run begins after a pause.
+    pub fn reset(&mut self) {
+        self.anchor = self.clock.elapsed();
+        self.cycles = 0;
+        self.cycles_since_sync = 0;
+    }
+
+    // Records cycles that have just been emulated, sleeping if a batch is complete and
+    // emulation is ahead of the clock.
+    pub fn add_cycles(&mut self, cycles: u16) {
+        self.cycles += cycles as u64;
+        self.cycles_since_sync += cycles as u64;
+        if self.cycles_since_sync >= self.batch_cycles() {
+            self.cycles_since_sync = 0;
+            self.sync();
+        }
+    }
+
+    // Sleeps until wall-clock time has caught up with the cycles emulated so far.
+    pub fn sync(&mut self) {
+        let target = self.anchor + self.emulated_time();
+        let now = self.clock.elapsed();
+        if target > now {
+            self.clock.sleep(target - now);
+        }
+    }
+
+    // The real time the cycles emulated so far should have taken.
+    pub fn emulated_time(&self) -> Duration {
+        Duration::from_nanos((self.cycles as u128 * 1_000_000_000 / self.clock_hz as u128) as u64)
+    }
+
+    fn batch_cycles(&self) -> u64 {
+        (self.clock_hz / FRAMES_PER_SECOND).max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, cell::Cell, time::Duration};
+
+    use super::{Clock, Pacer, CPC_CLOCK_HZ};
+
+    // A clock that only moves when slept on, i.e. emulation itself takes no time.
+    struct FakeClock {
+        now: Rc<Cell<Duration>>,
+        sleeps: Rc<Cell<u32>>
+    }
+
+    impl Clock for FakeClock {
+        fn elapsed(&self) -> Duration {
+            self.now.get()
+        }
+
+        fn sleep(&mut self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+            self.sleeps.set(self.sleeps.get() + 1);
+        }
+    }
+
+    fn fake_pacer() -> (Pacer, Rc<Cell<Duration>>, Rc<Cell<u32>>) {
+        let now = Rc::new(Cell::new(Duration::ZERO));
+        let sleeps = Rc::new(Cell::new(0));
+        let clock = FakeClock { now: now.clone(), sleeps: sleeps.clone() };
+        (Pacer::new(CPC_CLOCK_HZ, Box::new(clock)), now, sleeps)
+    }
+
+    #[test]
+    fn million_cycles_take_a_quarter_second() {
+        let (mut pacer, now, sleeps) = fake_pacer();
+        for _ in 0..250_000 {
+            pacer.add_cycles(4);
+        }
+        pacer.sync();
+
+        assert_eq!(pacer.emulated_time(), Duration::from_millis(250));
+        assert_eq!(now.get(), Duration::from_millis(250));
+        // One sleep per 80,000-cycle frame rather than one per instruction.
+        assert_eq!(sleeps.get(), 13);
+    }
+
+    #[test]
+    fn time_lost_is_carried_forward() {
+        let (mut pacer, now, _) = fake_pacer();
+        // The host stalls for 30ms during the first 20ms frame.
+        now.set(Duration::from_millis(30));
+        for _ in 0..20_000 {
+            pacer.add_cycles(4);
+        }
+        assert_eq!(now.get(), Duration::from_millis(30));
+
+        // The next frame only sleeps the 10ms needed to get back in step.
+        for _ in 0..20_000 {
+            pacer.add_cycles(4);
+        }
+        assert_eq!(now.get(), Duration::from_millis(40));
+    }
+
+    #[test]
+    fn clock_speed_is_configurable() {
+        let (mut pacer, now, _) = fake_pacer();
+        pacer.clock_hz = 1_000_000;
+        pacer.add_cycles(1000);
+        pacer.sync();
+        assert_eq!(now.get(), Duration::from_millis(1));
+    }
+}
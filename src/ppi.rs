@@ -0,0 +1,234 @@
+use crate::memory::{PortDevice, FLOATING_BUS};
+use crate::psg::{PsgChip, PsgFunction};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+///////////////////////
+//
+// PPI 8255 - PSG, keyboard and system status
+//
+///////////////////////
+
+// The PPI is reached through #F4xx to #F7xx, with A9 and A8 choosing the port:
+//   #F4xx - port A: the PSG's data bus
+//   #F5xx - port B: status, read only. See PORT_B_STATUS.
+//   #F6xx - port C: bits 0-3 select the keyboard row, 4 is the cassette motor, 5 cassette
+//           write data, and bits 7 and 6 drive the PSG's BDIR and BC1 lines
+//   #F7xx - control
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ppi {
+    pub psg: PsgChip,
+    port_a: u8,
+    port_c: u8,
+    port_a_input: bool,
+    // One byte per keyboard row, one bit per column. A pressed key reads as 0.
+    keyboard: [u8; KEYBOARD_ROWS],
+    vsync: bool
+}
+
+pub const KEYBOARD_ROWS: usize = 10;
+
+// Port B bits other than VSYNC (bit 0): bits 1-3 are the manufacturer links (7 is Amstrad), bit 4
+// says the screen is 50Hz, bit 5 that no expansion is asserting /EXP, bit 6 that the printer is
+// busy (none is attached) and bit 7 is cassette read data.
+const PORT_B_STATUS: u8 = 0x7E;
+
+impl Ppi {
+    pub fn default() -> Ppi {
+        Ppi { psg: PsgChip::default(), port_a: 0, port_c: 0, port_a_input: true, keyboard: [0xFF; KEYBOARD_ROWS], vsync: false }
+    }
+
+    // Presses or releases the key at row (0-9) and column (0-7) of the keyboard matrix.
+    pub fn set_key(&mut self, row: usize, column: usize, pressed: bool) {
+        if pressed {
+            self.keyboard[row] &= !(1 << column);
+        } else {
+            self.keyboard[row] |= 1 << column;
+        }
+    }
+
+    pub fn keyboard_row(&self, row: usize) -> u8 {
+        self.keyboard[row]
+    }
+
+    // Set while the CRTC is in vertical sync, which software polls to wait for the frame flyback.
+    pub fn set_vsync(&mut self, active: bool) {
+        self.vsync = active;
+    }
+
+    // The keyboard row port C currently selects. Rows 10-15 don't exist, so no keys read as pressed.
+    fn selected_keyboard_row(&self) -> u8 {
+        self.keyboard.get((self.port_c & 0x0F) as usize).copied().unwrap_or(0xFF)
+    }
+
+    pub fn port_a(&self) -> u8 {
+        self.port_a
+    }
+
+    pub fn port_b(&self) -> u8 {
+        PORT_B_STATUS | self.vsync as u8
+    }
+
+    pub fn port_c(&self) -> u8 {
+        self.port_c
+    }
+
+    // The mode set control word for the current port directions. Port B is always an input and
+    // port C always an output.
+    pub fn control(&self) -> u8 {
+        0x82 | if self.port_a_input { 0x10 } else { 0 }
+    }
+
+    // The PSG acts on whatever function port C is asking for with port A's output on the bus.
+    fn drive_psg(&mut self) {
+        if !self.port_a_input {
+            self.psg.bus_control(PsgFunction::from_port_c(self.port_c), self.port_a);
+        }
+    }
+
+    // With bit 7 set, a control write sets the port directions (bit 4 makes port A an input) and
+    // clears the outputs. With bit 7 clear, it sets (bit 0 = 1) or resets one bit of port C,
+    // chosen by bits 1-3.
+    fn write_control(&mut self, value: u8) {
+        if value & 0x80 != 0 {
+            self.port_a_input = value & 0x10 != 0;
+            self.port_a = 0;
+            self.port_c = 0;
+        } else {
+            let bit = 1 << ((value >> 1) & 0x07);
+            if value & 0x01 != 0 {
+                self.port_c |= bit;
+            } else {
+                self.port_c &= !bit;
+            }
+            self.drive_psg();
+        }
+    }
+
+    fn read_port_a(&mut self) -> u8 {
+        if !self.port_a_input {
+            return self.port_a;
+        }
+        match PsgFunction::from_port_c(self.port_c) {
+            PsgFunction::Read => {
+                // The selected row's columns are wired to the PSG's I/O port A.
+                self.psg.set_port_a_pins(self.selected_keyboard_row());
+                self.psg.read_selected()
+            }
+            _ => FLOATING_BUS
+        }
+    }
+}
+
+impl PortDevice for Ppi {
+    fn write(&mut self, port: u16, value: u8) {
+        match (port >> 8) & 0x03 {
+            0 => {
+                self.port_a = value;
+                self.drive_psg();
+            }
+            2 => {
+                self.port_c = value;
+                self.drive_psg();
+            }
+            3 => self.write_control(value),
+            _ => {}
+        }
+    }
+
+    fn read(&mut self, port: u16) -> Option<u8> {
+        match (port >> 8) & 0x03 {
+            0 => Some(self.read_port_a()),
+            1 => Some(self.port_b()),
+            2 => Some(self.port_c),
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::memory::PortDevice;
+    use super::Ppi;
+
+    // Port A as output for writing to the PSG, or as input for reading from it.
+    const PORT_A_OUT: u8 = 0x82;
+    const PORT_A_IN: u8 = 0x92;
+
+    #[test]
+    fn out_through_ppi_reaches_psg() {
+        let mut ppi = Ppi::default();
+        ppi.write(0xF782, PORT_A_OUT);
+        // Latch register 8, then write an amplitude to it, returning to inactive in between
+        // as the firmware does.
+        ppi.write(0xF400, 8);
+        ppi.write(0xF600, 0xC0);
+        ppi.write(0xF600, 0x00);
+        ppi.write(0xF400, 0x0C);
+        ppi.write(0xF600, 0x80);
+        ppi.write(0xF600, 0x00);
+        assert_eq!(ppi.psg.amplitude(0), 0x0C);
+
+        ppi.write(0xF700, PORT_A_IN);
+        ppi.write(0xF600, 0x40);
+        assert_eq!(ppi.read(0xF400), Some(0x0C));
+    }
+
+    #[test]
+    fn input_port_a_floats_unless_reading_psg() {
+        let mut ppi = Ppi::default();
+        ppi.write(0xF700, PORT_A_IN);
+        // Writes to an input port go nowhere.
+        ppi.write(0xF400, 0x07);
+        ppi.write(0xF600, 0xC0);
+        assert_eq!(ppi.read(0xF400), Some(0xFF));
+    }
+
+    // Selects PSG register 14, then leaves the PSG reading it with port A as an input, as the
+    // firmware's keyboard scan does.
+    fn select_keyboard_register(ppi: &mut Ppi) {
+        ppi.write(0xF700, PORT_A_OUT);
+        ppi.write(0xF400, 14);
+        ppi.write(0xF600, 0xC0);
+        ppi.write(0xF600, 0x00);
+        ppi.write(0xF700, PORT_A_IN);
+    }
+
+    #[test]
+    fn keyboard_row_reads_through_psg() {
+        let mut ppi = Ppi::default();
+        ppi.set_key(8, 5, true); // A
+        ppi.set_key(8, 3, true); // Q
+        ppi.set_key(2, 0, true); // CLR, on another row
+        select_keyboard_register(&mut ppi);
+
+        ppi.write(0xF600, 0x40 | 8);
+        assert_eq!(ppi.read(0xF400), Some(!0b0010_1000));
+        ppi.write(0xF600, 0x40 | 2);
+        assert_eq!(ppi.read(0xF400), Some(!0b0000_0001));
+        ppi.write(0xF600, 0x40 | 3);
+        assert_eq!(ppi.read(0xF400), Some(0xFF));
+
+        ppi.set_key(8, 5, false);
+        ppi.write(0xF600, 0x40 | 8);
+        assert_eq!(ppi.read(0xF400), Some(!0b0000_1000));
+    }
+
+    #[test]
+    fn port_b_reports_vsync() {
+        let mut ppi = Ppi::default();
+        assert_eq!(ppi.read(0xF500), Some(0x7E));
+        ppi.set_vsync(true);
+        assert_eq!(ppi.read(0xF500), Some(0x7F));
+    }
+
+    #[test]
+    fn control_sets_and_resets_port_c_bits() {
+        let mut ppi = Ppi::default();
+        ppi.write(0xF700, PORT_A_OUT);
+        ppi.write(0xF700, 0x0F); // set bit 7
+        ppi.write(0xF700, 0x0D); // set bit 6
+        ppi.write(0xF700, 0x0C); // reset bit 6
+        assert_eq!(ppi.port_c(), 0x80);
+    }
+}
@@ -0,0 +1,385 @@
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+///////////////////////
+//
+// PSG AY-3-8912 - sound registers and sample generation
+//
+///////////////////////
+
+// The CPU has no direct route to the PSG. Its data lines hang off PPI port A and its BDIR and
+// BC1 control lines off port C bits 7 and 6, which together pick what the chip does with the
+// data bus.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PsgFunction {
+    Inactive, // BDIR 0, BC1 0
+    Read,     // BDIR 0, BC1 1 - the selected register drives the data bus
+    Write,    // BDIR 1, BC1 0 - the data bus is written to the selected register
+    Latch     // BDIR 1, BC1 1 - the data bus selects a register
+}
+
+impl PsgFunction {
+    pub fn from_port_c(value: u8) -> PsgFunction {
+        match value >> 6 {
+            0b01 => PsgFunction::Read,
+            0b10 => PsgFunction::Write,
+            0b11 => PsgFunction::Latch,
+            _ => PsgFunction::Inactive
+        }
+    }
+}
+
+pub const CHANNELS: usize = 3;
+
+// The CPC clocks the PSG at 1MHz. Its tone, noise and envelope counters tick at an eighth of that.
+pub const PSG_CLOCK_HZ: u32 = 1_000_000;
+const TICK_HZ: u32 = PSG_CLOCK_HZ / 8;
+
+// Output level for each 4-bit amplitude. The AY's steps are logarithmic, roughly 3dB apart.
+const VOLUMES: [f32; 16] = [
+    0.0, 0.0137, 0.0205, 0.0291, 0.0423, 0.0618, 0.0847, 0.1369,
+    0.1691, 0.2647, 0.3527, 0.4499, 0.5704, 0.6873, 0.8482, 1.0
+];
+
+// The registers, several of which are narrower than a byte:
+//   R0-R5  - tone period for channels A, B and C: fine (8 bits) then coarse (4 bits)
+//   R6     - noise period (5 bits)
+//   R7     - mixer: bits 0-2 disable tone and bits 3-5 disable noise on A-C (0 is on).
+//            Bits 6-7 set the I/O port directions.
+//   R8-R10 - amplitude for A-C: bits 0-3 a fixed level, or bit 4 to follow the envelope
+//   R11-12 - envelope period, fine then coarse
+//   R13    - envelope shape (4 bits)
+//   R14-15 - the I/O ports
+const REGISTER_MASKS: [u8; 16] = [0xFF, 0x0F, 0xFF, 0x0F, 0xFF, 0x0F, 0x1F, 0xFF, 0x1F, 0x1F, 0x1F, 0xFF, 0xFF, 0x0F, 0xFF, 0xFF];
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PsgChip {
+    selected: usize,
+    registers: [u8; 16],
+    // What's driving the I/O port A pins from outside. On the CPC that's the keyboard.
+    port_a_pins: u8,
+    // Channels can be muted for debugging, independently of the mixer register.
+    channel_enabled: [bool; CHANNELS],
+    // Sound generation state.
+    tone_counters: [u16; CHANNELS],
+    tone_outputs: [bool; CHANNELS],
+    noise_counter: u16,
+    noise_shift: u32,
+    envelope_counter: u32,
+    envelope_step: u8,
+    envelope_attack: bool,
+    envelope_holding: bool,
+    tick_remainder: u32
+}
+
+impl PsgChip {
+    pub fn default() -> PsgChip {
+        PsgChip {
+            selected: 0, registers: [0; 16], port_a_pins: 0xFF, channel_enabled: [true; CHANNELS],
+            tone_counters: [0; CHANNELS], tone_outputs: [false; CHANNELS], noise_counter: 0, noise_shift: 1,
+            envelope_counter: 0, envelope_step: 0, envelope_attack: false, envelope_holding: false, tick_remainder: 0
+        }
+    }
+
+    // Latches the register that following reads and writes use. Values above 15 don't address
+    // this chip, so leave nothing selected until a valid latch.
+    pub fn select(&mut self, register: u8) {
+        self.selected = register as usize;
+    }
+
+    // Writing the envelope shape restarts the envelope.
+    pub fn write_selected(&mut self, value: u8) {
+        if self.selected < self.registers.len() {
+            self.registers[self.selected] = value & REGISTER_MASKS[self.selected];
+        }
+        if self.selected == 13 {
+            self.restart_envelope();
+        }
+    }
+
+    // R14 reads the I/O port A pins when mixer bit 6 makes the port an input, as it does for
+    // keyboard scanning.
+    pub fn read_selected(&self) -> u8 {
+        if self.selected == 14 && self.mixer() & 0x40 == 0 {
+            return self.port_a_pins;
+        }
+        self.registers.get(self.selected).copied().unwrap_or(0xFF)
+    }
+
+    pub fn selected(&self) -> u8 {
+        self.selected as u8
+    }
+
+    pub fn set_port_a_pins(&mut self, value: u8) {
+        self.port_a_pins = value;
+    }
+
+    // Carries out a bus function with value on the data lines, returning what the chip drives
+    // onto them for a read.
+    pub fn bus_control(&mut self, function: PsgFunction, value: u8) -> Option<u8> {
+        match function {
+            PsgFunction::Latch => self.select(value),
+            PsgFunction::Write => self.write_selected(value),
+            PsgFunction::Read => return Some(self.read_selected()),
+            PsgFunction::Inactive => {}
+        }
+        None
+    }
+
+    pub fn registers(&self) -> &[u8; 16] {
+        &self.registers
+    }
+
+    // The 12-bit tone period for channel (0-2 for A-C).
+    pub fn tone_period(&self, channel: usize) -> u16 {
+        ((self.registers[channel * 2 + 1] as u16) << 8) | self.registers[channel * 2] as u16
+    }
+
+    pub fn noise_period(&self) -> u8 {
+        self.registers[6]
+    }
+
+    pub fn mixer(&self) -> u8 {
+        self.registers[7]
+    }
+
+    pub fn amplitude(&self, channel: usize) -> u8 {
+        self.registers[8 + channel]
+    }
+
+    pub fn envelope_period(&self) -> u16 {
+        ((self.registers[12] as u16) << 8) | self.registers[11] as u16
+    }
+
+    pub fn envelope_shape(&self) -> u8 {
+        self.registers[13]
+    }
+
+    pub fn set_channel_enabled(&mut self, channel: usize, enabled: bool) {
+        self.channel_enabled[channel] = enabled;
+    }
+
+    pub fn channel_enabled(&self, channel: usize) -> bool {
+        self.channel_enabled[channel]
+    }
+
+    // Fills samples with mono output between 0.0 and 1.0 at sample_rate, carrying on from
+    // where the last call left off.
+    pub fn generate(&mut self, sample_rate: u32, samples: &mut [f32]) {
+        for sample in samples.iter_mut() {
+            self.tick_remainder += TICK_HZ;
+            while self.tick_remainder >= sample_rate {
+                self.tick_remainder -= sample_rate;
+                self.tick();
+            }
+            *sample = self.mix();
+        }
+    }
+
+    fn tick(&mut self) {
+        for channel in 0..CHANNELS {
+            self.tone_counters[channel] += 1;
+            if self.tone_counters[channel] >= self.tone_period(channel).max(1) {
+                self.tone_counters[channel] = 0;
+                self.tone_outputs[channel] = !self.tone_outputs[channel];
+            }
+        }
+
+        // Noise runs at half the tone rate, from a 17-bit shift register tapped at bits 0 and 3.
+        self.noise_counter += 1;
+        if self.noise_counter >= (self.noise_period().max(1) as u16) * 2 {
+            self.noise_counter = 0;
+            let bit = (self.noise_shift ^ (self.noise_shift >> 3)) & 1;
+            self.noise_shift = (self.noise_shift >> 1) | (bit << 16);
+        }
+
+        // The envelope takes one of its 16 steps every 256 PSG clocks times the period.
+        self.envelope_counter += 1;
+        if self.envelope_counter >= 32 * self.envelope_period().max(1) as u32 {
+            self.envelope_counter = 0;
+            self.step_envelope();
+        }
+    }
+
+    // Envelope shape bits: 3 continue, 2 attack (count up rather than down), 1 alternate, 0 hold.
+    fn restart_envelope(&mut self) {
+        self.envelope_counter = 0;
+        self.envelope_step = 0;
+        self.envelope_attack = self.envelope_shape() & 0x04 != 0;
+        self.envelope_holding = false;
+    }
+
+    fn step_envelope(&mut self) {
+        if self.envelope_holding {
+            return;
+        }
+        if self.envelope_step < 15 {
+            self.envelope_step += 1;
+            return;
+        }
+        // End of a cycle. Without continue, the envelope drops to 0 and stays there.
+        let shape = self.envelope_shape();
+        if shape & 0x08 == 0 {
+            self.envelope_attack = false;
+            self.envelope_holding = true;
+            return;
+        }
+        if shape & 0x02 != 0 {
+            self.envelope_attack = !self.envelope_attack;
+        }
+        if shape & 0x01 != 0 {
+            self.envelope_holding = true;
+        } else {
+            self.envelope_step = 0;
+        }
+    }
+
+    fn envelope_level(&self) -> u8 {
+        if self.envelope_attack { self.envelope_step } else { 15 - self.envelope_step }
+    }
+
+    fn mix(&self) -> f32 {
+        let mixer = self.mixer();
+        let noise = self.noise_shift & 1 != 0;
+        let mut total = 0.0;
+        for channel in 0..CHANNELS {
+            let tone_off = mixer & (1 << channel) != 0;
+            let noise_off = mixer & (8 << channel) != 0;
+            if !self.channel_enabled[channel] || !((self.tone_outputs[channel] || tone_off) && (noise || noise_off)) {
+                continue;
+            }
+            let amplitude = self.amplitude(channel);
+            let level = if amplitude & 0x10 != 0 { self.envelope_level() } else { amplitude & 0x0F };
+            total += VOLUMES[level as usize];
+        }
+        total / CHANNELS as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PsgChip, PsgFunction};
+
+    const SAMPLE_RATE: u32 = 44_100;
+
+    fn write_registers(psg: &mut PsgChip, writes: &[(u8, u8)]) {
+        for (register, value) in writes {
+            psg.select(*register);
+            psg.write_selected(*value);
+        }
+    }
+
+    // Counts sign changes about the mean, i.e. twice the frequency for one second of samples.
+    fn zero_crossings(samples: &[f32]) -> usize {
+        let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        samples.windows(2).filter(|pair| (pair[0] > mean) != (pair[1] > mean)).count()
+    }
+
+    #[test]
+    fn latch_write_and_read_back() {
+        let mut psg = PsgChip::default();
+        assert_eq!(psg.bus_control(PsgFunction::Latch, 8), None);
+        psg.bus_control(PsgFunction::Write, 0x0F);
+        assert_eq!(psg.bus_control(PsgFunction::Read, 0xFF), Some(0x0F));
+        assert_eq!(psg.amplitude(0), 0x0F);
+        assert_eq!(psg.registers()[8], 0x0F);
+    }
+
+    #[test]
+    fn registers_are_masked_and_combined() {
+        let mut psg = PsgChip::default();
+        for (register, value) in [(2, 0x34), (3, 0xF2), (6, 0xFF), (11, 0x00), (12, 0x10), (13, 0xFE)] {
+            psg.select(register);
+            psg.write_selected(value);
+        }
+        assert_eq!(psg.tone_period(1), 0x234);
+        assert_eq!(psg.noise_period(), 0x1F);
+        assert_eq!(psg.envelope_period(), 0x1000);
+        assert_eq!(psg.envelope_shape(), 0x0E);
+    }
+
+    #[test]
+    fn invalid_latch_selects_nothing() {
+        let mut psg = PsgChip::default();
+        psg.select(0x18);
+        psg.write_selected(0x55);
+        assert_eq!(psg.read_selected(), 0xFF);
+        assert!(psg.registers().iter().all(|value| *value == 0));
+    }
+
+    #[test]
+    fn r14_reads_port_a_pins_as_input() {
+        let mut psg = PsgChip::default();
+        psg.set_port_a_pins(0xFB);
+        write_registers(&mut psg, &[(14, 0x12)]);
+        assert_eq!(psg.read_selected(), 0xFB);
+
+        write_registers(&mut psg, &[(7, 0x40), (14, 0x12)]);
+        assert_eq!(psg.read_selected(), 0x12);
+    }
+
+    #[test]
+    fn function_from_port_c() {
+        assert_eq!(PsgFunction::from_port_c(0x3F), PsgFunction::Inactive);
+        assert_eq!(PsgFunction::from_port_c(0x40), PsgFunction::Read);
+        assert_eq!(PsgFunction::from_port_c(0x80), PsgFunction::Write);
+        assert_eq!(PsgFunction::from_port_c(0xC0), PsgFunction::Latch);
+    }
+
+    #[test]
+    fn tone_at_1khz() {
+        let mut psg = PsgChip::default();
+        // Period 62 gives 1MHz / (16 * 62) = 1008Hz on channel A, with noise off.
+        write_registers(&mut psg, &[(0, 62), (1, 0), (7, 0b0011_1110), (8, 0x0F)]);
+        let mut samples = vec![0.0; SAMPLE_RATE as usize];
+        psg.generate(SAMPLE_RATE, &mut samples);
+
+        let crossings = zero_crossings(&samples);
+        assert!((2000..2030).contains(&crossings), "{} crossings", crossings);
+        assert!(samples.iter().all(|sample| (0.0..=1.0).contains(sample)));
+    }
+
+    #[test]
+    fn silent_without_amplitude() {
+        let mut psg = PsgChip::default();
+        write_registers(&mut psg, &[(0, 62), (7, 0b0011_1110)]);
+        let mut samples = vec![1.0; 1000];
+        psg.generate(SAMPLE_RATE, &mut samples);
+        assert!(samples.iter().all(|sample| *sample == 0.0));
+    }
+
+    #[test]
+    fn envelope_decays_then_holds_at_zero() {
+        let mut psg = PsgChip::default();
+        // Tone and noise off leave the channel high, so the output follows the envelope alone.
+        // Shape 0 decays once; period 1 takes 16 * 256us = 4ms.
+        write_registers(&mut psg, &[(7, 0b0011_1111), (8, 0x10), (11, 1), (12, 0), (13, 0)]);
+        let mut samples = vec![0.0; 441];
+        psg.generate(SAMPLE_RATE, &mut samples);
+
+        assert_eq!(samples[0], 1.0 / 3.0);
+        assert!(samples.windows(2).all(|pair| pair[1] <= pair[0]));
+        assert_eq!(samples[440], 0.0);
+    }
+
+    #[test]
+    fn muting_a_leaves_b_and_c() {
+        let tones = [(0, 50), (2, 70), (4, 90), (7, 0b0011_1000), (8, 0x0F), (9, 0x0F), (10, 0x0F)];
+        let mut muted = PsgChip::default();
+        write_registers(&mut muted, &tones);
+        muted.set_channel_enabled(0, false);
+        assert!(!muted.channel_enabled(0));
+        assert!(muted.channel_enabled(1));
+
+        let mut without_a = PsgChip::default();
+        write_registers(&mut without_a, &tones);
+        write_registers(&mut without_a, &[(8, 0)]);
+
+        let mut muted_samples = vec![0.0; 4410];
+        let mut expected = vec![0.0; 4410];
+        muted.generate(SAMPLE_RATE, &mut muted_samples);
+        without_a.generate(SAMPLE_RATE, &mut expected);
+        assert_eq!(muted_samples, expected);
+        assert!(muted_samples.iter().any(|sample| *sample > 0.0));
+    }
+}
@@ -1,14 +1,27 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
 use std::ops::Add;
-use std::thread::{Thread, self};
-use std::time::{self, SystemTime};
+use std::path::Path;
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::VecDeque;
 
 ///////////////////////
 //
 // Runtime components - memory, registers, instruction set 
 //
 ///////////////////////
-use crate::memory::{Memory, Registers, AddressBus, DataBus, DefaultRegister};
-use crate::instruction_set::{InstructionSet, Instruction, Operands};
+use crate::memory::{Memory, Registers, AddressBus, DataBus, DefaultRegister, Register, PortSelect, PortDevice, UpperRomSelect};
+use crate::instruction_set::{InstructionSet, Instruction, Operands, OperandKind, UnknownOpcodeError, index::IndexRegister, render};
+use crate::utils::{combine_to_double_byte, split_double_byte, signed};
+use crate::pacing::{Pacer, FRAME_CYCLES, FRAMES_PER_SECOND};
+use crate::gate_array::{GateArray, BORDER};
+use crate::crtc::Crtc;
+use crate::ppi::Ppi;
+use crate::fdc::Fdc;
+use crate::sna::Snapshot;
+use crate::screen::{Screen, Mode};
 
 use log::{debug, error, log_enabled, info, Level};
 
@@ -24,138 +37,1040 @@ impl RuntimeComponents {
         let mem = Memory::default();
         let registers: Registers = Registers::default();
         let address_bus = AddressBus { value: 0 };
-        let data_bus = DataBus { };
+        let data_bus = DataBus::default();
         RuntimeComponents { mem, registers, address_bus, data_bus }
     }
 }
 
+#[derive(Debug, PartialEq)]
+pub enum RuntimeError {
+    // run_until executed its maximum number of instructions without PC reaching the target.
+    InstructionLimitReached { target: u16, pc: u16, instructions: usize },
+    // The instruction at pc couldn't be decoded. PC is left pointing at its first byte.
+    UnknownOpcode { pc: u16, error: UnknownOpcodeError }
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuntimeError::InstructionLimitReached { target, pc, instructions } =>
+                write!(f, "PC didn't reach #{:04X} within {} instructions (stopped at #{:04X})", target, instructions, pc),
+            RuntimeError::UnknownOpcode { pc, error } =>
+                write!(f, "{} at #{:04X}", error, pc)
+        }
+    }
+}
+
 pub struct Runtime {
     instruction_set: InstructionSet,
-    pub components: RuntimeComponents
+    pub components: RuntimeComponents,
+    // When false, instructions run back to back without consulting the clock or sleeping.
+    pub throttle: bool,
+    pub pacer: Pacer,
+    // Devices on the I/O bus. The DataBus holds them too, to route IN and OUT.
+    pub gate_array: Rc<RefCell<GateArray>>,
+    pub crtc: Rc<RefCell<Crtc>>,
+    pub ppi: Rc<RefCell<Ppi>>,
+    pub rom_select: Rc<RefCell<UpperRomSelect>>,
+    pub fdc: Rc<RefCell<Fdc>>,
+    // When set, run_frame generates a frame of PSG output at this rate onto the end of audio,
+    // for a sound device to drain. Only the most recent AUDIO_BUFFER_FRAMES are kept.
+    pub audio_sample_rate: Option<u32>,
+    pub audio: VecDeque<f32>,
+    // Where set_trace sends a line per instruction executed.
+    trace: Option<Box<dyn Write>>
 }
 
+const AUDIO_BUFFER_FRAMES: usize = 10;
+
 impl Runtime {
 
     pub fn default() -> Runtime {
         Runtime::new(InstructionSet::default(), RuntimeComponents::default())
     }
 
-    fn new(instruction_set: InstructionSet, components: RuntimeComponents) -> Runtime {
-        Runtime { instruction_set, components }
+    fn new(instruction_set: InstructionSet, mut components: RuntimeComponents) -> Runtime {
+        let gate_array = Rc::new(RefCell::new(GateArray::default()));
+        components.data_bus.attach(PortSelect::GateArray, gate_array.clone());
+        let crtc = Rc::new(RefCell::new(Crtc::default()));
+        components.data_bus.attach(PortSelect::Crtc, crtc.clone());
+        let ppi = Rc::new(RefCell::new(Ppi::default()));
+        components.data_bus.attach(PortSelect::Ppi, ppi.clone());
+        let rom_select = Rc::new(RefCell::new(UpperRomSelect::default()));
+        components.data_bus.attach(PortSelect::RomSelect, rom_select.clone());
+        let fdc = Rc::new(RefCell::new(Fdc::default()));
+        components.data_bus.attach(PortSelect::Peripheral, fdc.clone());
+        Runtime {
+            instruction_set, components, throttle: true, pacer: Pacer::default(), gate_array, crtc, ppi, rom_select, fdc,
+            audio_sample_rate: None, audio: VecDeque::new(), trace: None
+        }
+    }
+
+    // Writes a line for every instruction executed from now on: the same address, machine code,
+    // assembly and cycles as the debug log, followed by AF, BC, DE, HL and SP once it has run.
+    pub fn set_trace(&mut self, writer: Box<dyn Write>) {
+        self.trace = Some(writer);
+    }
+
+    // Reads memory as the CPU sees it, with the current RAM configuration and any enabled ROMs.
+    pub fn peek(&self, addr: u16) -> u8 {
+        self.components.mem.read_u8(addr)
+    }
+
+    // Writes to the RAM paged in at addr. As with the CPU, ROM showing there is left unchanged.
+    pub fn poke(&mut self, addr: u16, value: u8) {
+        self.components.mem.write_u8(addr, value);
+    }
+
+    // Peeks len bytes from addr onwards, wrapping round from #FFFF to #0000.
+    pub fn peek_range(&self, addr: u16, len: usize) -> Vec<u8> {
+        (0..len).map(|offset| self.peek(addr.wrapping_add(offset as u16))).collect()
     }
 
-    pub fn load_rom_from_bytes(&mut self, bytes: &[u8]) {
+    // Loads the OS into the lower ROM and, for a 32K image, BASIC into upper ROM slot 0.
+    pub fn load_rom_from_bytes(&mut self, bytes: &[u8]) -> Result<(), String> {
         match bytes.len() {
             0x4000 => self.load_os_rom(bytes),
             0x8000 => {
                 self.load_os_rom(&bytes[..=0x3FFF]);
-                self.load_expansion_rom(&bytes[0x4000..]);
+                self.load_rom(0, &bytes[0x4000..]);
             },
-            _ => {
-                error!("Unexpected ROM size: {}", bytes.len());
-                assert!(false);
+            _ => return Err(format!("Unexpected ROM size: {}", bytes.len()))
+        }
+        Ok(())
+    }
+
+    // Restores the machine from an SNA snapshot, leaving it ready to continue from the saved PC.
+    // ROMs aren't part of a snapshot, so they need loading as usual.
+    pub fn load_snapshot(&mut self, bytes: &[u8]) -> Result<(), &'static str> {
+        let snapshot = Snapshot::from_bytes(bytes)?;
+
+        let registers = &mut self.components.registers;
+        let pairs = [
+            (&mut registers.b, &mut registers.c, snapshot.bc), (&mut registers.d, &mut registers.e, snapshot.de),
+            (&mut registers.h, &mut registers.l, snapshot.hl), (&mut registers.b_, &mut registers.c_, snapshot.bc_),
+            (&mut registers.d_, &mut registers.e_, snapshot.de_), (&mut registers.h_, &mut registers.l_, snapshot.hl_)
+        ];
+        for (high, low, value) in pairs {
+            let (h, l) = split_double_byte(value);
+            high.set(h);
+            low.set(l);
+        }
+        let (a, f) = split_double_byte(snapshot.af);
+        registers.a.set(a);
+        registers.f.set(f);
+        let (a_, f_) = split_double_byte(snapshot.af_);
+        registers.a_.set(a_);
+        registers.f_.set(f_);
+        registers.i.set(snapshot.i);
+        registers.r.set(snapshot.r);
+        registers.ix = snapshot.ix;
+        registers.iy = snapshot.iy;
+        registers.sp.set(snapshot.sp as usize);
+        registers.pc.set(snapshot.pc);
+        registers.iff1 = snapshot.iff1;
+        registers.iff2 = snapshot.iff2;
+        registers.interrupt_mode = snapshot.interrupt_mode;
+        registers.halted = false;
+        registers.ei_pending = false;
+
+        let mem = &mut self.components.mem;
+        let (base, expansion) = snapshot.memory.split_at(snapshot.memory.len().min(0x10000));
+        mem.locations[..base.len()].copy_from_slice(base);
+        let expansion_length = expansion.len().min(mem.expansion.len());
+        mem.expansion[..expansion_length].copy_from_slice(&expansion[..expansion_length]);
+
+        // Devices are put back in their saved state by replaying the writes that set it up.
+        {
+            let mut gate_array = self.gate_array.borrow_mut();
+            for (pen, ink) in snapshot.inks.iter().enumerate() {
+                let select = if pen == BORDER { 0x10 } else { pen as u8 };
+                gate_array.write(select);
+                gate_array.write(0x40 | ink);
             }
+            gate_array.write(snapshot.selected_pen & 0x1F);
+            // Leave out bit 4, which would reset the interrupt counter rather than being state.
+            gate_array.write(0x80 | (snapshot.rmr & 0x0F));
+            gate_array.write(0xC0 | snapshot.ram_config);
         }
+        {
+            let mut crtc = self.crtc.borrow_mut();
+            for (register, value) in snapshot.crtc_registers.iter().enumerate() {
+                crtc.select(register as u8);
+                crtc.write_selected(*value);
+            }
+            crtc.select(snapshot.crtc_selected);
+        }
+        self.rom_select.borrow_mut().selected = snapshot.rom_selected;
+        {
+            let mut ppi = self.ppi.borrow_mut();
+            for (register, value) in snapshot.psg_registers.iter().enumerate() {
+                ppi.psg.select(register as u8);
+                ppi.psg.write_selected(*value);
+            }
+            ppi.psg.select(snapshot.psg_selected);
+            // Port B is all inputs, so only A, C and the control register are restored.
+            ppi.write(0xF700, snapshot.ppi_control | 0x80);
+            ppi.write(0xF400, snapshot.ppi_a);
+            ppi.write(0xF600, snapshot.ppi_c);
+        }
+        self.sync_memory_config();
+        Ok(())
+    }
+
+    // Captures the machine as a version 1 SNA snapshot, with the 6128's second 64K after base
+    // RAM. Only state the format has room for is kept: a pending EI or HALT is lost.
+    pub fn save_snapshot(&self) -> Vec<u8> {
+        let registers = &self.components.registers;
+        let pair = |high: &dyn Register, low: &dyn Register| combine_to_double_byte(high.get(), low.get());
+        let mem = &self.components.mem;
+        let mut memory = mem.locations.to_vec();
+        memory.extend(&mem.expansion);
+
+        let gate_array = self.gate_array.borrow();
+        let crtc = self.crtc.borrow();
+        let ppi = self.ppi.borrow();
+        let selected_pen = match gate_array.selected_pen() {
+            BORDER => 0x10,
+            pen => pen as u8
+        };
+        Snapshot {
+            version: 1,
+            af: pair(&registers.a, &registers.f),
+            bc: pair(&registers.b, &registers.c),
+            de: pair(&registers.d, &registers.e),
+            hl: pair(&registers.h, &registers.l),
+            r: registers.r.get(),
+            i: registers.i.get(),
+            iff1: registers.iff1,
+            iff2: registers.iff2,
+            ix: registers.ix,
+            iy: registers.iy,
+            sp: registers.sp.get() as u16,
+            pc: registers.pc.get(),
+            interrupt_mode: registers.interrupt_mode,
+            af_: pair(&registers.a_, &registers.f_),
+            bc_: pair(&registers.b_, &registers.c_),
+            de_: pair(&registers.d_, &registers.e_),
+            hl_: pair(&registers.h_, &registers.l_),
+            selected_pen,
+            inks: std::array::from_fn(|pen| gate_array.ink(pen)),
+            rmr: gate_array.rmr(),
+            ram_config: gate_array.ram_config(),
+            crtc_selected: crtc.selected(),
+            crtc_registers: std::array::from_fn(|register| crtc.register(register)),
+            rom_selected: self.rom_select.borrow().selected,
+            ppi_a: ppi.port_a(),
+            ppi_b: ppi.port_b(),
+            ppi_c: ppi.port_c(),
+            ppi_control: ppi.control(),
+            psg_selected: ppi.psg.selected(),
+            psg_registers: *ppi.psg.registers(),
+            memory
+        }.to_bytes()
     }
 
     fn load_os_rom(&mut self, bytes: &[u8]) {
-        let mut i = 0;
-        while i < 0x4000 {
-            self.components.mem.locations[i] = bytes[i];
-            i += 1;
-        }
+        self.components.mem.load_lower_rom(bytes);
     }
 
-    fn load_expansion_rom(&mut self, bytes: &[u8]) {
-        let mut i = 0xC000;
-        while i < 0xFFFF {
-            self.components.mem.locations[i] = bytes[i-0xC000];
-            i += 1;
-        }
+    // Loads a 16K upper ROM, e.g. AMSDOS into slot 7.
+    pub fn load_rom(&mut self, slot: u8, bytes: &[u8]) {
+        self.components.mem.load_upper_rom(slot, bytes);
     }
 
+    // Decodes video memory as the CRTC and Gate Array are currently set up to display it.
+    pub fn screen(&self) -> Screen {
+        let mode = Mode::from_gate_array(self.gate_array.borrow().mode());
+        Screen::decode(&self.components.mem, &self.crtc.borrow(), mode)
+    }
 
-    pub fn run(&mut self, start_address: u16) {
+    // Saves the current screen, border included, as a PNG. The image is at the mode's native
+    // resolution, so mode 0 pixels come out square rather than twice as wide as they're shown.
+    pub fn screenshot(&self, path: &Path) -> io::Result<()> {
+        let screen = self.screen();
+        let (width, height) = screen.rgba_size();
+        let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&screen.to_rgba(&self.gate_array.borrow()))?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    // Runs from start_address until an instruction can't be executed.
+    pub fn run(&mut self, start_address: u16) -> Result<(), RuntimeError> {
         self.components.registers.pc.set(start_address);
+        self.pacer.reset();
         loop {
-            let pc = self.components.registers.pc.get();
-            let instruction_byte = self.components.mem.locations[self.components.registers.pc.get() as usize];
-            
-            let instruction:&Box<dyn Instruction>;
-            match instruction_byte {
-                0xCB => {
-                    self.components.registers.pc.inc();
-                    let instruction_byte = self.components.mem.locations[self.components.registers.pc.get() as usize];
-                    instruction = self.instruction_set.bit_instruction_for(instruction_byte);
-                }
-                0xDD => {
-                    self.components.registers.pc.inc();
-                    let instruction_byte = self.components.mem.locations[self.components.registers.pc.get() as usize];
-                    instruction = self.instruction_set.index_instruction_for(instruction_byte);
-                }
-                0xED => {
-                    self.components.registers.pc.inc();
-                    let instruction_byte = self.components.mem.locations[self.components.registers.pc.get() as usize];
-                    instruction = self.instruction_set.extended_instruction_for(instruction_byte);
-                },
-                basic_instruction_byte => {
-                    instruction = self.instruction_set.instruction_for(basic_instruction_byte);
-                }
-            };
-            
-            let inst_machine_code: String;
-            let inst_assembly: String;
-
-            let op_count = instruction.operand_count();
-            let operands: Operands;
-            match op_count {
-                0 => { 
-                    operands = Operands::None;
-                    inst_machine_code = instruction.machine_code().to_string();
-                    inst_assembly = instruction.assembly().to_string();
-                }
-                1 => {
-                    self.components.registers.pc.inc();
-                    let operand1 = self.components.mem.locations[self.components.registers.pc.get() as usize];
-                    operands = Operands::One(operand1);
-                    let op1 = format!("{:0>2X}", &operand1);
-                    inst_machine_code = instruction.machine_code().replace("*1", &op1);
-                    inst_assembly = instruction.assembly().replace("*1", &op1);
-                }
-                2 => {
-                    self.components.registers.pc.inc();
-                    let operand1 = self.components.mem.locations[self.components.registers.pc.get() as usize];
-                    self.components.registers.pc.inc();
-                    let operand2 = self.components.mem.locations[self.components.registers.pc.get() as usize];
-                    operands = Operands::Two(operand1, operand2);
-                    let op1 = format!("{:0>2X}", &operand1);
-                    let op2 = format!("{:0>2X}", &operand2);
-                    inst_machine_code = instruction.machine_code().replace("*1", &op1).replace("*2", &op2);
-                    inst_assembly = instruction.assembly().replace("*1", &op1).replace("*2", &op2);
+            self.paced_step()?;
+        } 
+    }
+
+    // Runs from start_address until at least max_cycles have elapsed, returning the number of
+    // cycles actually executed. With throttling off this never touches the clock, so repeated
+    // runs of the same program produce identical state.
+    pub fn run_for(&mut self, start_address: u16, max_cycles: u64) -> Result<u64, RuntimeError> {
+        self.components.registers.pc.set(start_address);
+        self.pacer.reset();
+        let mut total_cycles: u64 = 0;
+        while total_cycles < max_cycles {
+            total_cycles += self.paced_step()? as u64;
+        }
+        Ok(total_cycles)
+    }
+
+    // Runs one video frame's worth of cycles on from the current PC, returning the number actually
+    // executed. It never sleeps: a front end that presents each frame can pace itself per frame
+    // instead of per instruction.
+    pub fn run_frame(&mut self) -> Result<u64, RuntimeError> {
+        let mut total_cycles: u64 = 0;
+        while total_cycles < FRAME_CYCLES {
+            total_cycles += self.step()? as u64;
+        }
+        if let Some(sample_rate) = self.audio_sample_rate {
+            self.generate_audio(sample_rate);
+        }
+        Ok(total_cycles)
+    }
+
+    fn generate_audio(&mut self, sample_rate: u32) {
+        let frame_samples = (sample_rate as u64 / FRAMES_PER_SECOND) as usize;
+        let mut samples = vec![0.0; frame_samples];
+        self.ppi.borrow_mut().psg.generate(sample_rate, &mut samples);
+        self.audio.extend(samples);
+        let excess = self.audio.len().saturating_sub(frame_samples * AUDIO_BUFFER_FRAMES);
+        self.audio.drain(..excess);
+    }
+
+    // Steps until PC reaches target, returning the number of instructions executed. Gives up with
+    // an error after max_instructions so a target that's never reached can't hang the caller.
+    // Like step, this runs unthrottled.
+    pub fn run_until(&mut self, target: u16, max_instructions: usize) -> Result<usize, RuntimeError> {
+        let mut instructions = 0;
+        while self.components.registers.pc.get() != target {
+            if instructions == max_instructions {
+                return Err(RuntimeError::InstructionLimitReached { target, pc: self.components.registers.pc.get(), instructions });
+            }
+            self.step()?;
+            instructions += 1;
+        }
+        Ok(instructions)
+    }
+
+    // Steps once, then gives the pacer a chance to sleep if emulation is ahead of real time.
+    fn paced_step(&mut self) -> Result<u16, RuntimeError> {
+        let cycles = self.step()?;
+        if self.throttle {
+            self.pacer.add_cycles(cycles);
+        }
+        Ok(cycles)
+    }
+
+    // Fetches, decodes and executes the instruction at PC, returning the number of cycles it took.
+    // This never sleeps, so it can drive single-stepping, debuggers and tests directly.
+    // Interrupts are accepted here, between instructions, and count as a step of their own.
+    pub fn step(&mut self) -> Result<u16, RuntimeError> {
+        // Only the step straight after EI is held off; EI sets the flag again if it runs now.
+        let ei_pending = std::mem::replace(&mut self.components.registers.ei_pending, false);
+        let cycles = if self.components.registers.iff1 && !ei_pending && self.gate_array.borrow().interrupt_pending() {
+            self.accept_interrupt()
+        } else if self.components.registers.halted {
+            // A halted CPU keeps executing NOPs in place; nothing is fetched and PC doesn't move.
+            4
+        } else {
+            self.execute_instruction()?
+        };
+        self.gate_array.borrow_mut().add_cycles(cycles);
+        let vsync = {
+            let mut crtc = self.crtc.borrow_mut();
+            crtc.add_cycles(cycles);
+            crtc.vsync()
+        };
+        self.ppi.borrow_mut().set_vsync(vsync);
+        // Pick up any paging or ROM change an OUT just made, ready for the next instruction.
+        self.sync_memory_config();
+        Ok(cycles)
+    }
+
+    // Pages RAM and ROM into the CPU's view as the Gate Array and ROM select port have them.
+    fn sync_memory_config(&mut self) {
+        let gate_array = self.gate_array.borrow();
+        self.components.mem.set_ram_config(gate_array.ram_config());
+        self.components.mem.set_rom_enables(gate_array.lower_rom_enabled(), gate_array.upper_rom_enabled());
+        self.components.mem.upper_roms.select(self.rom_select.borrow().selected);
+    }
+
+    // Pushes PC and jumps to the interrupt handler, returning the cycles the response took. The
+    // CPC's data bus reads #FF during the acknowledge, so mode 0 executes RST #38 like mode 1, and
+    // mode 2 uses #FF as the vector table offset.
+    fn accept_interrupt(&mut self) -> u16 {
+        self.gate_array.borrow_mut().acknowledge_interrupt();
+        let registers = &mut self.components.registers;
+        registers.halted = false;
+        registers.iff1 = false;
+        registers.iff2 = false;
+        registers.sp.push(&mut self.components.mem, registers.pc.get());
+        let (handler, cycles) = match registers.interrupt_mode {
+            2 => (Runtime::interrupt_mode_2_handler(&self.components, 0xFF), 19),
+            _ => (0x0038, 13)
+        };
+        self.components.registers.pc.set(handler);
+        cycles
+    }
+
+    fn execute_instruction(&mut self) -> Result<u16, RuntimeError> {
+        let pc = self.components.registers.pc.get();
+        let instruction_byte = self.components.mem.read_u8(self.components.registers.pc.get());
+        
+        let decoded: Result<&dyn Instruction, UnknownOpcodeError>;
+        // DD CB and FD CB put their displacement before the opcode, so it's read during decoding.
+        let mut displacement: Option<i8> = None;
+        match instruction_byte {
+            0xCB => {
+                self.components.registers.pc.inc();
+                let instruction_byte = self.components.mem.read_u8(self.components.registers.pc.get());
+                decoded = self.instruction_set.bit_instruction_for(instruction_byte);
+            }
+            0xDD => {
+                self.components.registers.pc.inc();
+                let instruction_byte = self.components.mem.read_u8(self.components.registers.pc.get());
+                if instruction_byte == 0xCB {
+                    displacement = Some(self.fetch_displacement());
+                    let instruction_byte = self.components.mem.read_u8(self.components.registers.pc.get());
+                    decoded = self.instruction_set.indexed_bit_instruction_for(IndexRegister::IX, instruction_byte);
+                } else {
+                    decoded = self.instruction_set.index_instruction_for(IndexRegister::IX, instruction_byte);
                 }
-                _ => {
-                    operands = Operands::None;
-                    inst_machine_code = "".to_string();
-                    inst_assembly = "".to_string();
-                    error!("Wrong op count returned for instruction at {}", self.components.registers.pc.get());
-                    assert!(false);
+            }
+            0xFD => {
+                self.components.registers.pc.inc();
+                let instruction_byte = self.components.mem.read_u8(self.components.registers.pc.get());
+                if instruction_byte == 0xCB {
+                    displacement = Some(self.fetch_displacement());
+                    let instruction_byte = self.components.mem.read_u8(self.components.registers.pc.get());
+                    decoded = self.instruction_set.indexed_bit_instruction_for(IndexRegister::IY, instruction_byte);
+                } else {
+                    decoded = self.instruction_set.index_instruction_for(IndexRegister::IY, instruction_byte);
                 }
             }
-            self.components.registers.pc.inc();
-            let mem = &mut self.components.mem;
-            let registers = &mut self.components.registers;
-            
-            let start_time = SystemTime::now();
-            let cycles = instruction.execute(&mut self.components, operands);
-
-            let mut elapsed = start_time.elapsed().unwrap().as_nanos();
-            let target_elapsed = cycles as u128 * 250u128; // 1 cycle is 250 nanoseconds on a 4Mhz chip.
-            while elapsed < target_elapsed { 
-                thread::sleep(time::Duration::from_nanos(1));
-                elapsed = start_time.elapsed().unwrap().as_nanos();
+            0xED => {
+                self.components.registers.pc.inc();
+                let instruction_byte = self.components.mem.read_u8(self.components.registers.pc.get());
+                decoded = self.instruction_set.extended_instruction_for(instruction_byte);
+            },
+            basic_instruction_byte => {
+                decoded = self.instruction_set.instruction_for(basic_instruction_byte);
             }
-            debug!("{:0>4X}\t{: <8}\t{: <12}\t({}/{}µs)", pc, inst_machine_code, inst_assembly, cycles, elapsed/1000);
-        } 
+        };
+        let instruction = decoded.map_err(|error| {
+            self.components.registers.pc.set(pc);
+            RuntimeError::UnknownOpcode { pc, error }
+        })?;
+        
+        let operands = match displacement {
+            Some(offset) => Operands::Relative(offset),
+            None => Runtime::fetch_operands(&mut self.components, instruction.operand_kind())
+        };
+        self.components.registers.pc.inc();
+        // Read the bytes before executing, in case the instruction overwrites itself.
+        let length = self.components.registers.pc.get().wrapping_sub(pc);
+        let bytes: Vec<u8> = (0..length).map(|offset| self.components.mem.read_u8(pc.wrapping_add(offset))).collect();
+        let (inst_machine_code, inst_assembly) = render(instruction, &operands, pc, &bytes);
+
+        let cycles = instruction.execute(&mut self.components, operands);
+        debug!("{:0>4X}\t{: <8}\t{: <12}\t({})", pc, inst_machine_code, inst_assembly, cycles);
+        if let Some(trace) = &mut self.trace {
+            let registers = &self.components.registers;
+            let pair = |high: &dyn Register, low: &dyn Register| combine_to_double_byte(high.get(), low.get());
+            let written = writeln!(
+                trace, "{:0>4X}\t{: <8}\t{: <12}\t({})\tAF={:0>4X} BC={:0>4X} DE={:0>4X} HL={:0>4X} SP={:0>4X}",
+                pc, inst_machine_code, inst_assembly, cycles, pair(&registers.a, &registers.f), pair(&registers.b, &registers.c),
+                pair(&registers.d, &registers.e), pair(&registers.h, &registers.l), registers.sp.get()
+            );
+            // Stop tracing rather than fail the run if the sink goes away, e.g. the disk fills up.
+            if let Err(e) = written {
+                error!("Couldn't write trace, stopping it: {}", e);
+                self.trace = None;
+            }
+        }
+        Ok(cycles)
+    }
+
+    // Reads the displacement byte after a DD CB or FD CB prefix, leaving PC on the opcode that follows it.
+    fn fetch_displacement(&mut self) -> i8 {
+        self.components.registers.pc.inc();
+        let offset = signed(self.components.mem.read_u8(self.components.registers.pc.get()));
+        self.components.registers.pc.inc();
+        offset
+    }
+
+    // In interrupt mode 2 the device supplies the low byte of a table address whose high byte is I.
+    // The handler address is the word stored at that location.
+    pub(crate) fn interrupt_mode_2_handler(components: &RuntimeComponents, low: u8) -> u16 {
+        let vector = combine_to_double_byte(components.registers.i.get(), low);
+        components.mem.read_u16(vector)
+    }
+
+    // Reads the bytes following the opcode at PC and packages them according to the operand kind.
+    // PC is left on the last byte read.
+    fn fetch_operands(components: &mut RuntimeComponents, kind: OperandKind) -> Operands {
+        let mut next_byte = || {
+            components.registers.pc.inc();
+            components.mem.read_u8(components.registers.pc.get())
+        };
+        match kind {
+            OperandKind::None => Operands::None,
+            OperandKind::Immediate8 => Operands::Immediate(next_byte()),
+            OperandKind::Relative8 => Operands::Relative(signed(next_byte())),
+            OperandKind::Word16 => {
+                let low = next_byte();
+                let high = next_byte();
+                Operands::Word(combine_to_double_byte(high, low))
+            }
+            OperandKind::IndexedImmediate8 => {
+                let offset = signed(next_byte());
+                let value = next_byte();
+                Operands::IndexedImmediate(offset, value)
+            }
+        }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::instruction_set::{Operands, OperandKind, UnknownOpcodeError};
+    use crate::memory::Register;
+    use super::{Runtime, RuntimeError};
+    use std::io::{self, Write};
+    use std::rc::Rc;
+    use std::cell::RefCell;
+
+    fn runtime_with_program(program: &[u8]) -> Runtime {
+        let mut runtime = Runtime::default();
+        runtime.components.mem.locations[..program.len()].copy_from_slice(program);
+        runtime.components.registers.pc.set(0x0000);
+        runtime
+    }
+
+    fn snapshot(runtime: &Runtime) -> (Vec<u8>, Vec<u16>) {
+        let registers = &runtime.components.registers;
+        let values = vec![
+            registers.a.get() as u16, registers.f.get() as u16, registers.b.get() as u16, registers.c.get() as u16,
+            registers.d.get() as u16, registers.e.get() as u16, registers.h.get() as u16, registers.l.get() as u16,
+            registers.sp.get() as u16, registers.pc.get()
+        ];
+        (runtime.components.mem.locations.to_vec(), values)
+    }
+
+    #[test]
+    fn unthrottled_runs_are_deterministic() {
+        // Repeatedly fills #4000-#400F with a countdown: LD HL,#4000; LD B,#10; LD (HL),B; INC HL; DJNZ -4; JR -11
+        let program = [0x21, 0x00, 0x40, 0x06, 0x10, 0x70, 0x23, 0x10, 0xFC, 0x18, 0xF5];
+
+        let mut first = runtime_with_program(&program);
+        first.throttle = false;
+        let first_cycles = first.run_for(0x0000, 10_000).unwrap();
+
+        let mut second = runtime_with_program(&program);
+        second.throttle = false;
+        let second_cycles = second.run_for(0x0000, 10_000).unwrap();
+
+        assert_eq!(first_cycles, second_cycles);
+        assert!(snapshot(&first) == snapshot(&second));
+        assert_eq!(first.components.mem.read_u8(0x4000), 0x10);
+        assert_eq!(first.components.mem.read_u8(0x400F), 0x01);
+    }
+
+    #[test]
+    fn step_executes_one_instruction() {
+        let mut runtime = runtime_with_program(&[0x00, 0x3C]);
+        assert_eq!(runtime.step().unwrap(), 4);
+        assert_eq!(runtime.components.registers.pc.get(), 0x0001);
+        assert_eq!(runtime.components.registers.a.get(), 0x00);
+    }
+
+    #[test]
+    fn poked_bytes_can_be_peeked_back() {
+        let mut runtime = Runtime::default();
+        runtime.poke(0x4000, 0xAB);
+        assert_eq!(runtime.peek(0x4000), 0xAB);
+
+        runtime.poke(0xFFFF, 0x01);
+        runtime.poke(0x0000, 0x02);
+        runtime.poke(0x0001, 0x03);
+        assert_eq!(runtime.peek_range(0xFFFF, 3), vec![0x01, 0x02, 0x03]);
+        // Untouched RAM still holds the #01 it powers up with.
+        assert_eq!(runtime.peek_range(0x4000, 2), vec![0xAB, 0x01]);
+    }
+
+    #[test]
+    fn peek_sees_rom_over_poked_ram() {
+        let mut runtime = Runtime::default();
+        runtime.load_rom_from_bytes(&[0xC9; 0x4000]).unwrap();
+        runtime.poke(0x0000, 0x12);
+        assert_eq!(runtime.peek(0x0000), 0xC9);
+        assert_eq!(runtime.components.mem.locations[0x0000], 0x12);
+    }
+
+    // A Write that keeps what's written somewhere the test can still see after handing it over.
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn trace_writes_a_line_per_instruction() {
+        // LD BC,#1234; LD A,#56; LD SP,#C000; CALL #000B; JR #000B
+        let mut runtime = runtime_with_program(&[0x01, 0x34, 0x12, 0x3E, 0x56, 0x31, 0x00, 0xC0, 0xCD, 0x0B, 0x00, 0x18, 0xFE]);
+        let buffer = Rc::new(RefCell::new(Vec::new()));
+        runtime.set_trace(Box::new(SharedBuffer(buffer.clone())));
+        for _ in 0..5 {
+            runtime.step().unwrap();
+        }
+
+        let trace = String::from_utf8(buffer.borrow().clone()).unwrap();
+        let lines: Vec<&str> = trace.lines().collect();
+        assert_eq!(lines, vec![
+            "0000\t01 34 12\tLD BC,1234  \t(10)\tAF=0000 BC=1234 DE=0000 HL=0000 SP=FFFF",
+            "0003\t3E 56   \tLD A,56     \t(7)\tAF=5600 BC=1234 DE=0000 HL=0000 SP=FFFF",
+            "0005\t31 00 C0\tLD SP,C000  \t(10)\tAF=5600 BC=1234 DE=0000 HL=0000 SP=C000",
+            "0008\tCD 0B 00\tCALL 000B   \t(17)\tAF=5600 BC=1234 DE=0000 HL=0000 SP=BFFE",
+            "000B\t18 FE   \tJR 000B     \t(12)\tAF=5600 BC=1234 DE=0000 HL=0000 SP=BFFE"
+        ]);
+    }
+
+    #[test]
+    fn run_until_stops_at_target() {
+        // NOP; NOP; NOP; JP #0100
+        let mut runtime = runtime_with_program(&[0x00, 0x00, 0x00, 0xC3, 0x00, 0x01]);
+        assert_eq!(runtime.run_until(0x0100, 100), Ok(4));
+        assert_eq!(runtime.components.registers.pc.get(), 0x0100);
+
+        // Already there: nothing is executed.
+        assert_eq!(runtime.run_until(0x0100, 100), Ok(0));
+    }
+
+    #[test]
+    fn run_until_gives_up_at_limit() {
+        // JR -2, looping forever at #0000.
+        let mut runtime = runtime_with_program(&[0x18, 0xFE]);
+        assert_eq!(runtime.run_until(0x4000, 10), Err(RuntimeError::InstructionLimitReached { target: 0x4000, pc: 0x0000, instructions: 10 }));
+    }
+
+    #[test]
+    fn unknown_opcode_is_an_error() {
+        // NOP, then ED 00, which has no implementation.
+        let mut runtime = runtime_with_program(&[0x00, 0xED, 0x00]);
+        assert_eq!(runtime.step(), Ok(4));
+
+        let error = UnknownOpcodeError { prefix: &[0xED], opcode: 0x00 };
+        assert_eq!(runtime.step(), Err(RuntimeError::UnknownOpcode { pc: 0x0001, error }));
+        assert_eq!(runtime.components.registers.pc.get(), 0x0001);
+        assert_eq!(error.to_string(), "Unimplemented instruction: #ED #00");
+
+        assert_eq!(runtime.run_until(0x4000, 10), Err(RuntimeError::UnknownOpcode { pc: 0x0001, error }));
+    }
+
+    #[test]
+    fn out_reaches_gate_array() {
+        // LD BC,#7F8E; OUT (C),C - an RMR write selecting mode 2 with both ROMs disabled.
+        let mut runtime = runtime_with_program(&[0x01, 0x8E, 0x7F, 0xED, 0x49]);
+        runtime.step().unwrap();
+        runtime.step().unwrap();
+
+        let gate_array = runtime.gate_array.borrow();
+        assert_eq!(gate_array.mode(), 2);
+        assert!(!gate_array.lower_rom_enabled());
+        assert!(!gate_array.upper_rom_enabled());
+    }
+
+    #[test]
+    fn crtc_register_select_write_and_read() {
+        // LD BC,#BC0C; OUT (C),C; LD BC,#BD20; OUT (C),C; LD B,#BF; IN A,(C)
+        let program = [0x01, 0x0C, 0xBC, 0xED, 0x49, 0x01, 0x20, 0xBD, 0xED, 0x49, 0x06, 0xBF, 0xED, 0x78];
+        let mut runtime = runtime_with_program(&program);
+        for _ in 0..6 {
+            runtime.step().unwrap();
+        }
+        assert_eq!(runtime.crtc.borrow().screen_start(), 0x2000);
+        assert_eq!(runtime.components.registers.a.get(), 0x20);
+    }
+
+    #[test]
+    fn out_through_ppi_reaches_psg() {
+        // Each pair is LD BC,nn; OUT (C),C: port A to output, latch register 8, go inactive, then
+        // write #0D to it.
+        let program = [
+            0x01, 0x82, 0xF7, 0xED, 0x49, 0x01, 0x08, 0xF4, 0xED, 0x49, 0x01, 0xC0, 0xF6, 0xED, 0x49,
+            0x01, 0x00, 0xF6, 0xED, 0x49, 0x01, 0x0D, 0xF4, 0xED, 0x49, 0x01, 0x80, 0xF6, 0xED, 0x49
+        ];
+        let mut runtime = runtime_with_program(&program);
+        for _ in 0..12 {
+            runtime.step().unwrap();
+        }
+        assert_eq!(runtime.ppi.borrow().psg.amplitude(0), 0x0D);
+    }
+
+    #[test]
+    fn interrupt_vectors_to_0038_in_im1() {
+        // IM 1; EI; loop: JR loop
+        let mut runtime = runtime_with_program(&[0xED, 0x56, 0xFB, 0x18, 0xFE]);
+        runtime.components.registers.sp.set(0xC000);
+        let mut cycles = 0;
+        while runtime.components.registers.pc.get() != 0x0038 {
+            cycles += runtime.step().unwrap();
+            assert!(cycles < 20_000, "no interrupt after {} cycles", cycles);
+        }
+        // 52 scanlines of 256 cycles.
+        assert!(cycles >= 13_312);
+        assert_eq!(runtime.components.mem.read_u16(0xBFFE), 0x0003);
+        assert_eq!(runtime.components.registers.sp.get(), 0xBFFE);
+        assert!(!runtime.components.registers.iff1);
+        assert!(!runtime.gate_array.borrow().interrupt_pending());
+    }
+
+    #[test]
+    fn interrupt_waits_for_instruction_after_ei() {
+        // EI; NOP; NOP
+        let mut runtime = runtime_with_program(&[0xFB, 0x00, 0x00]);
+        runtime.components.registers.interrupt_mode = 1;
+        runtime.components.registers.sp.set(0xC000);
+        runtime.gate_array.borrow_mut().add_cycles(52 * 256);
+        assert!(runtime.gate_array.borrow().interrupt_pending());
+
+        runtime.step().unwrap();
+        assert_eq!(runtime.components.registers.pc.get(), 0x0001);
+        // The NOP after EI still runs...
+        runtime.step().unwrap();
+        assert_eq!(runtime.components.registers.pc.get(), 0x0002);
+        // ...and the interrupt is taken once it has.
+        assert_eq!(runtime.step().unwrap(), 13);
+        assert_eq!(runtime.components.registers.pc.get(), 0x0038);
+        assert_eq!(runtime.components.mem.read_u16(0xBFFE), 0x0002);
+    }
+
+    #[test]
+    fn interrupts_wait_for_ei() {
+        // loop: JR loop
+        let mut runtime = runtime_with_program(&[0x18, 0xFE]);
+        runtime.run_frame().unwrap();
+        assert_eq!(runtime.components.registers.pc.get(), 0x0000);
+        assert!(runtime.gate_array.borrow().interrupt_pending());
+    }
+
+    #[test]
+    fn interrupt_wakes_halt() {
+        // IM 1; EI; HALT
+        let mut runtime = runtime_with_program(&[0xED, 0x56, 0xFB, 0x76]);
+        runtime.components.registers.sp.set(0xC000);
+        runtime.run_frame().unwrap();
+        assert!(!runtime.components.registers.halted);
+        // Returning from the handler continues after the HALT.
+        assert_eq!(runtime.components.mem.read_u16(0xBFFE), 0x0004);
+    }
+
+    #[test]
+    fn ram_config_pages_expansion_into_c000() {
+        // LD BC,#7FC1; OUT (C),C; LD A,#AA; LD (#C000),A
+        let mut runtime = runtime_with_program(&[0x01, 0xC1, 0x7F, 0xED, 0x49, 0x3E, 0xAA, 0x32, 0x00, 0xC0]);
+        runtime.components.mem.locations[0xC000] = 0x00;
+        for _ in 0..4 {
+            runtime.step().unwrap();
+        }
+        assert_eq!(runtime.components.mem.ram_config(), 1);
+        assert_eq!(runtime.components.mem.read_u8(0xC000), 0xAA);
+        // Block 7 is the last 16K of the expansion RAM.
+        assert_eq!(runtime.components.mem.expansion[0xC000], 0xAA);
+        assert_eq!(runtime.components.mem.locations[0xC000], 0x00);
+    }
+
+    #[test]
+    fn interrupt_mode_2_vectors_through_i() {
+        let mut runtime = Runtime::default();
+        runtime.components.registers.i.set(0x10);
+        runtime.components.mem.locations[0x10FE] = 0x34;
+        runtime.components.mem.locations[0x10FF] = 0x12;
+        assert_eq!(Runtime::interrupt_mode_2_handler(&runtime.components, 0xFE), 0x1234);
+    }
+
+    #[test]
+    fn jr_receives_relative_operand() {
+        let mut runtime = runtime_with_program(&[0x18, 0xFE]);
+        let kind = runtime.instruction_set.instruction_for(0x18).unwrap().operand_kind();
+        let operands = Runtime::fetch_operands(&mut runtime.components, kind);
+        assert_eq!(operands, Operands::Relative(-2));
+        assert_eq!(runtime.components.registers.pc.get(), 0x0001);
+    }
+
+    #[test]
+    fn jr_jumps_backwards() {
+        let mut runtime = runtime_with_program(&[0x00, 0x18, 0xFD]);
+        runtime.components.registers.pc.set(0x0001);
+        let cycles = runtime.step().unwrap();
+        assert_eq!(cycles, 12);
+        assert_eq!(runtime.components.registers.pc.get(), 0x0000);
+    }
+
+    #[test]
+    fn ld_hl_nn_receives_word_operand() {
+        let mut runtime = runtime_with_program(&[0x21, 0x34, 0x12]);
+        let kind = runtime.instruction_set.instruction_for(0x21).unwrap().operand_kind();
+        let operands = Runtime::fetch_operands(&mut runtime.components, kind);
+        assert_eq!(operands, Operands::Word(0x1234));
+        assert_eq!(runtime.components.registers.pc.get(), 0x0002);
+
+        runtime.components.registers.pc.set(0x0000);
+        runtime.step().unwrap();
+        assert_eq!(runtime.components.registers.h.get(), 0x12);
+        assert_eq!(runtime.components.registers.l.get(), 0x34);
+    }
+
+    #[test]
+    fn indexed_store_receives_displacement_and_value() {
+        // LD (IX-2),#99
+        let mut runtime = runtime_with_program(&[0xDD, 0x36, 0xFE, 0x99, 0x00]);
+        runtime.components.registers.ix = 0x4002;
+        let cycles = runtime.step().unwrap();
+        assert_eq!(cycles, 19);
+        assert_eq!(runtime.components.mem.read_u8(0x4000), 0x99);
+        assert_eq!(runtime.components.registers.pc.get(), 0x0004);
+    }
+
+    #[test]
+    fn indexed_bit_instruction_reads_displacement_before_opcode() {
+        // SET 7,(IX+3) then RES 0,(IY-1)
+        let mut runtime = runtime_with_program(&[0xDD, 0xCB, 0x03, 0xFE, 0xFD, 0xCB, 0xFF, 0x86]);
+        runtime.components.registers.ix = 0x4000;
+        runtime.components.registers.iy = 0x4004;
+        runtime.components.mem.locations[0x4003] = 0x01;
+
+        assert_eq!(runtime.step().unwrap(), 23);
+        assert_eq!(runtime.components.mem.read_u8(0x4003), 0x81);
+        assert_eq!(runtime.components.registers.pc.get(), 0x0004);
+
+        assert_eq!(runtime.step().unwrap(), 23);
+        assert_eq!(runtime.components.mem.read_u8(0x4003), 0x80);
+        assert_eq!(runtime.components.registers.pc.get(), 0x0008);
+    }
+
+    #[test]
+    fn push_iy_pop_iy_round_trips_through_the_stack() {
+        // PUSH IY; POP IY
+        let mut runtime = runtime_with_program(&[0xFD, 0xE5, 0xFD, 0xE1]);
+        runtime.components.registers.sp.set(0x8000);
+        runtime.components.registers.iy = 0xBEEF;
+
+        assert_eq!(runtime.step().unwrap(), 15);
+        assert_eq!(runtime.components.registers.sp.get(), 0x7FFE);
+        runtime.components.registers.iy = 0x0000;
+
+        assert_eq!(runtime.step().unwrap(), 14);
+        assert_eq!(runtime.components.registers.iy, 0xBEEF);
+        assert_eq!(runtime.components.registers.sp.get(), 0x8000);
+        assert_eq!(runtime.components.registers.pc.get(), 0x0004);
+    }
+
+    #[test]
+    fn halt_stops_pc_advancing() {
+        // HALT; INC A
+        let mut runtime = runtime_with_program(&[0x76, 0x3C]);
+
+        assert_eq!(runtime.step().unwrap(), 4);
+        assert!(runtime.components.registers.halted);
+        assert_eq!(runtime.components.registers.pc.get(), 0x0001);
+
+        for _ in 0..3 {
+            assert_eq!(runtime.step().unwrap(), 4);
+            assert_eq!(runtime.components.registers.pc.get(), 0x0001);
+        }
+        assert_eq!(runtime.components.registers.a.get(), 0x00);
+    }
+
+    #[test]
+    fn rmr_disables_lower_rom() {
+        // LD BC,#7F84 (RMR, lower ROM off); OUT (C),C - in the ROM itself, so the next fetch
+        // comes from RAM.
+        let mut rom = vec![0x00; 0x4000];
+        rom[..5].copy_from_slice(&[0x01, 0x84, 0x7F, 0xED, 0x49]);
+        let mut runtime = Runtime::default();
+        runtime.load_rom_from_bytes(&rom).unwrap();
+        // INC A in the RAM underneath.
+        runtime.components.mem.locations[0x0005] = 0x3C;
+        runtime.components.registers.pc.set(0x0000);
+        for _ in 0..3 {
+            runtime.step().unwrap();
+        }
+        assert_eq!(runtime.components.registers.a.get(), 0x01);
+    }
+
+    #[test]
+    fn out_selects_upper_rom() {
+        // LD BC,#DF07; OUT (C),C; LD A,(#C000); LD C,#00; OUT (C),C; LD A,(#C000)
+        let program = [0x01, 0x07, 0xDF, 0xED, 0x49, 0x3A, 0x00, 0xC0, 0x0E, 0x00, 0xED, 0x49, 0x3A, 0x00, 0xC0];
+        let mut runtime = runtime_with_program(&program);
+        runtime.load_rom(0, &[0xBA; 0x4000]);
+        runtime.load_rom(7, &[0xD0; 0x4000]);
+
+        for _ in 0..3 {
+            runtime.step().unwrap();
+        }
+        assert_eq!(runtime.components.registers.a.get(), 0xD0);
+        for _ in 0..3 {
+            runtime.step().unwrap();
+        }
+        assert_eq!(runtime.components.registers.a.get(), 0xBA);
+    }
+
+    #[test]
+    fn port_b_vsync_rises_once_a_frame() {
+        // LD B,#F5; IN A,(C); JR -4, polling port B.
+        let mut runtime = runtime_with_program(&[0x06, 0xF5, 0xED, 0x78, 0x18, 0xFC]);
+        runtime.step().unwrap();
+        // The firmware's CRTC settings give 312 lines of 256 cycles.
+        let frame = 312 * 256;
+        let mut cycles = 0u32;
+        let mut vsync = false;
+        let mut rising_edges = Vec::new();
+        while cycles < 2 * frame {
+            cycles += runtime.step().unwrap() as u32;
+            if runtime.components.registers.pc.get() == 0x0004 {
+                let now = runtime.components.registers.a.get() & 0x01 != 0;
+                if now && !vsync {
+                    rising_edges.push(cycles);
+                }
+                vsync = now;
+            }
+        }
+        assert_eq!(rising_edges.len(), 2);
+        // A frame apart, give or take the 24-cycle loop.
+        let gap = rising_edges[1] - rising_edges[0];
+        assert!((frame - 24..=frame + 24).contains(&gap), "{} cycles apart", gap);
+    }
+
+    #[test]
+    fn odd_sized_rom_is_an_error() {
+        let mut runtime = Runtime::default();
+        assert_eq!(runtime.load_rom_from_bytes(&[0; 0x1000]), Err("Unexpected ROM size: 4096".to_string()));
+    }
+
+    #[test]
+    fn expansion_rom_fills_to_top_of_memory() {
+        let mut runtime = Runtime::default();
+        let mut rom = vec![0x00; 0x8000];
+        rom[0x7FFF] = 0xC9;
+        runtime.load_rom_from_bytes(&rom).unwrap();
+        assert_eq!(runtime.components.mem.read_u8(0xFFFF), 0xC9);
+    }
+
+    #[test]
+    fn screenshot_writes_png_of_screen() {
+        let mut runtime = Runtime::default();
+        {
+            let mut gate_array = runtime.gate_array.borrow_mut();
+            gate_array.write(0x81); // mode 1
+            gate_array.write(0x10); // border
+            gate_array.write(0x4C); // bright red
+        }
+        let path = std::env::temp_dir().join(format!("cpc_emu_screenshot_{}.png", std::process::id()));
+        runtime.screenshot(&path).unwrap();
+
+        let decoder = png::Decoder::new(std::io::BufReader::new(std::fs::File::open(&path).unwrap()));
+        let mut reader = decoder.read_info().unwrap();
+        let mut image = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut image).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!((info.width, info.height), (320 + 64, 200 + 64));
+        assert_eq!(info.color_type, png::ColorType::Rgba);
+        assert_eq!(image[..4], [0xFF, 0x00, 0x00, 0xFF]);
+    }
+
+    #[test]
+    fn run_frame_continues_from_pc() {
+        // LD A,#00; loop: INC A; JR loop
+        let mut runtime = runtime_with_program(&[0x3E, 0x00, 0x3C, 0x18, 0xFD]);
+        let cycles = runtime.run_frame().unwrap();
+        assert!((80_000..80_016).contains(&cycles));
+
+        let a = runtime.components.registers.a.get();
+        runtime.run_frame().unwrap();
+        // A counts on rather than starting again from LD A,#00.
+        assert_eq!(runtime.components.registers.a.get(), a.wrapping_add(136));
+    }
+
+    #[test]
+    fn run_frame_buffers_audio() {
+        // JR -2
+        let mut runtime = runtime_with_program(&[0x18, 0xFE]);
+        runtime.run_frame().unwrap();
+        assert!(runtime.audio.is_empty());
+
+        runtime.audio_sample_rate = Some(44_100);
+        runtime.run_frame().unwrap();
+        assert_eq!(runtime.audio.len(), 882);
+        for _ in 0..20 {
+            runtime.run_frame().unwrap();
+        }
+        assert_eq!(runtime.audio.len(), 8820);
+    }
+
+    #[test]
+    fn load_snapshot_restores_registers_memory_and_devices() {
+        let mut runtime = Runtime::default();
+        runtime.load_snapshot(&crate::sna::tests::crafted_snapshot()).unwrap();
+
+        let registers = &runtime.components.registers;
+        assert_eq!((registers.a.get(), registers.f.get()), (0x02, 0x01));
+        assert_eq!((registers.h.get(), registers.l.get()), (0x08, 0x07));
+        assert_eq!((registers.b_.get(), registers.c_.get()), (0x14, 0x13));
+        assert_eq!((registers.i.get(), registers.r.get()), (0x0A, 0x09));
+        assert_eq!((registers.ix, registers.iy), (0x1234, 0x5678));
+        assert_eq!(registers.sp.get(), 0xBFF0);
+        assert_eq!(registers.pc.get(), 0x4000);
+        assert!(registers.iff1 && registers.iff2);
+        assert_eq!(registers.interrupt_mode, 1);
+
+        assert_eq!(runtime.components.mem.read_u8(0x1234), 0x5A);
+        assert_eq!(runtime.components.mem.ram_config(), 1);
+        assert_eq!(runtime.components.mem.read_u8(0xC000), 0xA5);
+
+        let gate_array = runtime.gate_array.borrow();
+        assert_eq!(gate_array.ink(1), 26);
+        assert_eq!(gate_array.mode(), 1);
+        assert!(gate_array.lower_rom_enabled());
+        assert!(!gate_array.upper_rom_enabled());
+        assert_eq!(runtime.crtc.borrow().screen_start(), 0x1000);
+        assert_eq!(runtime.rom_select.borrow().selected, 7);
+        assert_eq!(runtime.ppi.borrow().psg.amplitude(0), 0x0F);
+    }
+
+    #[test]
+    fn saved_snapshot_loads_back_identically() {
+        let crafted = crate::sna::tests::crafted_snapshot();
+        let mut runtime = Runtime::default();
+        runtime.load_snapshot(&crafted).unwrap();
+        let saved = runtime.save_snapshot();
+        assert_eq!(saved.len(), 0x100 + 0x20000);
+        // The header comes back byte for byte, apart from port B, which reads the PPI's status
+        // inputs, and the RMR's interrupt reset bit, which isn't state.
+        let mut header = crafted[..0x100].to_vec();
+        header[0x40] = 0x09;
+        header[0x57] = 0x7E;
+        assert_eq!(saved[..0x100], header[..]);
+
+        let mut restored = Runtime::default();
+        restored.load_snapshot(&saved).unwrap();
+        assert_eq!(restored.save_snapshot(), saved);
+        assert_eq!(restored.components.registers.pc.get(), 0x4000);
+        assert_eq!(restored.components.mem.read_u8(0x1234), 0x5A);
+    }
+}
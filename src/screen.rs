@@ -1,13 +1,341 @@
-#[derive(Debug)]
-enum Mode {
-    ZERO,
-    ONE,
-    TWO
+use crate::memory::Memory;
+use crate::crtc::Crtc;
+use crate::gate_array::{GateArray, BORDER};
+
+///////////////////////
+//
+// Screen - decodes video memory into pen numbers
+//
+///////////////////////
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum Mode {
+    ZERO, // 160x200, 16 colours, 2 pixels per byte
+    ONE,  // 320x200, 4 colours, 4 pixels per byte
+    TWO   // 640x200, 2 colours, 8 pixels per byte
+}
+
+impl Mode {
+    // Maps the Gate Array's mode bits. The undocumented mode 3 isn't supported and is shown as mode 0.
+    pub fn from_gate_array(mode: u8) -> Mode {
+        match mode {
+            1 => Mode::ONE,
+            2 => Mode::TWO,
+            _ => Mode::ZERO
+        }
+    }
+
+    pub fn pixels_per_byte(&self) -> usize {
+        match self {
+            Mode::ZERO => 2,
+            Mode::ONE => 4,
+            Mode::TWO => 8
+        }
+    }
+
+    // Pixel n (0 is leftmost) of a video memory byte. Pixel bits are interleaved rather than
+    // packed, e.g. in mode 1 pixel 0 is made from bits 7 (pen bit 0) and 3 (pen bit 1).
+    pub fn pen(&self, byte: u8, n: usize) -> u8 {
+        let bit = |index: usize| (byte >> index) & 1;
+        match self {
+            Mode::ZERO => bit(7 - n) | bit(3 - n) << 1 | bit(5 - n) << 2 | bit(1 - n) << 3,
+            Mode::ONE => bit(7 - n) | bit(3 - n) << 1,
+            Mode::TWO => bit(7 - n)
+        }
+    }
+}
+
+///////////////////////
+//
+// Palette - hardware colours to RGB
+//
+///////////////////////
+
+// The RGB for each 5-bit hardware colour code, as written to the Gate Array with a colour select
+// command (#40-#5F). Each channel is off, half or full, giving 27 colours; the remaining five
+// codes duplicate others.
+pub const HARDWARE_COLOURS: [(u8, u8, u8); 32] = [
+    (0x80, 0x80, 0x80), // #40 White
+    (0x80, 0x80, 0x80), // #41 White (duplicate)
+    (0x00, 0xFF, 0x80), // #42 Sea Green
+    (0xFF, 0xFF, 0x80), // #43 Pastel Yellow
+    (0x00, 0x00, 0x80), // #44 Blue
+    (0xFF, 0x00, 0x80), // #45 Purple
+    (0x00, 0x80, 0x80), // #46 Cyan
+    (0xFF, 0x80, 0x80), // #47 Pink
+    (0xFF, 0x00, 0x80), // #48 Purple (duplicate)
+    (0xFF, 0xFF, 0x80), // #49 Pastel Yellow (duplicate)
+    (0xFF, 0xFF, 0x00), // #4A Bright Yellow
+    (0xFF, 0xFF, 0xFF), // #4B Bright White
+    (0xFF, 0x00, 0x00), // #4C Bright Red
+    (0xFF, 0x00, 0xFF), // #4D Bright Magenta
+    (0xFF, 0x80, 0x00), // #4E Orange
+    (0xFF, 0x80, 0xFF), // #4F Pastel Magenta
+    (0x00, 0x00, 0x80), // #50 Blue (duplicate)
+    (0x00, 0xFF, 0x80), // #51 Sea Green (duplicate)
+    (0x00, 0xFF, 0x00), // #52 Bright Green
+    (0x00, 0xFF, 0xFF), // #53 Bright Cyan
+    (0x00, 0x00, 0x00), // #54 Black
+    (0x00, 0x00, 0xFF), // #55 Bright Blue
+    (0x00, 0x80, 0x00), // #56 Green
+    (0x00, 0x80, 0xFF), // #57 Sky Blue
+    (0x80, 0x00, 0x80), // #58 Magenta
+    (0x80, 0xFF, 0x80), // #59 Pastel Green
+    (0x80, 0xFF, 0x00), // #5A Lime
+    (0x80, 0xFF, 0xFF), // #5B Pastel Cyan
+    (0x80, 0x00, 0x00), // #5C Red
+    (0x80, 0x00, 0xFF), // #5D Mauve
+    (0x80, 0x80, 0x00), // #5E Yellow
+    (0x80, 0x80, 0xFF)  // #5F Pastel Blue
+];
+
+// Only the low 5 bits of a colour select command pick the colour.
+pub fn hardware_colour(code: u8) -> (u8, u8, u8) {
+    HARDWARE_COLOURS[(code & 0x1F) as usize]
 }
 
+pub struct Palette {
+    // RGB for each hardware colour code.
+    colours: [(u8, u8, u8); 32]
+}
+
+impl Palette {
+    pub fn default() -> Palette {
+        Palette { colours: HARDWARE_COLOURS }
+    }
+
+    pub fn rgb(&self, hardware_colour: u8) -> (u8, u8, u8) {
+        self.colours[(hardware_colour & 0x1F) as usize]
+    }
+}
+
+// How much border surrounds the display area: in characters across and scanlines down.
+const BORDER_CHARACTERS: usize = 4;
+const BORDER_LINES: usize = 32;
+
+// A decoded frame: one pen number per pixel, row by row from the top left.
 #[derive(Debug)]
-struct Screen {
-    mode: Mode,
-    screen_mem: [u8]
+pub struct Screen {
+    pub mode: Mode,
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>
+}
+
+impl Screen {
+    // Decodes the area the CRTC displays: R1 characters (2 bytes each) across and R6 character
+    // rows down, each R9 + 1 scanlines tall.
+    //
+    // Video memory isn't laid out line by line. The CRTC counts characters in a 10-bit memory
+    // address (MA) and scanlines within a row in a raster address (RA), and they're wired to
+    // the address bus like so:
+    //   bits 15-14: MA bits 13-12 (which 16K page holds the screen)
+    //   bits 13-11: RA bits 2-0 (so each scanline of a row is #800 bytes after the previous)
+    //   bits 10-1:  MA bits 9-0
+    //   bit 0:      which byte of the character
+    pub fn decode(mem: &Memory, crtc: &Crtc, mode: Mode) -> Screen {
+        let columns = crtc.horizontal_displayed() as usize;
+        let rows = crtc.vertical_displayed() as usize;
+        let lines_per_row = crtc.max_raster_address() as usize + 1;
+        let start = crtc.screen_start() as usize;
+
+        let width = columns * 2 * mode.pixels_per_byte();
+        let height = rows * lines_per_row;
+        let mut pixels = Vec::with_capacity(width * height);
+
+        for row in 0..rows {
+            for raster in 0..lines_per_row {
+                for column in 0..columns {
+                    let ma = start + row * columns + column;
+                    for byte in 0..2 {
+                        let address = ((ma & 0x3000) << 2) | ((raster & 0x07) << 11) | ((ma & 0x03FF) << 1) | byte;
+                        let value = mem.read_video(address as u16);
+                        for n in 0..mode.pixels_per_byte() {
+                            pixels.push(mode.pen(value, n));
+                        }
+                    }
+                }
+            }
+        }
+        Screen { mode, width, height, pixels }
+    }
+
+    pub fn pen_at(&self, x: usize, y: usize) -> u8 {
+        self.pixels[y * self.width + x]
+    }
+
+    // The size of the image to_rgba produces, i.e. the display area plus the border.
+    pub fn rgba_size(&self) -> (usize, usize) {
+        (self.width + 2 * self.border_width(), self.height + 2 * BORDER_LINES)
+    }
+
+    // Colours the frame using the Gate Array's current inks, surrounded by the border.
+    // Pixels are 4 bytes (red, green, blue, alpha), row by row from the top left.
+    pub fn to_rgba(&self, gate_array: &GateArray) -> Vec<u8> {
+        let palette = Palette::default();
+        let rgba = |pen: usize| {
+            let (r, g, b) = palette.rgb(gate_array.ink(pen));
+            [r, g, b, 0xFF]
+        };
+        let inks: Vec<[u8; 4]> = (0..=BORDER).map(rgba).collect();
+
+        let (width, height) = self.rgba_size();
+        let border_width = self.border_width();
+        let mut image = Vec::with_capacity(width * height * 4);
+        for y in 0..height {
+            for x in 0..width {
+                let inside = (border_width..border_width + self.width).contains(&x)
+                    && (BORDER_LINES..BORDER_LINES + self.height).contains(&y);
+                let pen = if inside { self.pen_at(x - border_width, y - BORDER_LINES) as usize } else { BORDER };
+                image.extend_from_slice(&inks[pen]);
+            }
+        }
+        image
+    }
+
+    fn border_width(&self) -> usize {
+        BORDER_CHARACTERS * 2 * self.mode.pixels_per_byte()
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::memory::Memory;
+    use crate::crtc::Crtc;
+    use crate::gate_array::GateArray;
+    use super::{Screen, Mode, Palette, HARDWARE_COLOURS, hardware_colour};
+
+    fn blank_memory() -> Memory {
+        let mut mem = Memory::default();
+        mem.locations.fill(0);
+        mem
+    }
+
+    fn row_of(screen: &Screen, y: usize, count: usize) -> Vec<u8> {
+        (0..count).map(|x| screen.pen_at(x, y)).collect()
+    }
+
+    #[test]
+    fn mode_2_bits_are_pixels() {
+        let mut mem = blank_memory();
+        mem.locations[0xC000] = 0xF0;
+        mem.locations[0xC001] = 0x81;
+        let screen = Screen::decode(&mem, &Crtc::default(), Mode::TWO);
+
+        assert_eq!((screen.width, screen.height), (640, 200));
+        assert_eq!(row_of(&screen, 0, 16), vec![1, 1, 1, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn mode_1_pens_interleave_bits() {
+        let mut mem = blank_memory();
+        // Pixel 0 from bits 7/3, pixel 1 from 6/2, pixel 2 from 5/1, pixel 3 from 4/0.
+        mem.locations[0xC000] = 0b1000_0100 | 0b0010_0010;
+        let screen = Screen::decode(&mem, &Crtc::default(), Mode::ONE);
+
+        assert_eq!((screen.width, screen.height), (320, 200));
+        assert_eq!(row_of(&screen, 0, 5), vec![1, 2, 3, 0, 0]);
+    }
+
+    #[test]
+    fn mode_0_pens_interleave_bits() {
+        let mut mem = blank_memory();
+        mem.locations[0xC000] = 0xAA; // every bit of pixel 0
+        mem.locations[0xC001] = 0x14; // bits 4 and 2 of pixel 1: pen bits 2 and 1
+        let screen = Screen::decode(&mem, &Crtc::default(), Mode::ZERO);
+
+        assert_eq!((screen.width, screen.height), (160, 200));
+        assert_eq!(row_of(&screen, 0, 4), vec![15, 0, 0, 6]);
+    }
+
+    #[test]
+    fn scanlines_interleave_in_800_byte_blocks() {
+        let mut mem = blank_memory();
+        mem.locations[0xC800] = 0x80; // second scanline of the first character row
+        mem.locations[0xC000 + 80] = 0x80; // first scanline of the second row
+        let screen = Screen::decode(&mem, &Crtc::default(), Mode::TWO);
+
+        assert_eq!(screen.pen_at(0, 0), 0);
+        assert_eq!(screen.pen_at(0, 1), 1);
+        assert_eq!(screen.pen_at(0, 8), 1);
+        assert_eq!(screen.pixels.iter().filter(|pen| **pen != 0).count(), 2);
+    }
+
+    #[test]
+    fn screen_start_moves_the_display() {
+        let mut mem = blank_memory();
+        mem.locations[0x4000 + 0x10 * 2] = 0x80;
+        let mut crtc = Crtc::default();
+        // Page 1 (#4000), offset #10 characters.
+        crtc.select(12);
+        crtc.write_selected(0x10);
+        crtc.select(13);
+        crtc.write_selected(0x10);
+        let screen = Screen::decode(&mem, &crtc, Mode::TWO);
+        assert_eq!(screen.pen_at(0, 0), 1);
+    }
+
+    #[test]
+    fn palette_levels() {
+        let palette = Palette::default();
+        assert_eq!(palette.rgb(0x14), (0x00, 0x00, 0x00)); // black
+        assert_eq!(palette.rgb(0x04), (0x00, 0x00, 0x80)); // blue
+        assert_eq!(palette.rgb(0x0C), (0xFF, 0x00, 0x00)); // bright red
+        assert_eq!(palette.rgb(0x00), (0x80, 0x80, 0x80)); // white
+        assert_eq!(palette.rgb(0x0B), (0xFF, 0xFF, 0xFF)); // bright white
+    }
+
+    #[test]
+    fn hardware_colour_table() {
+        assert_eq!(hardware_colour(0x4B), (0xFF, 0xFF, 0xFF)); // bright white
+        assert_eq!(hardware_colour(0x4C), (0xFF, 0x00, 0x00)); // bright red
+        assert_eq!(hardware_colour(0x54), (0x00, 0x00, 0x00)); // black
+        assert_eq!(hardware_colour(0x15), (0x00, 0x00, 0xFF)); // bright blue, without the command bits
+
+        // The duplicated codes: #40/#41 are both white, #44/#50 both blue.
+        assert_eq!(hardware_colour(0x40), hardware_colour(0x41));
+        assert_eq!(hardware_colour(0x44), hardware_colour(0x50));
+
+        let mut distinct = HARDWARE_COLOURS.to_vec();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), 27);
+    }
+
+    #[test]
+    fn single_colour_screen_to_rgba() {
+        let screen = Screen::decode(&blank_memory(), &Crtc::default(), Mode::ONE);
+        let mut gate_array = GateArray::default();
+        gate_array.write(0x00); // pen 0
+        gate_array.write(0x4C); // bright red
+        gate_array.write(0x10); // border
+        gate_array.write(0x4C);
+
+        let image = screen.to_rgba(&gate_array);
+        let (width, height) = screen.rgba_size();
+        assert_eq!((width, height), (320 + 64, 200 + 64));
+        assert_eq!(image.len(), width * height * 4);
+        assert!(image.chunks(4).all(|pixel| pixel == [0xFF, 0x00, 0x00, 0xFF]));
+    }
+
+    #[test]
+    fn border_surrounds_display() {
+        let mut mem = blank_memory();
+        mem.locations[0xC000] = 0x80;
+        let screen = Screen::decode(&mem, &Crtc::default(), Mode::TWO);
+        let mut gate_array = GateArray::default();
+        gate_array.write(0x01); // pen 1
+        gate_array.write(0x4B); // bright white
+        gate_array.write(0x10); // border
+        gate_array.write(0x44); // blue
+
+        let image = screen.to_rgba(&gate_array);
+        let (width, _) = screen.rgba_size();
+        let pixel = |x: usize, y: usize| &image[(y * width + x) * 4..(y * width + x) * 4 + 4];
+        assert_eq!(pixel(0, 0), [0x00, 0x00, 0x80, 0xFF]);
+        assert_eq!(pixel(63, 32), [0x00, 0x00, 0x80, 0xFF]);
+        assert_eq!(pixel(64, 32), [0xFF, 0xFF, 0xFF, 0xFF]);
+        assert_eq!(pixel(65, 32), [0x80, 0x80, 0x80, 0xFF]); // pen 0 is still hardware colour 0, white
+    }
+}
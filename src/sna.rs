@@ -0,0 +1,201 @@
+///////////////////////
+//
+// SNA - snapshots of the whole machine's state
+//
+///////////////////////
+
+// An SNA file is a 256-byte header followed by a dump of RAM. All words are little endian.
+//   00-07  "MV - SNA"
+//   10     version
+//   11-2D  Z80 registers: F A C B E D L H R I IFF1 IFF2 IX IY SP PC IM F' A' C' B' E' D' L' H'
+//   2E     Gate Array selected pen
+//   2F-3F  Gate Array hardware colours for pens 0-15 and the border
+//   40     Gate Array RMR: screen mode and ROM enables
+//   41     RAM configuration
+//   42     CRTC selected register
+//   43-54  CRTC registers 0-17
+//   55     upper ROM selected
+//   56-59  PPI ports A, B and C and the control register
+//   5A     PSG selected register
+//   5B-6A  PSG registers 0-15
+//   6B-6C  size of the memory dump in K: 64, or 128 for a 6128
+// Versions 2 and 3 add more after this, which isn't needed to resume. Version 3 files can also
+// leave the dump size 0 and store memory in compressed chunks, which isn't supported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub version: u8,
+    pub af: u16,
+    pub bc: u16,
+    pub de: u16,
+    pub hl: u16,
+    pub r: u8,
+    pub i: u8,
+    pub iff1: bool,
+    pub iff2: bool,
+    pub ix: u16,
+    pub iy: u16,
+    pub sp: u16,
+    pub pc: u16,
+    pub interrupt_mode: u8,
+    pub af_: u16,
+    pub bc_: u16,
+    pub de_: u16,
+    pub hl_: u16,
+    pub selected_pen: u8,
+    pub inks: [u8; 17],
+    pub rmr: u8,
+    pub ram_config: u8,
+    pub crtc_selected: u8,
+    pub crtc_registers: [u8; 18],
+    pub rom_selected: u8,
+    pub ppi_a: u8,
+    pub ppi_b: u8,
+    pub ppi_c: u8,
+    pub ppi_control: u8,
+    pub psg_selected: u8,
+    pub psg_registers: [u8; 16],
+    // Base RAM, then the second 64K if the snapshot has it.
+    pub memory: Vec<u8>
+}
+
+const SIGNATURE: &[u8] = b"MV - SNA";
+pub const HEADER_SIZE: usize = 0x100;
+
+impl Snapshot {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Snapshot, &'static str> {
+        if bytes.len() < HEADER_SIZE || &bytes[..SIGNATURE.len()] != SIGNATURE {
+            return Err("Invalid SNA format");
+        }
+        let word = |offset: usize| u16::from_le_bytes([bytes[offset], bytes[offset + 1]]);
+        let memory_size = match word(0x6B) {
+            0 => return Err("Compressed SNA memory isn't supported"),
+            kilobytes => kilobytes as usize * 1024
+        };
+        if bytes.len() < HEADER_SIZE + memory_size {
+            return Err("SNA memory dump is truncated");
+        }
+        Ok(Snapshot {
+            version: bytes[0x10],
+            af: word(0x11),
+            bc: word(0x13),
+            de: word(0x15),
+            hl: word(0x17),
+            r: bytes[0x19],
+            i: bytes[0x1A],
+            iff1: bytes[0x1B] & 0x01 != 0,
+            iff2: bytes[0x1C] & 0x01 != 0,
+            ix: word(0x1D),
+            iy: word(0x1F),
+            sp: word(0x21),
+            pc: word(0x23),
+            interrupt_mode: bytes[0x25],
+            af_: word(0x26),
+            bc_: word(0x28),
+            de_: word(0x2A),
+            hl_: word(0x2C),
+            selected_pen: bytes[0x2E],
+            inks: bytes[0x2F..0x40].try_into().unwrap(),
+            rmr: bytes[0x40],
+            ram_config: bytes[0x41],
+            crtc_selected: bytes[0x42],
+            crtc_registers: bytes[0x43..0x55].try_into().unwrap(),
+            rom_selected: bytes[0x55],
+            ppi_a: bytes[0x56],
+            ppi_b: bytes[0x57],
+            ppi_c: bytes[0x58],
+            ppi_control: bytes[0x59],
+            psg_selected: bytes[0x5A],
+            psg_registers: bytes[0x5B..0x6B].try_into().unwrap(),
+            memory: bytes[HEADER_SIZE..HEADER_SIZE + memory_size].to_vec()
+        })
+    }
+
+    // Writes the header for version 1, which every SNA reader understands, then the memory.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0; HEADER_SIZE];
+        bytes[..SIGNATURE.len()].copy_from_slice(SIGNATURE);
+        bytes[0x10] = 1;
+        let words = [
+            (0x11, self.af), (0x13, self.bc), (0x15, self.de), (0x17, self.hl), (0x1D, self.ix), (0x1F, self.iy),
+            (0x21, self.sp), (0x23, self.pc), (0x26, self.af_), (0x28, self.bc_), (0x2A, self.de_), (0x2C, self.hl_),
+            (0x6B, (self.memory.len() / 1024) as u16)
+        ];
+        for (offset, value) in words {
+            bytes[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+        }
+        bytes[0x19] = self.r;
+        bytes[0x1A] = self.i;
+        bytes[0x1B] = self.iff1 as u8;
+        bytes[0x1C] = self.iff2 as u8;
+        bytes[0x25] = self.interrupt_mode;
+        bytes[0x2E] = self.selected_pen;
+        bytes[0x2F..0x40].copy_from_slice(&self.inks);
+        bytes[0x40] = self.rmr;
+        bytes[0x41] = self.ram_config;
+        bytes[0x42] = self.crtc_selected;
+        bytes[0x43..0x55].copy_from_slice(&self.crtc_registers);
+        bytes[0x55] = self.rom_selected;
+        bytes[0x56..0x5A].copy_from_slice(&[self.ppi_a, self.ppi_b, self.ppi_c, self.ppi_control]);
+        bytes[0x5A] = self.psg_selected;
+        bytes[0x5B..0x6B].copy_from_slice(&self.psg_registers);
+        bytes.extend(&self.memory);
+        bytes
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::{Snapshot, HEADER_SIZE};
+
+    // A 6128 snapshot with a distinct value in every register, pen 1 set to bright white (26),
+    // mode 1 with the upper ROM disabled, RAM configuration 1 (the second 64K's last bank at
+    // #C000) and CRTC R12 #10. #1234 holds #5A, and #A5 is the first byte of that last bank.
+    pub fn crafted_snapshot() -> Vec<u8> {
+        let mut bytes = vec![0; HEADER_SIZE + 128 * 1024];
+        bytes[..8].copy_from_slice(b"MV - SNA");
+        bytes[0x10] = 1;
+        bytes[0x11..0x19].copy_from_slice(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]); // F A C B E D L H
+        bytes[0x19] = 0x09; // R
+        bytes[0x1A] = 0x0A; // I
+        bytes[0x1B] = 1;
+        bytes[0x1C] = 1;
+        bytes[0x1D..0x25].copy_from_slice(&[0x34, 0x12, 0x78, 0x56, 0xF0, 0xBF, 0x00, 0x40]); // IX IY SP PC
+        bytes[0x25] = 1;
+        bytes[0x26..0x2E].copy_from_slice(&[0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18]);
+        bytes[0x2E] = 1;
+        bytes[0x30] = 26;
+        bytes[0x40] = 0x89;
+        bytes[0x41] = 1;
+        bytes[0x43 + 12] = 0x10;
+        bytes[0x55] = 7;
+        bytes[0x59] = 0x82;
+        bytes[0x5B + 8] = 0x0F;
+        bytes[0x6B..0x6D].copy_from_slice(&128u16.to_le_bytes());
+        bytes[HEADER_SIZE + 0x1234] = 0x5A;
+        bytes[HEADER_SIZE + 0x1C000] = 0xA5;
+        bytes
+    }
+
+    #[test]
+    fn header_fields_are_parsed() {
+        let snapshot = Snapshot::from_bytes(&crafted_snapshot()).unwrap();
+        assert_eq!(snapshot.af, 0x0201);
+        assert_eq!(snapshot.hl, 0x0807);
+        assert_eq!((snapshot.ix, snapshot.iy, snapshot.sp, snapshot.pc), (0x1234, 0x5678, 0xBFF0, 0x4000));
+        assert_eq!(snapshot.hl_, 0x1817);
+        assert_eq!(snapshot.inks[1], 26);
+        assert_eq!(snapshot.crtc_registers[12], 0x10);
+        assert_eq!(snapshot.psg_registers[8], 0x0F);
+        assert_eq!(snapshot.memory.len(), 0x20000);
+    }
+
+    #[test]
+    fn bad_snapshots_are_rejected() {
+        let mut bytes = crafted_snapshot();
+        assert_eq!(Snapshot::from_bytes(&bytes[..0x10100]), Err("SNA memory dump is truncated"));
+        bytes[0x6B] = 0;
+        assert_eq!(Snapshot::from_bytes(&bytes), Err("Compressed SNA memory isn't supported"));
+        bytes[0] = b'X';
+        assert_eq!(Snapshot::from_bytes(&bytes), Err("Invalid SNA format"));
+    }
+}
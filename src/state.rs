@@ -0,0 +1,553 @@
+use std::fmt;
+
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use serde::de::{self, DeserializeOwned, DeserializeSeed, Visitor, SeqAccess, MapAccess, EnumAccess, VariantAccess, IntoDeserializer};
+use serde::ser::{self, SerializeSeq, SerializeTuple, SerializeTupleStruct, SerializeTupleVariant, SerializeMap, SerializeStruct, SerializeStructVariant};
+
+use crate::runtime::Runtime;
+use crate::memory::{Memory, Registers, AddressBus, UpperRomSelect};
+use crate::gate_array::GateArray;
+use crate::crtc::Crtc;
+use crate::ppi::Ppi;
+use crate::fdc::Fdc;
+
+///////////////////////
+//
+// State - save and restore everything the emulated machine holds
+//
+///////////////////////
+
+// Unlike an SNA snapshot, this keeps every detail: paged ROMs, the disc in the drive, the PSG's
+// sound generators, a pending EI and so on. The instruction set, the bus wiring and host-side
+// pacing and audio aren't state and are rebuilt fresh.
+//
+// The format is this emulator's own and only meant to be read back by the same build: every
+// field in declaration order, integers little endian at their full width, and sequences, maps
+// and strings prefixed with a u64 length, much as bincode does.
+
+// Both halves have the same fields in the same order, which is all the format relies on.
+#[derive(Serialize)]
+struct StateRef<'a> {
+    registers: &'a Registers,
+    memory: &'a Memory,
+    address_bus: &'a AddressBus,
+    gate_array: &'a GateArray,
+    crtc: &'a Crtc,
+    ppi: &'a Ppi,
+    rom_select: &'a UpperRomSelect,
+    fdc: &'a Fdc
+}
+
+#[derive(Deserialize)]
+struct State {
+    registers: Registers,
+    memory: Memory,
+    address_bus: AddressBus,
+    gate_array: GateArray,
+    crtc: Crtc,
+    ppi: Ppi,
+    rom_select: UpperRomSelect,
+    fdc: Fdc
+}
+
+impl Runtime {
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        let state = StateRef {
+            registers: &self.components.registers,
+            memory: &self.components.mem,
+            address_bus: &self.components.address_bus,
+            gate_array: &self.gate_array.borrow(),
+            crtc: &self.crtc.borrow(),
+            ppi: &self.ppi.borrow(),
+            rom_select: &self.rom_select.borrow(),
+            fdc: &self.fdc.borrow()
+        };
+        encode(&state).expect("Runtime state is always encodable")
+    }
+
+    // A runtime in exactly the state to_snapshot saved.
+    pub fn from_snapshot(bytes: &[u8]) -> Result<Runtime, StateError> {
+        let state: State = decode(bytes)?;
+        let mut runtime = Runtime::default();
+        runtime.components.registers = state.registers;
+        runtime.components.mem = state.memory;
+        runtime.components.address_bus = state.address_bus;
+        // The devices are already attached to the bus, so fill them in rather than replacing them.
+        *runtime.gate_array.borrow_mut() = state.gate_array;
+        *runtime.crtc.borrow_mut() = state.crtc;
+        *runtime.ppi.borrow_mut() = state.ppi;
+        *runtime.rom_select.borrow_mut() = state.rom_select;
+        *runtime.fdc.borrow_mut() = state.fdc;
+        Ok(runtime)
+    }
+}
+
+// For boxed byte arrays longer than serde handles itself, such as the 64K of base RAM. The array
+// is built on the heap, as a 64K value passed up through the deserializer's calls could overflow
+// the stack. Used with #[serde(with = "crate::state::byte_array")].
+pub mod byte_array {
+    use std::fmt;
+    use serde::{Serializer, Deserializer};
+    use serde::de::{self, Visitor, SeqAccess};
+
+    pub fn serialize<S: Serializer, const N: usize>(bytes: &[u8; N], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&bytes[..])
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(deserializer: D) -> Result<Box<[u8; N]>, D::Error> {
+        deserializer.deserialize_bytes(ByteArrayVisitor::<N>)
+    }
+
+    struct ByteArrayVisitor<const N: usize>;
+
+    impl<'de, const N: usize> Visitor<'de> for ByteArrayVisitor<N> {
+        type Value = Box<[u8; N]>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{} bytes", N)
+        }
+
+        fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Box<[u8; N]>, E> {
+            bytes.to_vec().into_boxed_slice().try_into().map_err(|_| E::invalid_length(bytes.len(), &self))
+        }
+
+        // Self-describing formats without a bytes type, such as JSON, give a sequence instead.
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Box<[u8; N]>, A::Error> {
+            let mut bytes = Vec::with_capacity(N);
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
+            }
+            let length = bytes.len();
+            bytes.into_boxed_slice().try_into().map_err(|_| de::Error::invalid_length(length, &self))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct StateError(String);
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for StateError {}
+
+impl ser::Error for StateError {
+    fn custom<T: fmt::Display>(msg: T) -> StateError {
+        StateError(msg.to_string())
+    }
+}
+
+impl de::Error for StateError {
+    fn custom<T: fmt::Display>(msg: T) -> StateError {
+        StateError(msg.to_string())
+    }
+}
+
+pub fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, StateError> {
+    let mut encoder = Encoder { bytes: Vec::new() };
+    value.serialize(&mut encoder)?;
+    Ok(encoder.bytes)
+}
+
+// Fails if bytes are left over, as well as if there aren't enough.
+pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StateError> {
+    let mut decoder = Decoder { bytes };
+    let value = T::deserialize(&mut decoder)?;
+    if !decoder.bytes.is_empty() {
+        return Err(StateError(format!("{} bytes left over", decoder.bytes.len())));
+    }
+    Ok(value)
+}
+
+struct Encoder {
+    bytes: Vec<u8>
+}
+
+impl Encoder {
+    fn length(&mut self, length: Option<usize>) -> Result<(), StateError> {
+        let length = length.ok_or_else(|| StateError("Sequences need a known length".to_string()))?;
+        self.bytes.extend((length as u64).to_le_bytes());
+        Ok(())
+    }
+}
+
+impl Serializer for &mut Encoder {
+    type Ok = ();
+    type Error = StateError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), StateError> { self.serialize_u8(v as u8) }
+    fn serialize_i8(self, v: i8) -> Result<(), StateError> { self.bytes.extend(v.to_le_bytes()); Ok(()) }
+    fn serialize_i16(self, v: i16) -> Result<(), StateError> { self.bytes.extend(v.to_le_bytes()); Ok(()) }
+    fn serialize_i32(self, v: i32) -> Result<(), StateError> { self.bytes.extend(v.to_le_bytes()); Ok(()) }
+    fn serialize_i64(self, v: i64) -> Result<(), StateError> { self.bytes.extend(v.to_le_bytes()); Ok(()) }
+    fn serialize_u8(self, v: u8) -> Result<(), StateError> { self.bytes.push(v); Ok(()) }
+    fn serialize_u16(self, v: u16) -> Result<(), StateError> { self.bytes.extend(v.to_le_bytes()); Ok(()) }
+    fn serialize_u32(self, v: u32) -> Result<(), StateError> { self.bytes.extend(v.to_le_bytes()); Ok(()) }
+    fn serialize_u64(self, v: u64) -> Result<(), StateError> { self.bytes.extend(v.to_le_bytes()); Ok(()) }
+    fn serialize_f32(self, v: f32) -> Result<(), StateError> { self.bytes.extend(v.to_le_bytes()); Ok(()) }
+    fn serialize_f64(self, v: f64) -> Result<(), StateError> { self.bytes.extend(v.to_le_bytes()); Ok(()) }
+    fn serialize_char(self, v: char) -> Result<(), StateError> { self.serialize_u32(v as u32) }
+    fn serialize_str(self, v: &str) -> Result<(), StateError> { self.serialize_bytes(v.as_bytes()) }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), StateError> {
+        self.length(Some(v.len()))?;
+        self.bytes.extend(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), StateError> { self.serialize_u8(0) }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), StateError> {
+        self.serialize_u8(1)?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), StateError> { Ok(()) }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), StateError> { Ok(()) }
+
+    fn serialize_unit_variant(self, _name: &'static str, index: u32, _variant: &'static str) -> Result<(), StateError> {
+        self.serialize_u32(index)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self, _name: &'static str, value: &T) -> Result<(), StateError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(self, _name: &'static str, index: u32, _variant: &'static str, value: &T) -> Result<(), StateError> {
+        self.serialize_u32(index)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, length: Option<usize>) -> Result<Self, StateError> {
+        self.length(length)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _length: usize) -> Result<Self, StateError> { Ok(self) }
+    fn serialize_tuple_struct(self, _name: &'static str, _length: usize) -> Result<Self, StateError> { Ok(self) }
+
+    fn serialize_tuple_variant(self, _name: &'static str, index: u32, _variant: &'static str, _length: usize) -> Result<Self, StateError> {
+        self.serialize_u32(index)?;
+        Ok(self)
+    }
+
+    fn serialize_map(self, length: Option<usize>) -> Result<Self, StateError> {
+        self.length(length)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _length: usize) -> Result<Self, StateError> { Ok(self) }
+
+    fn serialize_struct_variant(self, _name: &'static str, index: u32, _variant: &'static str, _length: usize) -> Result<Self, StateError> {
+        self.serialize_u32(index)?;
+        Ok(self)
+    }
+}
+
+impl SerializeSeq for &mut Encoder {
+    type Ok = ();
+    type Error = StateError;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), StateError> { value.serialize(&mut **self) }
+    fn end(self) -> Result<(), StateError> { Ok(()) }
+}
+
+impl SerializeTuple for &mut Encoder {
+    type Ok = ();
+    type Error = StateError;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), StateError> { value.serialize(&mut **self) }
+    fn end(self) -> Result<(), StateError> { Ok(()) }
+}
+
+impl SerializeTupleStruct for &mut Encoder {
+    type Ok = ();
+    type Error = StateError;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), StateError> { value.serialize(&mut **self) }
+    fn end(self) -> Result<(), StateError> { Ok(()) }
+}
+
+impl SerializeTupleVariant for &mut Encoder {
+    type Ok = ();
+    type Error = StateError;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), StateError> { value.serialize(&mut **self) }
+    fn end(self) -> Result<(), StateError> { Ok(()) }
+}
+
+impl SerializeMap for &mut Encoder {
+    type Ok = ();
+    type Error = StateError;
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), StateError> { key.serialize(&mut **self) }
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), StateError> { value.serialize(&mut **self) }
+    fn end(self) -> Result<(), StateError> { Ok(()) }
+}
+
+impl SerializeStruct for &mut Encoder {
+    type Ok = ();
+    type Error = StateError;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, _key: &'static str, value: &T) -> Result<(), StateError> { value.serialize(&mut **self) }
+    fn end(self) -> Result<(), StateError> { Ok(()) }
+}
+
+impl SerializeStructVariant for &mut Encoder {
+    type Ok = ();
+    type Error = StateError;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, _key: &'static str, value: &T) -> Result<(), StateError> { value.serialize(&mut **self) }
+    fn end(self) -> Result<(), StateError> { Ok(()) }
+}
+
+struct Decoder<'de> {
+    bytes: &'de [u8]
+}
+
+impl<'de> Decoder<'de> {
+    fn take(&mut self, count: usize) -> Result<&'de [u8], StateError> {
+        if count > self.bytes.len() {
+            return Err(StateError("Unexpected end of state".to_string()));
+        }
+        let (taken, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], StateError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn length(&mut self) -> Result<usize, StateError> {
+        Ok(u64::from_le_bytes(self.take_array()?) as usize)
+    }
+}
+
+// The data doesn't say what type it is, so every value has to be asked for by type.
+impl<'de> Deserializer<'de> for &mut Decoder<'de> {
+    type Error = StateError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, StateError> {
+        Err(StateError("State can only be decoded into known types".to_string()))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> {
+        match self.take(1)?[0] {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            other => Err(StateError(format!("Invalid bool {}", other)))
+        }
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> { visitor.visit_i8(i8::from_le_bytes(self.take_array()?)) }
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> { visitor.visit_i16(i16::from_le_bytes(self.take_array()?)) }
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> { visitor.visit_i32(i32::from_le_bytes(self.take_array()?)) }
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> { visitor.visit_i64(i64::from_le_bytes(self.take_array()?)) }
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> { visitor.visit_u8(self.take(1)?[0]) }
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> { visitor.visit_u16(u16::from_le_bytes(self.take_array()?)) }
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> { visitor.visit_u32(u32::from_le_bytes(self.take_array()?)) }
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> { visitor.visit_u64(u64::from_le_bytes(self.take_array()?)) }
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> { visitor.visit_f32(f32::from_le_bytes(self.take_array()?)) }
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> { visitor.visit_f64(f64::from_le_bytes(self.take_array()?)) }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> {
+        let value = u32::from_le_bytes(self.take_array()?);
+        visitor.visit_char(char::from_u32(value).ok_or_else(|| StateError(format!("Invalid char {}", value)))?)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> {
+        let length = self.length()?;
+        let text = std::str::from_utf8(self.take(length)?).map_err(|e| StateError(e.to_string()))?;
+        visitor.visit_borrowed_str(text)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> { self.deserialize_str(visitor) }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> {
+        let length = self.length()?;
+        visitor.visit_borrowed_bytes(self.take(length)?)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> { self.deserialize_bytes(visitor) }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> {
+        match self.take(1)?[0] {
+            0 => visitor.visit_none(),
+            1 => visitor.visit_some(self),
+            other => Err(StateError(format!("Invalid option tag {}", other)))
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> { visitor.visit_unit() }
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, StateError> { visitor.visit_unit() }
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, StateError> { visitor.visit_newtype_struct(self) }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> {
+        let remaining = self.length()?;
+        visitor.visit_seq(Elements { decoder: self, remaining })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, length: usize, visitor: V) -> Result<V::Value, StateError> {
+        visitor.visit_seq(Elements { decoder: self, remaining: length })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(self, _name: &'static str, length: usize, visitor: V) -> Result<V::Value, StateError> {
+        self.deserialize_tuple(length, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> {
+        let remaining = self.length()?;
+        visitor.visit_map(Elements { decoder: self, remaining })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, _name: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value, StateError> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, StateError> {
+        visitor.visit_enum(self)
+    }
+
+    // Only enum variants are identified, and those are written as their index.
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> { self.deserialize_u32(visitor) }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, StateError> { self.deserialize_any(visitor) }
+}
+
+// The elements of a sequence, tuple, struct or map: count of them, one after another.
+struct Elements<'a, 'de> {
+    decoder: &'a mut Decoder<'de>,
+    remaining: usize
+}
+
+impl<'a, 'de> SeqAccess<'de> for Elements<'a, 'de> {
+    type Error = StateError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, StateError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.decoder).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'a, 'de> MapAccess<'de> for Elements<'a, 'de> {
+    type Error = StateError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, StateError> {
+        self.next_element_seed(seed)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, StateError> {
+        seed.deserialize(&mut *self.decoder)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> EnumAccess<'de> for &mut Decoder<'de> {
+    type Error = StateError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), StateError> {
+        let index = u32::from_le_bytes(self.take_array()?);
+        let value = seed.deserialize(index.into_deserializer())?;
+        Ok((value, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for &mut Decoder<'de> {
+    type Error = StateError;
+
+    fn unit_variant(self) -> Result<(), StateError> { Ok(()) }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, StateError> { seed.deserialize(self) }
+
+    fn tuple_variant<V: Visitor<'de>>(self, length: usize, visitor: V) -> Result<V::Value, StateError> {
+        self.deserialize_tuple(length, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value, StateError> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use serde::{Serialize, Deserialize};
+
+    use crate::runtime::Runtime;
+    use crate::memory::Register;
+    use super::{encode, decode, StateError};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Empty,
+        Circle(u16),
+        Rect { width: u8, height: u8 }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Sample {
+        flag: bool,
+        name: String,
+        shapes: Vec<Shape>,
+        lookup: BTreeMap<u8, Vec<u8>>,
+        missing: Option<i32>,
+        #[serde(with = "super::byte_array")]
+        big: Box<[u8; 40]>
+    }
+
+    #[test]
+    fn codec_round_trips_values() {
+        let sample = Sample {
+            flag: true,
+            name: "CPC".to_string(),
+            shapes: vec![Shape::Empty, Shape::Circle(0x1234), Shape::Rect { width: 3, height: 4 }],
+            lookup: BTreeMap::from([(7, vec![1, 2])]),
+            missing: None,
+            big: Box::new([0xAA; 40])
+        };
+        let bytes = encode(&sample).unwrap();
+        assert_eq!(bytes[..4], [1, 3, 0, 0]);
+        assert_eq!(decode::<Sample>(&bytes).unwrap(), sample);
+        assert_eq!(decode::<Sample>(&bytes[..10]), Err(StateError("Unexpected end of state".to_string())));
+    }
+
+    #[test]
+    fn runtime_state_round_trips_mid_program() {
+        // LD SP,#C000; LD A,#00; loop: INC A; PUSH AF; POP BC; LD (#4000),A; JR loop
+        let mut runtime = Runtime::default();
+        runtime.components.mem.locations[..14].copy_from_slice(&[0x31, 0x00, 0xC0, 0x3E, 0x00, 0x3C, 0xF5, 0xC1, 0x32, 0x00, 0x40, 0x18, 0xF8, 0x00]);
+        runtime.components.registers.pc.set(0x0000);
+        runtime.ppi.borrow_mut().set_key(8, 5, true);
+        for _ in 0..1000 {
+            runtime.step().unwrap();
+        }
+
+        let saved = runtime.to_snapshot();
+        let mut restored = Runtime::from_snapshot(&saved).unwrap();
+        assert_eq!(restored.to_snapshot(), saved);
+        assert_eq!(restored.components.registers.a.get(), runtime.components.registers.a.get());
+        assert_eq!(restored.ppi.borrow().keyboard_row(8), !0b0010_0000);
+
+        // Both carry on identically.
+        for _ in 0..1000 {
+            runtime.step().unwrap();
+            restored.step().unwrap();
+        }
+        assert_eq!(restored.to_snapshot(), runtime.to_snapshot());
+    }
+}
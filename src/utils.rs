@@ -19,10 +19,15 @@ pub fn signed(value: u8) -> i8 {
    value as i8
 }
 
+// True when the byte has an even number of set bits, as reported by the Z80's P/V flag.
+pub fn parity(value: u8) -> bool {
+    value.count_ones() & 1 == 0
+}
+
 
 #[cfg(test)]
 mod tests {
-    use super::{split_double_byte, signed};
+    use super::{split_double_byte, signed, parity};
     
     #[test]
     fn test_split_double_byte() {
@@ -39,4 +44,13 @@ mod tests {
         assert!(signed_minus_5 == -5);
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_parity() {
+        assert!(parity(0x00));
+        assert!(parity(0x03));
+        assert!(!parity(0x01));
+        assert!(!parity(0x7F));
+        assert!(parity(0xFF));
+    }
+
+}